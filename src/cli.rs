@@ -5,6 +5,18 @@ use clap::{Parser, Subcommand};
 #[command(about = "タスク管理ツール", long_about = None)]
 #[command(version)]
 pub struct Cli {
+    /// 表示言語（ja, en）。省略時は設定ファイルの lang、環境変数 LANG の順に決定
+    #[arg(long, global = true, value_name = "LANG")]
+    pub lang: Option<String>,
+    
+    /// 色を付けるか（auto: 端末に出力するときのみ、NO_COLOR / CLICOLOR_FORCE も考慮）
+    #[arg(long, global = true, value_name = "WHEN", value_parser = ["auto", "always", "never"])]
+    pub color: Option<String>,
+    
+    /// 状態の記号を ASCII 文字のみで表示（例: [✓] の代わりに [x]）
+    #[arg(long, global = true)]
+    pub ascii: bool,
+    
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -32,11 +44,43 @@ pub enum Commands {
         /// タスクのタグ（カンマ区切り）
         #[arg(short = 'g', long)]
         tags: Option<String>,
+        
+        /// タスクのプロジェクト
+        #[arg(long)]
+        project: Option<String>,
+        
+        /// カスタムフィールドの値（KEY=VALUE 形式、複数指定可）
+        #[arg(short = 's', long = "set", value_name = "KEY=VALUE")]
+        set: Vec<String>,
+        
+        /// 見積もり時間（例: 3h, 1h30m）
+        #[arg(short, long)]
+        estimate: Option<String>,
+        
+        /// 先に完了する必要があるタスクのID（カンマ区切り）
+        #[arg(long, value_name = "IDS")]
+        depends_on: Option<String>,
+        
+        /// 作業の開始予定日時（YYYY-MM-DD または YYYY-MM-DD HH:MM 形式）
+        #[arg(long, visible_alias = "scheduled")]
+        start: Option<String>,
+        
+        /// この日時まで一覧・next・agenda に表示しない
+        #[arg(short, long)]
+        wait: Option<String>,
     },
     
     /// タスクの一覧を表示
     #[command(visible_alias = "ls")]
     List {
+        /// 保存した表示条件（@名前）。指定したオプションが優先され、--where は両方の条件を満たすタスク
+        #[arg(value_name = "@VIEW")]
+        view: Option<String>,
+        
+        /// 絞り込みの条件（例: "tag:work priority:高 due:overdue"、複数指定するとすべてを満たすタスク）
+        #[arg(long = "where", value_name = "CONDITION")]
+        filter: Vec<String>,
+        
         /// 完了済みタスクも含めて表示
         #[arg(short, long)]
         all: bool,
@@ -52,6 +96,175 @@ pub enum Commands {
         /// タグでフィルタリング
         #[arg(short, long)]
         tags: Option<String>,
+        
+        /// カスタムフィールドでフィルタリング（例: story_points>=3、複数指定可）
+        #[arg(short, long, value_name = "CONDITION")]
+        field: Vec<String>,
+        
+        /// 並び替えキー（id, title, priority, due, created, urgency またはカスタムフィールド名。'-' を前に付けると逆順）
+        #[arg(long, value_name = "KEY", allow_hyphen_values = true)]
+        sort: Option<String>,
+        
+        /// 表示したタスクの残り見積もり時間の合計を表示
+        #[arg(long)]
+        effort: bool,
+        
+        /// 待機中のタスクも表示
+        #[arg(short, long)]
+        waiting: bool,
+        
+        /// 表示形式（line, table）
+        #[arg(long, value_name = "FORMAT")]
+        format: Option<String>,
+        
+        /// 表形式で表示する列（カンマ区切り、例: id,status,priority,title,tags,due,created,urgency）
+        #[arg(long, value_name = "COLUMNS")]
+        columns: Option<String>,
+        
+        /// 設定ファイルの [templates] に定義したテンプレート名（またはテンプレートそのもの）
+        #[arg(long, value_name = "NAME")]
+        template: Option<String>,
+    },
+    
+    /// 緊急度の高い順に次にやるべきタスクを表示（ブロック中のタスクは除く）
+    #[command(visible_alias = "n")]
+    Next {
+        /// 表示する件数
+        #[arg(default_value = "3")]
+        count: usize,
+    },
+    
+    /// 期限切れ・今日・明日・日ごと・期限なしに分けて未完了タスクを表示
+    #[command(visible_alias = "ag")]
+    Agenda {
+        /// 表示する日数（1〜3660、省略時は今週の終わりまで）
+        #[arg(short, long, value_parser = clap::value_parser!(i64).range(1..=3660))]
+        days: Option<i64>,
+    },
+    
+    /// 月または週のカレンダーに期限のタスクを表示
+    #[command(visible_alias = "cal")]
+    Calendar {
+        /// 表示する月（YYYY-MM、--week の場合は週に含まれる日付 YYYY-MM-DD）
+        date: Option<String>,
+        
+        /// 週表示（日ごとの列にタスク名を表示）
+        #[arg(short, long)]
+        week: bool,
+    },
+    
+    /// ステータス・タグ・優先度を列にしたカンバンボードを表示
+    #[command(visible_alias = "b")]
+    Board {
+        /// 列の種類（status, tag, priority）
+        #[arg(short, long, default_value = "status")]
+        by: String,
+        
+        /// 完了したタスクも表示
+        #[arg(short, long)]
+        all: bool,
+    },
+    
+    /// タスクをボードの別の列に移動
+    #[command(visible_alias = "mv")]
+    Move {
+        /// タスクID
+        id: i64,
+        
+        /// 移動先の列（ステータス名、タグ名、優先度）
+        column: String,
+        
+        /// 列の種類（status, tag, priority）
+        #[arg(short, long, default_value = "status")]
+        by: String,
+    },
+    
+    /// 開始日・期限のあるタスクをガントチャートで表示
+    #[command(visible_alias = "tl")]
+    Timeline {
+        /// 表示の開始日（省略時は今週の始まり）
+        #[arg(long)]
+        from: Option<String>,
+        
+        /// 表示の終了日（省略時は開始日から 4 週間）
+        #[arg(long)]
+        to: Option<String>,
+        
+        /// 完了済みタスクも表示
+        #[arg(short, long)]
+        all: bool,
+    },
+    
+    /// タスクの待機日時を延ばして一時的に非表示にする
+    Snooze {
+        /// タスクID
+        id: i64,
+        
+        /// 延ばす期間（例: 3d, 2h, 1h30m）
+        duration: String,
+    },
+    
+    /// タスクに日時付きのメモを追加（show で古い順に表示）
+    Note {
+        /// タスクID
+        id: i64,
+        
+        /// メモの内容
+        message: String,
+    },
+    
+    /// メモを全文検索（一致したメモを関連度の高い順に表示）
+    Search {
+        /// 検索する語（すべての語を含むメモを表示）
+        #[arg(required = true)]
+        query: Vec<String>,
+    },
+    
+    /// ファイルをタスクに添付（既定ではデータの保存先にコピー）
+    Attach {
+        /// タスクID
+        id: i64,
+        
+        /// 添付するファイル
+        file: String,
+        
+        /// コピーせず元の場所のファイルを参照する
+        #[arg(long)]
+        no_copy: bool,
+    },
+    
+    /// URL をタスクに関連付け
+    Link {
+        /// タスクID
+        id: i64,
+        
+        /// URL（https://... など）
+        url: String,
+    },
+    
+    /// 添付したファイル・URL を既定のアプリケーションで開く
+    Open {
+        /// タスクID
+        id: i64,
+        
+        /// 添付の番号（1 から、show で確認。省略時は 1）
+        n: Option<usize>,
+    },
+    
+    /// 添付したファイル・URL を外す
+    Detach {
+        /// タスクID
+        id: i64,
+        
+        /// 添付の番号（1 から）
+        n: usize,
+    },
+    
+    /// どのタスクからも参照されていないコピーした添付ファイルを削除
+    CleanAttachments {
+        /// 削除するファイルを表示するだけで削除しない
+        #[arg(long)]
+        dry_run: bool,
     },
     
     /// 特定のタスクの詳細を表示
@@ -64,11 +277,36 @@ pub enum Commands {
     /// タスクを更新
     #[command(visible_alias = "u")]
     Update {
-        /// 更新するタスクのID
-        id: i64,
+        /// 更新するタスクのID（3 5 8-12 のように複数・範囲を指定可）
+        #[arg(required_unless_present = "filter", value_name = "IDS")]
+        ids: Vec<String>,
         
-        /// 新しいタイトル
+        /// 対象のタスクを絞り込む条件（例: "tag:sprint-41 priority:高"）
+        #[arg(long = "where", value_name = "CONDITION")]
+        filter: Option<String>,
+        
+        /// 実行せずに対象を表示
+        #[arg(long)]
+        dry_run: bool,
+        
+        /// 確認せずに実行
         #[arg(short, long)]
+        yes: bool,
+        
+        /// 追加するタグ（カンマ区切り、複数指定可）
+        #[arg(long, value_name = "TAGS")]
+        add_tag: Vec<String>,
+        
+        /// 削除するタグ（カンマ区切り、複数指定可）
+        #[arg(long, value_name = "TAGS")]
+        remove_tag: Vec<String>,
+        
+        /// タグをすべて削除（--add-tag と組み合わせると置き換え）
+        #[arg(long)]
+        clear_tags: bool,
+        
+        /// 新しいタイトル
+        #[arg(long)]
         title: Option<String>,
         
         /// 新しい説明
@@ -88,33 +326,128 @@ pub enum Commands {
         priority: Option<i32>,
         
         /// 新しいタグ（カンマ区切り）
-        #[arg(short = 'g', long)]
+        #[arg(short = 'g', long, conflicts_with = "clear_tags")]
         tags: Option<String>,
+        
+        /// 新しいプロジェクト
+        #[arg(long)]
+        project: Option<String>,
+        
+        /// プロジェクトを削除
+        #[arg(long, conflicts_with = "project")]
+        remove_project: bool,
+        
+        /// カスタムフィールドの値（KEY=VALUE 形式、空の値で削除、複数指定可）
+        #[arg(short = 's', long = "set", value_name = "KEY=VALUE")]
+        set: Vec<String>,
+        
+        /// 新しい見積もり時間（例: 3h, 1h30m）
+        #[arg(short, long)]
+        estimate: Option<String>,
+        
+        /// 見積もりを削除
+        #[arg(long)]
+        remove_estimate: bool,
+        
+        /// 先に完了する必要があるタスクのID（カンマ区切り、空文字列で削除）
+        #[arg(long, value_name = "IDS")]
+        depends_on: Option<String>,
+        
+        /// 新しい開始予定日時（YYYY-MM-DD または YYYY-MM-DD HH:MM 形式）
+        #[arg(long, visible_alias = "scheduled")]
+        start: Option<String>,
+        
+        /// 開始予定日時を削除
+        #[arg(long, visible_alias = "remove-scheduled")]
+        remove_start: bool,
+        
+        /// 新しい待機日時（この日時まで一覧・next・agenda に表示しない）
+        #[arg(short, long)]
+        wait: Option<String>,
+        
+        /// 待機日時を削除
+        #[arg(long)]
+        remove_wait: bool,
+    },
+    
+    /// タスクを $EDITOR で編集（フロントマター + Markdown の文書として編集し、差分を確認してから保存）
+    #[command(visible_alias = "e")]
+    Edit {
+        /// タスクのID
+        #[arg(required_unless_present = "new")]
+        id: Option<i64>,
+        
+        /// 新しいタスクを作成
+        #[arg(long, conflicts_with = "id")]
+        new: bool,
+        
+        /// 差分を確認せずに保存
+        #[arg(short, long)]
+        yes: bool,
     },
     
     /// タスクを完了状態に設定
-    #[command(visible_alias = "c")]
+    #[command(visible_aliases = ["c", "done"])]
     Complete {
-        /// 完了するタスクのID
-        id: i64,
+        /// 完了するタスクのID（3 5 8-12 のように複数・範囲を指定可）
+        #[arg(required_unless_present = "filter", value_name = "IDS")]
+        ids: Vec<String>,
+        
+        /// 対象のタスクを絞り込む条件（例: "tag:sprint-41 priority:高"）
+        #[arg(long = "where", value_name = "CONDITION")]
+        filter: Option<String>,
+        
+        /// 実行せずに対象を表示
+        #[arg(long)]
+        dry_run: bool,
+        
+        /// 確認せずに実行
+        #[arg(short, long)]
+        yes: bool,
     },
     
     /// タスクを未完了状態に設定
     #[command(visible_alias = "uc")]
     Uncomplete {
-        /// 未完了に戻すタスクのID
-        id: i64,
+        /// 未完了に戻すタスクのID（3 5 8-12 のように複数・範囲を指定可）
+        #[arg(required_unless_present = "filter", value_name = "IDS")]
+        ids: Vec<String>,
+        
+        /// 対象のタスクを絞り込む条件（例: "tag:sprint-41 priority:高"）
+        #[arg(long = "where", value_name = "CONDITION")]
+        filter: Option<String>,
+        
+        /// 実行せずに対象を表示
+        #[arg(long)]
+        dry_run: bool,
+        
+        /// 確認せずに実行
+        #[arg(short, long)]
+        yes: bool,
     },
     
     /// タスクを削除
     #[command(visible_alias = "rm")]
     Delete {
-        /// 削除するタスクのID（指定しない場合は --completed が必要）
-        id: Option<i64>,
+        /// 削除するタスクのID（3 5 8-12 のように複数・範囲を指定可。指定しない場合は --where か --completed が必要）
+        #[arg(value_name = "IDS")]
+        ids: Vec<String>,
         
         /// 完了済みタスクをすべて削除
         #[arg(short, long)]
         completed: bool,
+        
+        /// 対象のタスクを絞り込む条件（例: "tag:sprint-41 priority:高"）
+        #[arg(long = "where", value_name = "CONDITION")]
+        filter: Option<String>,
+        
+        /// 実行せずに対象を表示
+        #[arg(long)]
+        dry_run: bool,
+        
+        /// 確認せずに実行
+        #[arg(short, long)]
+        yes: bool,
     },
     
     /// タスクの統計情報を表示
@@ -136,4 +469,279 @@ pub enum Commands {
         /// 入力ファイル名
         file: String,
     },
-} 
+    
+    /// カスタムフィールドを管理
+    Field {
+        #[command(subcommand)]
+        action: FieldCommands,
+    },
+    
+    /// 一覧の表示条件を名前を付けて保存（list @名前 で呼び出す）
+    View {
+        #[command(subcommand)]
+        action: ViewCommands,
+    },
+    
+    /// コンテキスト（list・next・agenda・stats に適用し、add にタグを付ける表示条件）を管理
+    Context {
+        #[command(subcommand)]
+        action: ContextCommands,
+    },
+    
+    /// タスク内のチェックリストを編集
+    Check {
+        #[command(subcommand)]
+        action: CheckCommands,
+    },
+    
+    /// タスクとその依存先をテンプレートとして保存し、まとめて作成
+    Template {
+        #[command(subcommand)]
+        action: TemplateCommands,
+    },
+    
+    /// タイマーで作業時間を記録
+    Timer {
+        #[command(subcommand)]
+        action: TimerCommands,
+    },
+    
+    /// 作業時間を手動で記録
+    LogTime {
+        /// タスクのID
+        id: i64,
+        
+        /// 作業時間（例: 1h30m, 45m）
+        duration: String,
+        
+        /// 作業の開始日時（YYYY-MM-DD または YYYY-MM-DD HH:MM 形式、省略時は現在時刻に終了したものとする）
+        #[arg(short, long)]
+        date: Option<String>,
+    },
+    
+    /// ポモドーロで集中して作業（カウントダウン中は p/r/i/s/q + Enter で操作）
+    Focus {
+        /// タスクのID
+        id: i64,
+        
+        /// 1 ポモドーロの作業時間（分）
+        #[arg(short, long, default_value = "25")]
+        minutes: u32,
+        
+        /// 休憩時間（分）
+        #[arg(short, long = "break", default_value = "5")]
+        break_minutes: u32,
+        
+        /// ポモドーロの回数
+        #[arg(short, long, default_value = "4")]
+        rounds: u32,
+    },
+    
+    /// 全画面の対話的なインターフェースで操作（キー操作は画面下部に表示）
+    Tui,
+    
+    /// 対話的にコマンドを実行（履歴・補完・コンテキストに対応し、データベースの接続を使い回す）
+    Shell,
+    
+    /// レポートを表示
+    Report {
+        #[command(subcommand)]
+        kind: ReportCommands,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum FieldCommands {
+    /// カスタムフィールドを定義
+    Define {
+        /// フィールド名
+        name: String,
+        
+        /// フィールドの型（string, number, date, enum, url）
+        #[arg(short = 'y', long = "type", default_value = "string")]
+        field_type: String,
+        
+        /// enum 型の選択肢（カンマ区切り）
+        #[arg(short, long)]
+        values: Option<String>,
+    },
+    
+    /// カスタムフィールドの一覧を表示
+    #[command(visible_alias = "ls")]
+    List,
+    
+    /// カスタムフィールドを削除（設定済みの値も削除されます）
+    #[command(visible_alias = "rm")]
+    Remove {
+        /// フィールド名
+        name: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ViewCommands {
+    /// 表示条件を保存（同じ名前があれば置き換え）
+    Save {
+        /// 名前
+        name: String,
+        
+        /// 絞り込みの条件（list --where と同じ形式）
+        #[arg(value_name = "CONDITION")]
+        filter: Option<String>,
+        
+        /// 並び替えキー（'-' を前に付けると逆順）
+        #[arg(long, value_name = "KEY", allow_hyphen_values = true)]
+        sort: Option<String>,
+        
+        /// 表示形式（line, table）
+        #[arg(long, value_name = "FORMAT")]
+        format: Option<String>,
+        
+        /// 表形式で表示する列（カンマ区切り、指定すると表形式）
+        #[arg(long, value_name = "COLUMNS")]
+        columns: Option<String>,
+        
+        /// 完了済みタスクも含める
+        #[arg(short, long)]
+        all: bool,
+    },
+    
+    /// 保存した表示条件の一覧を表示
+    #[command(visible_alias = "ls")]
+    List,
+    
+    /// 表示条件を削除
+    #[command(visible_alias = "rm")]
+    Remove {
+        /// 名前
+        name: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ContextCommands {
+    /// コンテキストを設定
+    Set {
+        /// 表示条件の名前
+        name: String,
+        
+        /// 絞り込みの条件（指定すると同じ名前の表示条件として保存。例: "tag:work or tag:alpha"）
+        #[arg(value_name = "CONDITION")]
+        filter: Option<String>,
+    },
+    
+    /// コンテキストを解除
+    #[command(name = "none")]
+    Clear,
+    
+    /// 有効なコンテキストを表示
+    Show,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CheckCommands {
+    /// チェックリストに項目を追加
+    Add {
+        /// タスクID
+        id: i64,
+        
+        /// 項目の内容
+        text: String,
+    },
+    
+    /// 項目の完了・未完了を切り替え
+    Toggle {
+        /// タスクID
+        id: i64,
+        
+        /// 項目の番号（1 から）
+        n: usize,
+    },
+    
+    /// 項目を削除
+    #[command(visible_alias = "rm")]
+    Remove {
+        /// タスクID
+        id: i64,
+        
+        /// 項目の番号（1 から）
+        n: usize,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TemplateCommands {
+    /// タスクとその依存先をテンプレートとして保存（同じ名前があれば置き換え）
+    Save {
+        /// テンプレート名
+        name: String,
+        
+        /// 基準のタスクのID
+        id: i64,
+    },
+    
+    /// テンプレートからタスクを作成
+    Apply {
+        /// テンプレート名
+        name: String,
+        
+        /// 基準の期限（YYYY-MM-DD または YYYY-MM-DD HH:MM 形式、各タスクの期限はここからの差で設定）
+        #[arg(short = 't', long)]
+        due: Option<String>,
+        
+        /// タイトル・説明の {{名前}} に入れる値（KEY=VALUE 形式、複数指定可）
+        #[arg(long = "var", value_name = "KEY=VALUE")]
+        vars: Vec<String>,
+    },
+    
+    /// テンプレートの一覧を表示
+    #[command(visible_alias = "ls")]
+    List,
+    
+    /// テンプレートを削除
+    #[command(visible_alias = "rm")]
+    Remove {
+        /// テンプレート名
+        name: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TimerCommands {
+    /// タイマーを開始
+    Start {
+        /// タスクのID
+        id: i64,
+    },
+    
+    /// 実行中のタイマーを停止
+    Stop,
+    
+    /// 実行中のタイマーを表示
+    Status,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ReportCommands {
+    /// タスク・タグごとの作業時間を表示
+    Time {
+        /// 今週の作業時間を表示
+        #[arg(short, long)]
+        week: bool,
+        
+        /// 集計開始日時（YYYY-MM-DD または YYYY-MM-DD HH:MM 形式）
+        #[arg(long)]
+        from: Option<String>,
+        
+        /// 集計終了日時（YYYY-MM-DD または YYYY-MM-DD HH:MM 形式）
+        #[arg(long)]
+        to: Option<String>,
+    },
+    
+    /// 見積もりと実績（記録した作業時間または経過時間）をタグごとに比較
+    Estimate {
+        /// 未完了のタスクも含める（実績は記録した作業時間）
+        #[arg(short, long)]
+        all: bool,
+    },
+}
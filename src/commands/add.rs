@@ -1,15 +1,28 @@
+use crate::commands::context::active_context;
 use crate::db::TaskRepository;
 use crate::error::{Result, TaskError};
-use crate::models::task::{Priority, Task};
+use crate::models::field::parse_assignment;
+use crate::models::tag::parse_quick_tags;
+use crate::models::task::{parse_project, Priority, Task};
 use crate::utils::date::parse_date;
+use crate::utils::duration::parse_estimate;
+use crate::utils::ids::parse_id_list;
+use std::collections::BTreeMap;
 
 /// タスクを追加するコマンド
+#[allow(clippy::too_many_arguments)]
 pub fn add_task(
     title: String,
     description: Option<String>,
     due: Option<String>,
     priority: i32,
     tags: Option<String>,
+    project: Option<String>,
+    set: Vec<String>,
+    estimate: Option<String>,
+    depends_on: Option<String>,
+    start: Option<String>,
+    wait: Option<String>,
 ) -> Result<()> {
     // 優先度の検証
     let priority = Priority::from_i32(priority).map_err(|_| TaskError::InvalidPriority(priority))?;
@@ -20,18 +33,64 @@ pub fn add_task(
         None => None,
     };
 
-    // タグのパース
-    let tags_vec = tags
+    // 見積もりの変換
+    let estimate_minutes = match estimate {
+        Some(estimate_str) => Some(parse_estimate(&estimate_str)?),
+        None => None,
+    };
+
+    // タグのパース（有効なコンテキストのタグ・プロジェクトを加え、タイトル中の +tag / -tag で追加・削除）
+    let mut repo = TaskRepository::new()?;
+    let mut tags_vec: Vec<String> = tags
         .map(|t| t.split(',').map(|s| s.trim().to_string()).collect())
-        .unwrap_or_else(Vec::new);
+        .unwrap_or_default();
+    let mut project = project.and_then(|p| parse_project(&p));
+    if let Some(filter) = active_context(&repo)?.map(|context| context.task_filter(&repo)).transpose()?.flatten() {
+        for tag in filter.required_tags() {
+            if !tags_vec.contains(&tag) {
+                tags_vec.push(tag);
+            }
+        }
+        if project.is_none() {
+            project = filter.required_project();
+        }
+    }
+    let (title, tag_changes) = parse_quick_tags(&title);
+    if title.is_empty() {
+        return Err(TaskError::InvalidArgument(t!("add.empty_title")));
+    }
+    tag_changes.apply(&mut tags_vec);
+
+    // カスタムフィールドのパース
+    let mut custom_fields = BTreeMap::new();
+    for assignment in set {
+        let (key, value) = parse_assignment(&assignment).map_err(TaskError::InvalidArgument)?;
+        if !value.is_empty() {
+            custom_fields.insert(key, value);
+        }
+    }
 
     // タスクの作成
-    let task = Task::new(title, description, due_date, priority, tags_vec);
+    let mut task = Task::new(title, description, due_date, priority, tags_vec);
+    task.estimate_minutes = estimate_minutes;
+    task.project = project;
+    if let Some(start_str) = start {
+        task.start_date = Some(parse_date(&start_str)?);
+    }
+    if let Some(wait_str) = wait {
+        task.wait_until = Some(parse_date(&wait_str)?);
+    }
+    if task.starts_after_due() {
+        return Err(TaskError::InvalidArgument(t!("task.start_after_due")));
+    }
+    if let Some(ids) = depends_on {
+        task.depends_on = parse_id_list(&ids)?;
+    }
 
     // タスクの保存
-    let mut repo = TaskRepository::new()?;
+    task.custom_fields = repo.validate_custom_fields(&custom_fields)?;
     let task_id = repo.add_task(&task)?;
 
-    println!("タスクを追加しました（ID: {}）", task_id);
+    println!("{}", t!("add.done", id = task_id));
     Ok(())
 } 
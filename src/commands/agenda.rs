@@ -0,0 +1,140 @@
+use chrono::{Duration, Local, NaiveDate};
+use colored::Colorize;
+
+use crate::commands::context::retain_in_context;
+use crate::config::Config;
+use crate::db::TaskRepository;
+use crate::error::{Result, TaskError};
+use crate::models::task::Task;
+use crate::utils::date::{format_day, start_of_week};
+use crate::utils::format::format_task;
+use crate::utils::theme::{glyph, paint, Glyph, Role};
+
+/// --days に指定できる最大の日数（cli の範囲と同じ）
+pub const MAX_DAYS: i64 = 3660;
+
+/// アジェンダの区分
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AgendaSection {
+    Overdue,
+    Today,
+    Tomorrow,
+    Day(NaiveDate),
+    Later,
+    Someday,
+}
+
+impl AgendaSection {
+    pub fn label(&self) -> String {
+        match self {
+            AgendaSection::Overdue => t!("agenda.overdue"),
+            AgendaSection::Today => t!("agenda.today"),
+            AgendaSection::Tomorrow => t!("agenda.tomorrow"),
+            AgendaSection::Day(date) => format_day(*date),
+            AgendaSection::Later => t!("agenda.later"),
+            AgendaSection::Someday => t!("agenda.someday"),
+        }
+    }
+}
+
+/// 未完了タスクを期限日でアジェンダの区分に振り分ける
+///
+/// 今日から `last_day` までは日ごとの区分、それより後は「それ以降」になる。
+/// 空の区分は含まれない（「今日」は常に含まれる）。待機中のタスクは除外する。
+pub fn build_agenda(
+    tasks: Vec<Task>,
+    today: NaiveDate,
+    last_day: NaiveDate,
+) -> Vec<(AgendaSection, Vec<Task>)> {
+    let tomorrow = today + Duration::days(1);
+    let mut sections: Vec<(AgendaSection, Vec<Task>)> = vec![(AgendaSection::Today, Vec::new())];
+
+    for task in tasks.into_iter().filter(|t| !t.completed && !t.is_waiting()) {
+        let section = match task.due_date.map(|d| d.date_naive()) {
+            None => AgendaSection::Someday,
+            Some(date) if date < today => AgendaSection::Overdue,
+            Some(date) if date == today => AgendaSection::Today,
+            Some(date) if date == tomorrow && tomorrow <= last_day => AgendaSection::Tomorrow,
+            Some(date) if date <= last_day => AgendaSection::Day(date),
+            Some(_) => AgendaSection::Later,
+        };
+
+        match sections.iter_mut().find(|(s, _)| *s == section) {
+            Some((_, section_tasks)) => section_tasks.push(task),
+            None => sections.push((section, vec![task])),
+        }
+    }
+
+    sections.sort_by_key(|(section, _)| *section);
+    for (_, section_tasks) in sections.iter_mut() {
+        section_tasks.sort_by(|a, b| {
+            a.due_date
+                .cmp(&b.due_date)
+                .then((b.priority as i32).cmp(&(a.priority as i32)))
+        });
+    }
+
+    sections
+}
+
+/// 期限日ごとにタスクを表示するコマンド
+pub fn show_agenda(days: Option<i64>) -> Result<()> {
+    let today = Local::now().date_naive();
+
+    // 表示する日数（省略時は今週の終わりまで、最低でも明日まで）
+    let last_day = match days {
+        Some(days) if !(1..=MAX_DAYS).contains(&days) => {
+            return Err(TaskError::InvalidArgument(t!("agenda.invalid_days", max = MAX_DAYS)));
+        }
+        Some(days) => today + Duration::days(days - 1),
+        None => {
+            let config = Config::load()?;
+            let end_of_week = start_of_week(today, config.week_start) + Duration::days(6);
+            end_of_week.max(today + Duration::days(1))
+        }
+    };
+
+    let repo = TaskRepository::new()?;
+    let mut tasks = repo.get_all_tasks(true)?;
+    let context = retain_in_context(&repo, &mut tasks)?;
+
+    // 今日完了したタスク
+    let completed_today = tasks
+        .iter()
+        .filter(|t| t.completed && t.completed_at.is_some_and(|c| c.date_naive() == today))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if let Some(context) = context {
+        println!("{}", context.header());
+    }
+    println!(
+        "{}",
+        t!("agenda.header", icon = glyph(Glyph::Calendar), from = format_day(today), to = format_day(last_day))
+    );
+
+    for (section, section_tasks) in build_agenda(tasks, today, last_day) {
+        let header = format!("\n{}", tn!("agenda.section", section_tasks.len(), label = section.label()));
+        let header = match section {
+            AgendaSection::Overdue => paint(&header, Role::Overdue).bold(),
+            AgendaSection::Today => paint(&header, Role::DueToday).bold(),
+            _ => header.bold(),
+        };
+        println!("{}", header);
+        for task in &section_tasks {
+            println!("{}", format_task(task, false));
+        }
+    }
+
+    if !completed_today.is_empty() {
+        println!(
+            "{}",
+            paint(&format!("\n{}", tn!("agenda.completed_today", completed_today.len())), Role::Done).bold()
+        );
+        for task in &completed_today {
+            println!("{}", format_task(task, false));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::db::schema::data_dir;
+use crate::db::TaskRepository;
+use crate::error::{Result, TaskError};
+use crate::models::attachment::{is_url, stored_name, Attachment, AttachmentKind};
+use crate::models::task::Task;
+use crate::utils::theme::{paint, Role};
+
+/// 表示するハッシュの桁数
+const SHORT_HASH_LEN: usize = 12;
+
+/// コピーした添付ファイルの保存先（データベースと同じディレクトリの attachments）
+pub fn attachments_dir() -> Result<PathBuf> {
+    Ok(data_dir()?.join("attachments"))
+}
+
+/// ファイルをタスクに添付するコマンド（既定では内容のハッシュを名前にして保存先にコピー）
+pub fn attach_file(id: i64, file: String, no_copy: bool) -> Result<()> {
+    let path = Path::new(&file);
+    if !path.is_file() {
+        return Err(TaskError::InvalidArgument(t!("attach.not_a_file", path = file)));
+    }
+    let path = path.canonicalize()?;
+    let name = path.file_name().map(|name| name.to_string_lossy().into_owned());
+
+    let mut repo = TaskRepository::new()?;
+    repo.get_task(id)?;
+
+    let attachment = if no_copy {
+        Attachment::new(AttachmentKind::File, path.to_string_lossy().into_owned(), name)
+    } else {
+        // 大きなファイルでも全体を読み込まずにハッシュを計算する
+        let mut hasher = Sha256::new();
+        io::copy(&mut File::open(&path)?, &mut hasher)?;
+        let hash = format!("{:x}", hasher.finalize());
+        let target = stored_name(&hash, path.extension().and_then(|extension| extension.to_str()));
+
+        // 同じ内容のファイルは 1 つだけ保存する
+        let dir = attachments_dir()?;
+        fs::create_dir_all(&dir)?;
+        let stored = dir.join(&target);
+        if !stored.exists() {
+            fs::copy(&path, &stored)?;
+        }
+        Attachment::new(AttachmentKind::Stored, target, name)
+    };
+    repo.add_attachment(id, &attachment)?;
+
+    println!("{}", t!("attach.attached", id = id, name = describe(&attachment)));
+    Ok(())
+}
+
+/// URL をタスクに関連付けるコマンド
+pub fn link_url(id: i64, url: String) -> Result<()> {
+    let url = url.trim().to_string();
+    if !is_url(&url) {
+        return Err(TaskError::InvalidArgument(t!("attach.invalid_url", url = url)));
+    }
+
+    let mut repo = TaskRepository::new()?;
+    repo.add_attachment(id, &Attachment::new(AttachmentKind::Link, url.clone(), None))?;
+
+    println!("{}", t!("attach.linked", id = id, url = url));
+    Ok(())
+}
+
+/// 添付した URL・ファイルを既定のアプリケーションで開くコマンド（番号は 1 から、省略時は 1 番目）
+pub fn open_attachment(id: i64, n: Option<usize>) -> Result<()> {
+    let repo = TaskRepository::new()?;
+    let task = repo.get_task(id)?;
+    let attachment = &task.attachments[item_index(&task, n.unwrap_or(1))?];
+
+    // インポートしたデータなどの不正な対象は開かない
+    attachment.validate().map_err(TaskError::InvalidArgument)?;
+    let location = attachment.location(&attachments_dir()?).unwrap_or_default();
+    if attachment.kind != AttachmentKind::Link && !Path::new(&location).exists() {
+        return Err(TaskError::InvalidArgument(t!("attach.missing", path = location)));
+    }
+
+    println!("{}", t!("attach.opening", target = location));
+    open_with_default_app(&location)
+}
+
+/// 添付を外すコマンド（コピーしたファイルは clean-attachments で削除）
+pub fn detach(id: i64, n: usize) -> Result<()> {
+    let mut repo = TaskRepository::new()?;
+    let task = repo.get_task(id)?;
+    let index = item_index(&task, n)?;
+    repo.remove_attachment(id, index)?;
+
+    println!("{}", t!("attach.detached", id = id, name = describe(&task.attachments[index])));
+    Ok(())
+}
+
+/// どのタスクからも参照されていないコピーしたファイルを削除するコマンド
+pub fn clean_attachments(dry_run: bool) -> Result<()> {
+    let repo = TaskRepository::new()?;
+    let referenced = repo.get_stored_attachment_targets()?;
+
+    let dir = attachments_dir()?;
+    let mut orphans = Vec::new();
+    if dir.is_dir() {
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if entry.file_type()?.is_file() && !referenced.contains(&name) {
+                orphans.push((name, entry.metadata()?.len()));
+            }
+        }
+    }
+    orphans.sort();
+
+    if orphans.is_empty() {
+        println!("{}", t!("attach.no_orphans"));
+        return Ok(());
+    }
+
+    for (name, size) in &orphans {
+        println!("  {} {}", name, paint(&t!("attach.size", bytes = size), Role::Muted));
+        if !dry_run {
+            fs::remove_file(dir.join(name))?;
+        }
+    }
+    let bytes: u64 = orphans.iter().map(|(_, size)| size).sum();
+    let message = if dry_run { "attach.dry_run_cleaned" } else { "attach.cleaned" };
+    println!("{}", tn!(message, orphans.len(), bytes = bytes));
+    Ok(())
+}
+
+/// 添付の表示用文字列（URL・パス、またはコピーしたファイルの元の名前と短いハッシュ）
+pub fn describe(attachment: &Attachment) -> String {
+    match attachment.kind {
+        AttachmentKind::Link | AttachmentKind::File => attachment.target.clone(),
+        AttachmentKind::Stored => {
+            let hash: String = attachment.target.chars().take(SHORT_HASH_LEN).collect();
+            let name = attachment.name.clone().unwrap_or_else(|| attachment.target.clone());
+            format!("{}{}", name, paint(&t!("attach.stored", hash = hash), Role::Muted))
+        }
+    }
+}
+
+/// 1 始まりの番号を添付の位置に変換
+fn item_index(task: &Task, n: usize) -> Result<usize> {
+    if task.attachments.is_empty() {
+        return Err(TaskError::InvalidArgument(t!("attach.none", id = task.id.unwrap_or_default())));
+    }
+    if n == 0 || n > task.attachments.len() {
+        return Err(TaskError::InvalidArgument(t!("attach.invalid_item", n = n, count = task.attachments.len())));
+    }
+    Ok(n - 1)
+}
+
+/// OS の既定のアプリケーションで開く
+fn open_with_default_app(target: &str) -> Result<()> {
+    let (program, args): (&str, &[&str]) = if cfg!(target_os = "macos") {
+        ("open", &[])
+    } else if cfg!(target_os = "windows") {
+        // cmd の start は & や ^ を含む URL を分割するため使わない
+        ("rundll32", &["url.dll,FileProtocolHandler"])
+    } else {
+        ("xdg-open", &[])
+    };
+
+    let failed = |detail: String| TaskError::InvalidArgument(t!("attach.open_failed", program = program, detail = detail));
+    let status = Command::new(program).args(args).arg(target).status().map_err(|e| failed(e.to_string()))?;
+    if !status.success() {
+        return Err(failed(status.to_string()));
+    }
+    Ok(())
+}
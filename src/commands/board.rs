@@ -0,0 +1,327 @@
+use colored::*;
+
+use crate::config::{BoardConfig, Config};
+use crate::db::TaskRepository;
+use crate::error::{Result, TaskError};
+use crate::models::task::{Priority, Task};
+use crate::utils::format::color_by_priority;
+use crate::utils::text::{pad_to_width, terminal_width, truncate_to_width};
+use crate::utils::theme::{glyph, paint, Glyph, Role};
+
+/// 完了したタスクの列名
+pub const DONE_COLUMN: &str = "done";
+
+/// 列の最小幅（これより狭くなる場合は複数段に分けて表示）
+const MIN_COLUMN_WIDTH: usize = 20;
+
+/// 列の区切りの表示幅
+const SEPARATOR_WIDTH: usize = 3;
+
+/// ボードの列の分け方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoardGrouping {
+    Status,
+    Tag,
+    Priority,
+}
+
+impl BoardGrouping {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "status" => Ok(BoardGrouping::Status),
+            "tag" => Ok(BoardGrouping::Tag),
+            "priority" => Ok(BoardGrouping::Priority),
+            _ => Err(TaskError::InvalidArgument(t!("board.invalid_grouping", value = value))),
+        }
+    }
+}
+
+/// ボードの 1 列
+#[derive(Debug, Clone)]
+pub struct BoardColumn {
+    /// 列名（WIP 制限のキー。優先度の列は high・medium・low）
+    pub name: String,
+    /// 見出しに表示する名前（優先度の列は表示言語の名前）
+    pub label: String,
+    pub tasks: Vec<Task>,
+    /// WIP 制限
+    pub limit: Option<usize>,
+}
+
+impl BoardColumn {
+    /// タスク数が WIP 制限を超えているか
+    pub fn is_over_limit(&self) -> bool {
+        self.limit.is_some_and(|limit| self.tasks.len() > limit)
+    }
+}
+
+/// タスクを列に振り分ける
+///
+/// ステータスの場合は設定の列の後ろに未知のステータスと完了の列が続く。
+/// タグの場合、複数のタグを持つタスクはそれぞれの列に表示される。
+pub fn build_board(tasks: &[Task], grouping: BoardGrouping, config: &BoardConfig) -> Vec<BoardColumn> {
+    let mut names: Vec<String> = match grouping {
+        BoardGrouping::Status => config
+            .statuses
+            .iter()
+            .filter(|s| *s != DONE_COLUMN)
+            .cloned()
+            .collect(),
+        BoardGrouping::Tag if !config.tags.is_empty() => config.tags.clone(),
+        BoardGrouping::Tag => {
+            let mut tags: Vec<String> = tasks.iter().flat_map(|t| t.tags.iter().cloned()).collect();
+            tags.sort();
+            tags.dedup();
+            tags
+        }
+        BoardGrouping::Priority => [Priority::High, Priority::Medium, Priority::Low]
+            .iter()
+            .map(|p| p.key().to_string())
+            .collect(),
+    };
+
+    // 各タスクが属する列名
+    let placements: Vec<(Vec<String>, &Task)> = tasks
+        .iter()
+        .map(|task| {
+            let columns = match grouping {
+                BoardGrouping::Status if task.completed => vec![DONE_COLUMN.to_string()],
+                BoardGrouping::Status => vec![task.status.clone()],
+                BoardGrouping::Tag => {
+                    let matched: Vec<String> = names
+                        .iter()
+                        .filter(|name| task.tags.contains(name))
+                        .cloned()
+                        .collect();
+                    if matched.is_empty() {
+                        // どの列にも当てはまらないタスク
+                        vec![t!("board.other_column")]
+                    } else {
+                        matched
+                    }
+                }
+                BoardGrouping::Priority => vec![task.priority.key().to_string()],
+            };
+            (columns, task)
+        })
+        .collect();
+
+    // 設定にない列（未知のステータスなど）を末尾に追加
+    for (columns, _) in &placements {
+        for column in columns {
+            if !names.contains(column) && column != DONE_COLUMN {
+                names.push(column.clone());
+            }
+        }
+    }
+    if placements.iter().any(|(columns, _)| columns.iter().any(|c| c == DONE_COLUMN)) {
+        names.push(DONE_COLUMN.to_string());
+    }
+
+    names
+        .into_iter()
+        .map(|name| {
+            let mut column_tasks: Vec<Task> = placements
+                .iter()
+                .filter(|(columns, _)| columns.contains(&name))
+                .map(|(_, task)| (*task).clone())
+                .collect();
+            column_tasks.sort_by(|a, b| (b.priority as i32).cmp(&(a.priority as i32)).then(a.id.cmp(&b.id)));
+            let label = match grouping {
+                BoardGrouping::Priority => Priority::parse(&name).map(|p| p.label()).unwrap_or_else(|| name.clone()),
+                _ => name.clone(),
+            };
+            BoardColumn {
+                limit: config.wip.get(&name).copied(),
+                name,
+                label,
+                tasks: column_tasks,
+            }
+        })
+        .collect()
+}
+
+/// タスクを指定した列に移動し、移動先の列名を返す（タスクの内容を変更するのみで保存はしない）
+pub fn apply_move(task: &mut Task, column: &str, grouping: BoardGrouping, config: &BoardConfig) -> Result<String> {
+    match grouping {
+        BoardGrouping::Status => {
+            if column == DONE_COLUMN {
+                if !task.completed {
+                    task.complete();
+                }
+            } else if config.statuses.iter().any(|s| s == column) {
+                task.status = column.to_string();
+                if task.completed {
+                    task.uncomplete();
+                }
+            } else {
+                return Err(TaskError::InvalidArgument(t!(
+                    "board.invalid_status",
+                    status = column,
+                    statuses = config.statuses.join(", "),
+                    done = DONE_COLUMN,
+                )));
+            }
+        }
+        BoardGrouping::Tag => {
+            // 使用中のすべてのタグを列にしている場合、移動で他のタグを外すと情報が失われるため設定を必須にする
+            if config.tags.is_empty() {
+                return Err(TaskError::ConfigError(t!("board.tags_required")));
+            }
+            if !config.tags.iter().any(|t| t == column) {
+                return Err(TaskError::InvalidArgument(t!(
+                    "board.invalid_column",
+                    column = column,
+                    columns = config.tags.join(", "),
+                )));
+            }
+            task.tags.retain(|t| !config.tags.contains(t));
+            task.tags.push(column.to_string());
+        }
+        BoardGrouping::Priority => {
+            task.priority = parse_priority_column(column)?;
+            return Ok(task.priority.key().to_string());
+        }
+    }
+
+    Ok(column.to_string())
+}
+
+/// 優先度の列名（high・medium・low、表示言語の名前、1〜3）を解析
+fn parse_priority_column(column: &str) -> Result<Priority> {
+    Priority::parse(column).ok_or_else(|| TaskError::InvalidArgument(t!("board.invalid_priority", column = column)))
+}
+
+/// カンバンボードを表示するコマンド
+pub fn show_board(by: String, all: bool) -> Result<()> {
+    let grouping = BoardGrouping::parse(&by)?;
+    let config = Config::load()?;
+    let repo = TaskRepository::new()?;
+    let tasks = repo.get_all_tasks(all)?;
+
+    let columns = build_board(&tasks, grouping, &config.board);
+    if columns.is_empty() {
+        println!("{}", t!("board.empty"));
+        return Ok(());
+    }
+
+    // 端末の幅に収まる列数ごとに段を分けて表示
+    let width = terminal_width();
+    let per_row = ((width + SEPARATOR_WIDTH) / (MIN_COLUMN_WIDTH + SEPARATOR_WIDTH)).max(1);
+    for (i, chunk) in columns.chunks(per_row).enumerate() {
+        if i > 0 {
+            println!();
+        }
+        print_columns(chunk, width);
+    }
+
+    for column in columns.iter().filter(|c| c.is_over_limit()) {
+        println!(
+            "{}",
+            paint(
+                &format!(
+                    "\n{}",
+                    t!(
+                        "board.over_limit",
+                        column = column.label,
+                        count = column.tasks.len(),
+                        limit = column.limit.unwrap_or(0),
+                    )
+                ),
+                Role::Warning
+            )
+        );
+    }
+
+    Ok(())
+}
+
+/// 列を横に並べて表示
+fn print_columns(columns: &[BoardColumn], width: usize) {
+    let separator_width = SEPARATOR_WIDTH * (columns.len() - 1);
+    let column_width = (width.saturating_sub(separator_width) / columns.len()).max(MIN_COLUMN_WIDTH);
+    let separator = format!(" {} ", glyph(Glyph::VerticalLine));
+
+    let header = columns
+        .iter()
+        .map(|column| {
+            let label = match column.limit {
+                Some(limit) => format!("{} ({}/{})", column.label, column.tasks.len(), limit),
+                None => format!("{} ({})", column.label, column.tasks.len()),
+            };
+            let label = pad_to_width(&truncate_to_width(&label, column_width), column_width);
+            if column.is_over_limit() {
+                paint(&label, Role::Error).to_string()
+            } else {
+                label.bold().to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(&separator);
+    println!("{}", header);
+    let horizontal = glyph(Glyph::HorizontalLine);
+    println!(
+        "{}",
+        vec![horizontal.repeat(column_width); columns.len()]
+            .join(&format!("{}{}{}", horizontal, glyph(Glyph::Cross), horizontal))
+    );
+
+    let rows = columns.iter().map(|c| c.tasks.len()).max().unwrap_or(0);
+    for row in 0..rows {
+        let line = columns
+            .iter()
+            .map(|column| match column.tasks.get(row) {
+                Some(task) => {
+                    let text = pad_to_width(
+                        &truncate_to_width(
+                            &format!("{} {}", task.id.unwrap_or(0), task.title),
+                            column_width,
+                        ),
+                        column_width,
+                    );
+                    if task.completed {
+                        paint(&text, Role::Muted).to_string()
+                    } else {
+                        color_by_priority(&text, task.priority).to_string()
+                    }
+                }
+                None => " ".repeat(column_width),
+            })
+            .collect::<Vec<_>>()
+            .join(&separator);
+        println!("{}", line.trim_end());
+    }
+}
+
+/// タスクをボードの別の列に移動するコマンド
+pub fn move_task(id: i64, column: String, by: String) -> Result<()> {
+    let grouping = BoardGrouping::parse(&by)?;
+    let config = Config::load()?;
+    let mut repo = TaskRepository::new()?;
+
+    let mut task = repo.get_task(id)?;
+    let target_name = apply_move(&mut task, column.trim(), grouping, &config.board)?;
+    repo.update_task(&task)?;
+
+    println!("{}", paint(&t!("board.moved", id = id, column = column.trim()), Role::Done));
+
+    // 移動先の列が WIP 制限を超えた場合は警告
+    let tasks = repo.get_all_tasks(false)?;
+    let columns = build_board(&tasks, grouping, &config.board);
+    if let Some(target) = columns.iter().find(|c| c.name == target_name && c.is_over_limit()) {
+        println!(
+            "{}",
+            paint(
+                &t!(
+                    "board.over_limit",
+                    column = target.label,
+                    count = target.tasks.len(),
+                    limit = target.limit.unwrap_or(0),
+                ),
+                Role::Warning
+            )
+        );
+    }
+
+    Ok(())
+}
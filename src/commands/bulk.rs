@@ -0,0 +1,104 @@
+use crate::db::TaskRepository;
+use crate::error::{Result, TaskError};
+use crate::models::task::Task;
+use crate::utils::filter::TaskFilter;
+use crate::utils::format::format_task;
+use crate::utils::ids::parse_id_ranges;
+use crate::utils::prompt::confirm;
+
+/// 一括操作の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkAction {
+    Complete,
+    Uncomplete,
+    Delete,
+    Update,
+}
+
+impl BulkAction {
+    fn confirm_message(&self, count: usize) -> String {
+        match self {
+            BulkAction::Complete => tn!("bulk.confirm_complete", count),
+            BulkAction::Uncomplete => tn!("bulk.confirm_uncomplete", count),
+            BulkAction::Delete => tn!("bulk.confirm_delete", count),
+            BulkAction::Update => tn!("bulk.confirm_update", count),
+        }
+    }
+
+    fn dry_run_message(&self, count: usize) -> String {
+        match self {
+            BulkAction::Complete => tn!("bulk.dry_run_complete", count),
+            BulkAction::Uncomplete => tn!("bulk.dry_run_uncomplete", count),
+            BulkAction::Delete => tn!("bulk.dry_run_delete", count),
+            BulkAction::Update => tn!("bulk.dry_run_update", count),
+        }
+    }
+
+    /// 実行後の件数の表示
+    pub fn done_message(&self, count: usize) -> String {
+        match self {
+            BulkAction::Complete => tn!("bulk.completed", count),
+            BulkAction::Uncomplete => tn!("bulk.uncompleted", count),
+            BulkAction::Delete => tn!("bulk.deleted", count),
+            BulkAction::Update => tn!("bulk.updated", count),
+        }
+    }
+}
+
+/// 対象のタスクを選択（ID・範囲の一覧と --where の条件。両方指定した場合は両方を満たすタスク）
+///
+/// 一覧のIDが存在しない場合はエラー。結果はID順。
+pub fn select_tasks(repo: &TaskRepository, ids: &[String], filter: Option<&str>) -> Result<Vec<Task>> {
+    let ids = parse_id_ranges(ids)?;
+    let filter = match filter {
+        Some(expression) => Some(
+            TaskFilter::parse(expression, &repo.get_field_definitions()?)?
+                .search_notes(|term| repo.search_note_task_ids(term))?,
+        ),
+        None => None,
+    };
+
+    let mut tasks = match (ids.is_empty(), &filter) {
+        (true, None) => return Err(TaskError::InvalidArgument(t!("bulk.missing_target"))),
+        (true, Some(_)) => repo.get_all_tasks(true)?,
+        (false, _) => ids.iter().map(|id| repo.get_task(*id)).collect::<Result<Vec<_>>>()?,
+    };
+    tasks.retain(|task| filter.as_ref().is_none_or(|f| f.matches(task)));
+    tasks.sort_by_key(|task| task.id);
+    Ok(tasks)
+}
+
+/// 一括操作を実行してよいか確認
+///
+/// --dry-run の場合は対象（と details が返す変更内容）を表示して false を返す。対象が複数の場合と
+/// --where で選んだ場合は件数を示して確認する（--yes で省略）。
+pub fn confirm_bulk(
+    action: BulkAction,
+    tasks: &[Task],
+    filtered: bool,
+    dry_run: bool,
+    yes: bool,
+    details: impl Fn(&Task) -> Vec<String>,
+) -> Result<bool> {
+    if tasks.is_empty() {
+        println!("{}", t!("bulk.no_targets"));
+        return Ok(false);
+    }
+
+    if dry_run {
+        println!("{}", action.dry_run_message(tasks.len()));
+        for task in tasks {
+            println!("{}", format_task(task, false));
+            for line in details(task) {
+                println!("      {}", line);
+            }
+        }
+        return Ok(false);
+    }
+
+    if (tasks.len() > 1 || filtered) && !yes && !confirm(&action.confirm_message(tasks.len()))? {
+        println!("{}", t!("bulk.cancelled"));
+        return Ok(false);
+    }
+    Ok(true)
+}
@@ -0,0 +1,253 @@
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Duration, Local, NaiveDate, Weekday};
+use colored::*;
+
+use crate::config::Config;
+use crate::db::TaskRepository;
+use crate::error::{Result, TaskError};
+use crate::models::task::{Priority, Task};
+use crate::utils::date::{start_of_week, weekday_name};
+use crate::utils::format::{color_by_priority, format_priority};
+use crate::utils::text::{pad_to_width, truncate_to_width};
+use crate::utils::theme::{glyph, paint, Glyph, Role};
+
+/// 月表示の 1 日分のセルの幅
+const MONTH_CELL_WIDTH: usize = 7;
+
+/// 週表示の 1 日分の列の幅
+const WEEK_COLUMN_WIDTH: usize = 16;
+
+/// 1 日分の期限タスクの集計
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DaySummary {
+    /// 期限が当日の未完了タスク数
+    pub count: usize,
+    /// 最も高い優先度
+    pub highest: Option<Priority>,
+    /// 期限切れのタスクを含むか
+    pub overdue: bool,
+}
+
+/// 未完了タスクを期限日ごとに集計する
+pub fn summarize_days(tasks: &[Task]) -> BTreeMap<NaiveDate, DaySummary> {
+    let mut days: BTreeMap<NaiveDate, DaySummary> = BTreeMap::new();
+
+    for task in tasks.iter().filter(|t| !t.completed) {
+        let due = match task.due_date {
+            Some(due) => due,
+            None => continue,
+        };
+
+        let summary = days.entry(due.date_naive()).or_default();
+        summary.count += 1;
+        if summary.highest.is_none_or(|p| (task.priority as i32) > (p as i32)) {
+            summary.highest = Some(task.priority);
+        }
+        summary.overdue |= task.is_overdue();
+    }
+
+    days
+}
+
+/// "YYYY-MM" 形式の文字列を月の初日に変換
+pub fn parse_month(month: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(&format!("{}-01", month.trim()), "%Y-%m-%d").map_err(|_| {
+        TaskError::InvalidArgument(t!("calendar.invalid_month", input = month))
+    })
+}
+
+/// 月を週ごとの行に分割する（各行は 7 日分で、月の外の日は None）
+pub fn month_weeks(month: NaiveDate, week_start: Weekday) -> Vec<Vec<Option<NaiveDate>>> {
+    let first = month.with_day(1).unwrap_or(month);
+    let mut day = start_of_week(first, week_start);
+    let mut weeks = Vec::new();
+
+    while day.month() == first.month() || day < first {
+        let week = (0..7)
+            .map(|offset| {
+                let date = day + Duration::days(offset);
+                (date.month() == first.month() && date.year() == first.year()).then_some(date)
+            })
+            .collect();
+        weeks.push(week);
+        day += Duration::days(7);
+    }
+
+    weeks
+}
+
+/// カレンダーを表示するコマンド
+///
+/// 月表示では日ごとの期限タスク数を、週表示では日ごとのタスク名を表示する。
+pub fn show_calendar(date: Option<String>, week: bool) -> Result<()> {
+    let config = Config::load()?;
+    let repo = TaskRepository::new()?;
+    let tasks = repo.get_all_tasks(false)?;
+
+    if week {
+        // 週表示では任意の日付を受け付け、その日を含む週を表示
+        let day = match date {
+            Some(date) => NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").map_err(|_| {
+                TaskError::InvalidArgument(t!("calendar.invalid_day", input = date))
+            })?,
+            None => Local::now().date_naive(),
+        };
+        print_week(&tasks, start_of_week(day, config.week_start));
+    } else {
+        let month = match date {
+            Some(month) => parse_month(&month)?,
+            None => Local::now().date_naive().with_day(1).unwrap(),
+        };
+        print_month(&tasks, month, config.week_start);
+    }
+
+    Ok(())
+}
+
+/// 曜日の見出しを表示
+fn print_weekday_header(week_start: Weekday, width: usize) {
+    let mut weekday = week_start;
+    let mut header = String::new();
+    for _ in 0..7 {
+        header.push_str(&pad_to_width(&weekday_name(weekday), width));
+        weekday = weekday.succ();
+    }
+    println!("{}", header.trim_end().bold());
+}
+
+/// 月のカレンダーを表示
+fn print_month(tasks: &[Task], month: NaiveDate, week_start: Weekday) {
+    let summaries = summarize_days(tasks);
+    let today = Local::now().date_naive();
+
+    println!("{}", format!("{} {}", glyph(Glyph::Calendar), month.format(&t!("calendar.month_format"))).bold());
+    print_weekday_header(week_start, MONTH_CELL_WIDTH);
+
+    for week in month_weeks(month, week_start) {
+        let mut line = String::new();
+        for day in week {
+            let date = match day {
+                Some(date) => date,
+                None => {
+                    line.push_str(&" ".repeat(MONTH_CELL_WIDTH));
+                    continue;
+                }
+            };
+
+            let day_str = format!("{:>2}", date.day());
+            let day_str = if date == today {
+                day_str.reversed().to_string()
+            } else {
+                day_str
+            };
+
+            let (count_str, width) = match summaries.get(&date) {
+                Some(summary) => {
+                    let text = format!("({})", summary.count);
+                    let width = text.len();
+                    (color_by_summary(&text, summary).to_string(), width)
+                }
+                None => (String::new(), 0),
+            };
+
+            line.push_str(&day_str);
+            line.push_str(&count_str);
+            line.push_str(&" ".repeat(MONTH_CELL_WIDTH.saturating_sub(2 + width)));
+        }
+        println!("{}", line.trim_end());
+    }
+
+    println!(
+        "\n{} {} {} {}",
+        paint(&t!("calendar.legend"), Role::Muted),
+        paint(&t!("calendar.overdue"), Role::Highlight),
+        format_priority(Priority::High),
+        format_priority(Priority::Medium)
+    );
+}
+
+/// 週のカレンダーを表示
+fn print_week(tasks: &[Task], first_day: NaiveDate) {
+    let days: Vec<NaiveDate> = (0..7).map(|offset| first_day + Duration::days(offset)).collect();
+
+    // 日ごとのタスク（期限順、同時刻は優先度の高い順）
+    let columns: Vec<Vec<&Task>> = days
+        .iter()
+        .map(|day| {
+            let mut day_tasks: Vec<&Task> = tasks
+                .iter()
+                .filter(|t| !t.completed && t.due_date.is_some_and(|d| d.date_naive() == *day))
+                .collect();
+            day_tasks.sort_by(|a, b| {
+                a.due_date
+                    .cmp(&b.due_date)
+                    .then((b.priority as i32).cmp(&(a.priority as i32)))
+            });
+            day_tasks
+        })
+        .collect();
+
+    println!(
+        "{}",
+        t!("calendar.week_header", icon = glyph(Glyph::Calendar), from = days[0].format("%Y-%m-%d"), to = days[6].format("%Y-%m-%d")).bold()
+    );
+
+    let today = Local::now().date_naive();
+    let header = days
+        .iter()
+        .map(|day| {
+            let label = pad_to_width(
+                &t!("date.day", date = day.format("%m/%d"), weekday = weekday_name(day.weekday())),
+                WEEK_COLUMN_WIDTH,
+            );
+            if *day == today {
+                label.reversed().to_string()
+            } else {
+                label.bold().to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(glyph(Glyph::VerticalLine));
+    println!("{}", header);
+    println!("{}", vec![glyph(Glyph::HorizontalLine).repeat(WEEK_COLUMN_WIDTH); 7].join(glyph(Glyph::Cross)));
+
+    let rows = columns.iter().map(|c| c.len()).max().unwrap_or(0);
+    for row in 0..rows {
+        let line = columns
+            .iter()
+            .map(|column| match column.get(row) {
+                Some(task) => {
+                    let text = pad_to_width(
+                        &truncate_to_width(
+                            &format!("{} {}", task.id.unwrap_or(0), task.title),
+                            WEEK_COLUMN_WIDTH,
+                        ),
+                        WEEK_COLUMN_WIDTH,
+                    );
+                    if task.is_overdue() {
+                        paint(&text, Role::Highlight).to_string()
+                    } else {
+                        color_by_priority(&text, task.priority).to_string()
+                    }
+                }
+                None => " ".repeat(WEEK_COLUMN_WIDTH),
+            })
+            .collect::<Vec<_>>()
+            .join(glyph(Glyph::VerticalLine));
+        println!("{}", line.trim_end());
+    }
+
+    if rows == 0 {
+        println!("{}", t!("calendar.no_tasks_this_week"));
+    }
+}
+
+/// 集計に応じて色を付ける（期限切れを含む場合は赤、それ以外は最も高い優先度の色）
+fn color_by_summary(text: &str, summary: &DaySummary) -> ColoredString {
+    if summary.overdue {
+        paint(text, Role::Highlight)
+    } else {
+        color_by_priority(text, summary.highest.unwrap_or(Priority::Low))
+    }
+}
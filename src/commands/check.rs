@@ -0,0 +1,66 @@
+use crate::db::TaskRepository;
+use crate::error::{Result, TaskError};
+use crate::models::checklist::ChecklistItem;
+use crate::models::task::Task;
+use crate::utils::format::{format_checklist, format_task};
+
+/// チェックリストに項目を追加するコマンド
+pub fn add_check_item(id: i64, text: String) -> Result<()> {
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        return Err(TaskError::InvalidArgument(t!("check.empty_text")));
+    }
+
+    let mut repo = TaskRepository::new()?;
+    let mut task = repo.get_task(id)?;
+    task.checklist.push(ChecklistItem::new(text));
+    repo.update_task(&task)?;
+
+    println!("{}", t!("check.added", id = id, n = task.checklist.len()));
+    print_checklist(&task);
+    Ok(())
+}
+
+/// チェックリストの項目（1 始まりの番号）の完了・未完了を切り替えるコマンド
+pub fn toggle_check_item(id: i64, n: usize) -> Result<()> {
+    let mut repo = TaskRepository::new()?;
+    let mut task = repo.get_task(id)?;
+    let index = item_index(&task, n)?;
+    let item = &mut task.checklist[index];
+    item.done = !item.done;
+    let message = if item.done { "check.checked" } else { "check.unchecked" };
+    repo.update_task(&task)?;
+
+    println!("{}", t!(message, id = id, n = n));
+    print_checklist(&task);
+    Ok(())
+}
+
+/// チェックリストの項目（1 始まりの番号）を削除するコマンド
+pub fn remove_check_item(id: i64, n: usize) -> Result<()> {
+    let mut repo = TaskRepository::new()?;
+    let mut task = repo.get_task(id)?;
+    let index = item_index(&task, n)?;
+    task.checklist.remove(index);
+    repo.update_task(&task)?;
+
+    println!("{}", t!("check.removed", id = id, n = n));
+    print_checklist(&task);
+    Ok(())
+}
+
+/// 1 始まりの番号を項目の位置に変換
+fn item_index(task: &Task, n: usize) -> Result<usize> {
+    if n == 0 || n > task.checklist.len() {
+        return Err(TaskError::InvalidArgument(t!("check.invalid_item", n = n, count = task.checklist.len())));
+    }
+    Ok(n - 1)
+}
+
+/// タスクとチェックリストを表示
+fn print_checklist(task: &Task) {
+    println!("{}", format_task(task, false));
+    for line in format_checklist(&task.checklist) {
+        println!("    {}", line);
+    }
+}
@@ -1,27 +1,54 @@
+use crate::commands::bulk::{confirm_bulk, select_tasks, BulkAction};
 use crate::db::TaskRepository;
 use crate::error::Result;
 use crate::utils::format::format_task;
 
-/// タスクを完了状態に設定するコマンド
-pub fn complete_task(id: i64) -> Result<()> {
-    let mut repo = TaskRepository::new()?;
-    repo.complete_task(id)?;
-    
-    let task = repo.get_task(id)?;
-    println!("タスクを完了としてマークしました（ID: {}）", id);
-    println!("{}", format_task(&task, false));
-    
-    Ok(())
+/// タスクを完了状態に設定するコマンド（ID・範囲の一覧または --where の条件で複数指定可）
+pub fn complete_task(ids: Vec<String>, filter: Option<String>, dry_run: bool, yes: bool) -> Result<()> {
+    set_completed(ids, filter, dry_run, yes, true)
 }
 
-/// タスクを未完了状態に設定するコマンド
-pub fn uncomplete_task(id: i64) -> Result<()> {
+/// タスクを未完了状態に設定するコマンド（ID・範囲の一覧または --where の条件で複数指定可）
+pub fn uncomplete_task(ids: Vec<String>, filter: Option<String>, dry_run: bool, yes: bool) -> Result<()> {
+    set_completed(ids, filter, dry_run, yes, false)
+}
+
+fn set_completed(ids: Vec<String>, filter: Option<String>, dry_run: bool, yes: bool, completed: bool) -> Result<()> {
     let mut repo = TaskRepository::new()?;
-    repo.uncomplete_task(id)?;
-    
-    let task = repo.get_task(id)?;
-    println!("タスクを未完了としてマークしました（ID: {}）", id);
-    println!("{}", format_task(&task, false));
-    
+    let action = if completed { BulkAction::Complete } else { BulkAction::Uncomplete };
+
+    // すでに同じ状態のタスクは対象外
+    let mut tasks = select_tasks(&repo, &ids, filter.as_deref())?;
+    tasks.retain(|task| task.completed != completed);
+    if !confirm_bulk(action, &tasks, filter.is_some(), dry_run, yes, |_| Vec::new())? {
+        return Ok(());
+    }
+
+    let ids: Vec<i64> = tasks.iter().filter_map(|task| task.id).collect();
+    repo.transaction(|repo| {
+        ids.iter().try_for_each(|id| {
+            if completed {
+                repo.complete_task(*id)
+            } else {
+                repo.uncomplete_task(*id)
+            }
+        })
+    })?;
+
+    for id in &ids {
+        let task = repo.get_task(*id)?;
+        if ids.len() == 1 {
+            if completed {
+                println!("{}", t!("complete.done", id = id));
+            } else {
+                println!("{}", t!("complete.undone", id = id));
+            }
+        }
+        println!("{}", format_task(&task, false));
+    }
+    if ids.len() > 1 {
+        println!("{}", action.done_message(ids.len()));
+    }
+
     Ok(())
-} 
+}
@@ -0,0 +1,113 @@
+use crate::db::TaskRepository;
+use crate::error::{Result, TaskError};
+use crate::models::task::Task;
+use crate::models::view::View;
+use crate::utils::filter::TaskFilter;
+use crate::utils::theme::{paint, Role};
+
+/// 有効なコンテキストを保存する設定のキー
+const CONTEXT_KEY: &str = "context";
+
+/// 有効なコンテキスト（保存した表示条件の絞り込みの条件を list・next・agenda・stats に適用する）
+#[derive(Debug, Clone)]
+pub struct ActiveContext {
+    pub name: String,
+    pub filter: Option<String>,
+}
+
+impl ActiveContext {
+    /// 一覧の先頭に表示する行
+    pub fn header(&self) -> String {
+        let text = match &self.filter {
+            Some(filter) => t!("context.header", name = self.name, filter = filter),
+            None => t!("context.header_all", name = self.name),
+        };
+        paint(&text, Role::Muted).to_string()
+    }
+
+    /// 絞り込みの条件を解析（条件のないコンテキストはすべてのタスク）
+    pub fn task_filter(&self, repo: &TaskRepository) -> Result<Option<TaskFilter>> {
+        match &self.filter {
+            Some(filter) => Ok(Some(
+                TaskFilter::parse(filter, &repo.get_field_definitions()?)?
+                    .search_notes(|term| repo.search_note_task_ids(term))?,
+            )),
+            None => Ok(None),
+        }
+    }
+}
+
+/// 有効なコンテキストを取得（表示条件が削除されている場合はなし）
+pub fn active_context(repo: &TaskRepository) -> Result<Option<ActiveContext>> {
+    let Some(name) = repo.get_setting(CONTEXT_KEY)? else {
+        return Ok(None);
+    };
+    match repo.get_view(&name) {
+        Ok(view) => Ok(Some(ActiveContext { name: view.name, filter: view.filter })),
+        Err(TaskError::ViewNotFound(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// 有効なコンテキストの条件に合うタスクだけを残す
+pub fn retain_in_context(repo: &TaskRepository, tasks: &mut Vec<Task>) -> Result<Option<ActiveContext>> {
+    let Some(context) = active_context(repo)? else {
+        return Ok(None);
+    };
+    if let Some(filter) = context.task_filter(repo)? {
+        tasks.retain(|task| filter.matches(task));
+    }
+    Ok(Some(context))
+}
+
+/// コンテキストを設定するコマンド（条件を指定した場合は同じ名前の表示条件として保存する）
+pub fn set_context(name: String, filter: Option<String>) -> Result<()> {
+    let name = name.trim_start_matches('@').to_string();
+    let mut repo = TaskRepository::new()?;
+    match filter {
+        Some(filter) => {
+            View::validate_name(&name).map_err(TaskError::InvalidArgument)?;
+            TaskFilter::parse(&filter, &repo.get_field_definitions()?)?;
+            // 既存の表示条件の並び替えや列は残す
+            let view = match repo.get_view(&name) {
+                Ok(view) => View { filter: Some(filter), ..view },
+                Err(TaskError::ViewNotFound(_)) => View {
+                    name: name.clone(),
+                    filter: Some(filter),
+                    sort: None,
+                    format: None,
+                    columns: None,
+                    all: false,
+                },
+                Err(e) => return Err(e),
+            };
+            repo.save_view(&view)?;
+        }
+        None => {
+            repo.get_view(&name)?;
+        }
+    }
+    repo.set_setting(CONTEXT_KEY, Some(&name))?;
+
+    println!("{}", t!("context.set", name = name));
+    Ok(())
+}
+
+/// コンテキストを解除するコマンド
+pub fn clear_context() -> Result<()> {
+    let mut repo = TaskRepository::new()?;
+    repo.set_setting(CONTEXT_KEY, None)?;
+
+    println!("{}", t!("context.cleared"));
+    Ok(())
+}
+
+/// 有効なコンテキストを表示するコマンド
+pub fn show_context() -> Result<()> {
+    let repo = TaskRepository::new()?;
+    match active_context(&repo)? {
+        Some(context) => println!("{}", context.header()),
+        None => println!("{}", t!("context.none")),
+    }
+    Ok(())
+}
@@ -1,33 +1,42 @@
+use crate::commands::bulk::{confirm_bulk, select_tasks, BulkAction};
 use crate::db::TaskRepository;
 use crate::error::{Result, TaskError};
 use crate::utils::format::format_task;
 
-/// タスクを削除するコマンド
-pub fn delete_task(id: Option<i64>, completed: bool) -> Result<()> {
-    // いずれかのオプションが必要
-    if id.is_none() && !completed {
-        return Err(TaskError::InvalidArgument(
-            "タスクIDまたは --completed オプションを指定してください".to_string(),
-        ));
+/// タスクを削除するコマンド（ID・範囲の一覧、--where の条件、--completed で複数指定可）
+pub fn delete_task(ids: Vec<String>, filter: Option<String>, completed: bool, dry_run: bool, yes: bool) -> Result<()> {
+    // いずれかの指定が必要
+    if ids.is_empty() && filter.is_none() && !completed {
+        return Err(TaskError::InvalidArgument(t!("delete.missing_target")));
     }
-    
+
+    // --completed は完了済みのタスクに絞る条件として扱う
+    let filter = match (filter, completed) {
+        (None, true) => Some("is:done".to_string()),
+        (filter, _) => filter,
+    };
+
     let mut repo = TaskRepository::new()?;
-    
-    // 特定のタスクを削除
-    if let Some(task_id) = id {
-        // 削除前にタスク情報を取得して表示
-        let task = repo.get_task(task_id)?;
-        println!("以下のタスクを削除します:");
-        println!("{}", format_task(&task, false));
-        
-        repo.delete_task(task_id)?;
-        println!("タスクを削除しました（ID: {}）", task_id);
-    } 
-    // 完了済みタスクをすべて削除
-    else if completed {
-        let count = repo.delete_completed_tasks()?;
-        println!("{}件の完了済みタスクを削除しました", count);
+    let mut tasks = select_tasks(&repo, &ids, filter.as_deref())?;
+    if completed {
+        tasks.retain(|task| task.completed);
+    }
+    if tasks.len() == 1 && !dry_run {
+        // 削除前にタスク情報を表示
+        println!("{}", t!("delete.confirm"));
+        println!("{}", format_task(&tasks[0], false));
+    }
+    if !confirm_bulk(BulkAction::Delete, &tasks, filter.is_some(), dry_run, yes, |_| Vec::new())? {
+        return Ok(());
     }
-    
+
+    let ids: Vec<i64> = tasks.iter().filter_map(|task| task.id).collect();
+    repo.transaction(|repo| ids.iter().try_for_each(|id| repo.delete_task(*id)))?;
+
+    match ids.as_slice() {
+        [id] => println!("{}", t!("delete.done", id = id)),
+        _ => println!("{}", BulkAction::Delete.done_message(ids.len())),
+    }
+
     Ok(())
-} 
+}
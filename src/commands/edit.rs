@@ -0,0 +1,385 @@
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+
+use chrono::{DateTime, Local};
+
+use crate::db::TaskRepository;
+use crate::error::{Result, TaskError};
+use crate::models::field::FieldDefinition;
+use crate::models::task::{parse_project, Priority, Task, DEFAULT_STATUS};
+use crate::utils::date::{format_datetime, parse_date};
+use crate::utils::duration::{format_duration, parse_estimate};
+use crate::utils::format::format_task;
+use crate::utils::ids::parse_id_list;
+use crate::utils::prompt::confirm;
+use crate::utils::theme::{paint, Role};
+
+/// フロントマターの区切り
+const DELIMITER: &str = "---";
+
+/// 前回の編集の誤りを示すコメントの接頭辞（開き直すときに書き直す）
+const ERROR_MARK: &str = "# !";
+
+/// 差分の1行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Same(String),
+    Removed(String),
+    Added(String),
+}
+
+/// タスクを編集用の文書（フロントマター + Markdown の説明）に変換
+///
+/// フロントマターには `key: value` 形式でタイトル・優先度・日時などと定義済みのカスタムフィールドを並べ、
+/// 値のない項目も空のまま書き出す。
+pub fn render_document(task: &Task, definitions: &[FieldDefinition]) -> String {
+    render(task, definitions, true)
+}
+
+fn render(task: &Task, definitions: &[FieldDefinition], with_help: bool) -> String {
+    let date = |date: Option<DateTime<Local>>| date.map(format_datetime).unwrap_or_default();
+    let mut fields = vec![
+        ("title".to_string(), task.title.clone()),
+        ("priority".to_string(), (task.priority as i32).to_string()),
+        ("status".to_string(), task.status.clone()),
+        ("due".to_string(), date(task.due_date)),
+        ("start".to_string(), date(task.start_date)),
+        ("wait".to_string(), date(task.wait_until)),
+        (
+            "estimate".to_string(),
+            task.estimate().map(format_duration).unwrap_or_default(),
+        ),
+        ("project".to_string(), task.project.clone().unwrap_or_default()),
+        ("tags".to_string(), task.tags.join(", ")),
+        (
+            "depends_on".to_string(),
+            task.depends_on.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(", "),
+        ),
+    ];
+    for definition in definitions {
+        let value = task.custom_fields.get(&definition.name).cloned().unwrap_or_default();
+        fields.push((definition.name.clone(), value));
+    }
+
+    let mut lines = vec![DELIMITER.to_string()];
+    if with_help {
+        let header = match task.id {
+            Some(id) => t!("edit.help_edit", id = id),
+            None => t!("edit.help_new"),
+        };
+        lines.push(format!("# {}", header));
+        lines.push(format!("# {}", t!("edit.help_format")));
+    }
+    for (key, value) in fields {
+        if value.is_empty() {
+            lines.push(format!("{}:", key));
+        } else {
+            lines.push(format!("{}: {}", key, value));
+        }
+    }
+    lines.push(DELIMITER.to_string());
+    lines.push(String::new());
+    if let Some(description) = &task.description {
+        lines.push(description.clone());
+    }
+    lines.join("\n") + "\n"
+}
+
+/// 編集した文書を解析して base に反映したタスクを返す
+///
+/// フロントマターにない項目は base のまま、空の値は未設定にする。日時・優先度・見積もり・タグ・
+/// 依存先の形式と、カスタムフィールドの名前を検証する（値の検証は `validate_custom_fields`）。
+pub fn parse_document(text: &str, base: &Task, definitions: &[FieldDefinition]) -> Result<Task> {
+    let invalid = |detail: String| TaskError::InvalidArgument(detail);
+
+    // 先頭の空行と前回の誤りのコメントは読み飛ばす
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .skip_while(|(_, line)| line.trim().is_empty() || line.starts_with(ERROR_MARK));
+    if lines.next().map(|(_, line)| line.trim_end()) != Some(DELIMITER) {
+        return Err(invalid(t!("edit.missing_front_matter")));
+    }
+    let mut front_matter = Vec::new();
+    let mut closed = false;
+    for (number, line) in lines.by_ref() {
+        if line.trim_end() == DELIMITER {
+            closed = true;
+            break;
+        }
+        front_matter.push((number + 1, line));
+    }
+    if !closed {
+        return Err(invalid(t!("edit.unclosed_front_matter")));
+    }
+    let body = lines.map(|(_, line)| line).collect::<Vec<_>>().join("\n");
+
+    let mut task = base.clone();
+    let mut seen = HashSet::new();
+    for (number, line) in front_matter {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = line
+            .split_once(':')
+            .ok_or_else(|| invalid(t!("edit.invalid_line", line = number, content = line)))?;
+        let (key, value) = (key.trim(), value.trim());
+        if !seen.insert(key.to_string()) {
+            return Err(invalid(t!("edit.duplicate_key", key = key)));
+        }
+        apply_field(&mut task, key, value, definitions)
+            .map_err(|e| invalid(t!("edit.invalid_field", key = key, detail = error_detail(e))))?;
+    }
+
+    if task.title.is_empty() {
+        return Err(invalid(t!("edit.empty_title")));
+    }
+    if task.starts_after_due() {
+        return Err(invalid(t!("task.start_after_due")));
+    }
+    let description = body.trim_matches('\n').trim_end();
+    task.description = (!description.trim().is_empty()).then(|| description.to_string());
+    Ok(task)
+}
+
+/// フロントマターの1項目をタスクに反映
+fn apply_field(task: &mut Task, key: &str, value: &str, definitions: &[FieldDefinition]) -> Result<()> {
+    let date = |value: &str| if value.is_empty() { Ok(None) } else { parse_date(value).map(Some) };
+    match key {
+        "title" => task.title = value.to_string(),
+        "priority" => {
+            task.priority = Priority::parse(value)
+                .ok_or_else(|| TaskError::InvalidArgument(t!("edit.invalid_priority", value = value)))?;
+        }
+        "status" if value.is_empty() => task.status = DEFAULT_STATUS.to_string(),
+        "status" => task.status = value.to_string(),
+        "due" => task.due_date = date(value)?,
+        "start" => task.start_date = date(value)?,
+        "wait" => task.wait_until = date(value)?,
+        "estimate" if value.is_empty() => task.estimate_minutes = None,
+        "estimate" => task.estimate_minutes = Some(parse_estimate(value)?),
+        "project" => task.project = parse_project(value),
+        "tags" => {
+            let mut tags: Vec<String> = Vec::new();
+            for tag in value.split(',').map(|t| t.trim().trim_start_matches('#')).filter(|t| !t.is_empty()) {
+                if tag.contains(char::is_whitespace) {
+                    return Err(TaskError::InvalidArgument(t!("edit.invalid_tag", tag = tag)));
+                }
+                if !tags.iter().any(|t| t == tag) {
+                    tags.push(tag.to_string());
+                }
+            }
+            task.tags = tags;
+        }
+        "depends_on" => task.depends_on = parse_id_list(value)?,
+        name if definitions.iter().any(|d| d.name == name) => {
+            if value.is_empty() {
+                task.custom_fields.remove(name);
+            } else {
+                task.custom_fields.insert(name.to_string(), value.to_string());
+            }
+        }
+        name => return Err(TaskError::InvalidArgument(t!("edit.unknown_key", key = name))),
+    }
+    Ok(())
+}
+
+/// 誤りの内容（不正な引数の場合は接頭辞を除く）
+fn error_detail(error: TaskError) -> String {
+    match error {
+        TaskError::InvalidArgument(detail) => detail,
+        error => error.to_string(),
+    }
+}
+
+/// 文書の先頭（フロントマターの区切りの後）に誤りをコメントとして書き込む
+fn annotate_error(text: &str, message: &str) -> String {
+    let mut lines: Vec<String> = text
+        .lines()
+        .filter(|line| !line.starts_with(ERROR_MARK))
+        .map(String::from)
+        .collect();
+    let position = lines.iter().position(|line| line.trim_end() == DELIMITER).map_or(0, |i| i + 1);
+    let errors = std::iter::once(t!("edit.error_header"))
+        .chain(message.lines().map(String::from))
+        .map(|line| format!("{} {}", ERROR_MARK, line));
+    lines.splice(position..position, errors);
+    lines.join("\n") + "\n"
+}
+
+/// 文書の編集結果
+#[derive(Debug)]
+pub enum EditOutcome {
+    /// 編集して解析したタスク
+    Edited(Box<Task>),
+    /// 文書を変更せずに保存した
+    Unchanged,
+    /// 文書を空にして中止した
+    Cancelled,
+}
+
+/// 文書を編集して解析したタスクを返す
+///
+/// open は文書を受け取り、編集後の文書を返す。解析・検証に失敗した場合は誤りを書き込んだ文書で
+/// もう一度 open を呼ぶ。
+pub fn edit_document(
+    repo: &TaskRepository,
+    base: &Task,
+    definitions: &[FieldDefinition],
+    mut open: impl FnMut(&str) -> Result<String>,
+) -> Result<EditOutcome> {
+    let original = render_document(base, definitions);
+    let mut text = original.clone();
+    loop {
+        let edited = open(&text)?;
+        if edited.trim().is_empty() {
+            return Ok(EditOutcome::Cancelled);
+        }
+        if edited == original {
+            return Ok(EditOutcome::Unchanged);
+        }
+        let parsed = parse_document(&edited, base, definitions).and_then(|mut task| {
+            task.custom_fields = repo.validate_custom_fields(&task.custom_fields)?;
+            repo.validate_dependencies(task.id, &task.depends_on)?;
+            Ok(task)
+        });
+        match parsed {
+            Ok(task) => return Ok(EditOutcome::Edited(Box::new(task))),
+            Err(e) => text = annotate_error(&edited, &error_detail(e)),
+        }
+    }
+}
+
+/// 行単位の差分（最長共通部分列による）
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // common[i][j] = old[i..] と new[j..] の最長共通部分列の長さ
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut result = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            result.push(DiffLine::Same(old[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            result.push(DiffLine::Removed(old[i].to_string()));
+            i += 1;
+        } else {
+            result.push(DiffLine::Added(new[j].to_string()));
+            j += 1;
+        }
+    }
+    result
+}
+
+/// 2 つのタスクの文書の変更行（変わらない行は含まない）
+pub fn diff_tasks(before: &Task, after: &Task, definitions: &[FieldDefinition]) -> Vec<DiffLine> {
+    diff_lines(&render(before, definitions, false), &render(after, definitions, false))
+        .into_iter()
+        .filter(|line| !matches!(line, DiffLine::Same(_)))
+        .collect()
+}
+
+/// エディタでタスクを編集（--new の場合は新しいタスクを作成）するコマンド
+pub fn edit_task(id: Option<i64>, new: bool, yes: bool) -> Result<()> {
+    let mut repo = TaskRepository::new()?;
+    let definitions = repo.get_field_definitions()?;
+    let base = match id {
+        Some(id) if !new => repo.get_task(id)?,
+        _ => Task::new(String::new(), None, None, Priority::Medium, Vec::new()),
+    };
+
+    let name = base.id.map_or_else(|| "new".to_string(), |id| id.to_string());
+    let task = match edit_document(&repo, &base, &definitions, |text| open_in_editor(text, &name))? {
+        EditOutcome::Edited(task) => *task,
+        EditOutcome::Unchanged => {
+            println!("{}", t!("edit.no_changes"));
+            return Ok(());
+        }
+        EditOutcome::Cancelled => {
+            println!("{}", t!("edit.cancelled"));
+            return Ok(());
+        }
+    };
+
+    // 変更内容の確認
+    let before = if base.id.is_some() { render(&base, &definitions, false) } else { String::new() };
+    let diff = diff_lines(&before, &render(&task, &definitions, false));
+    if diff.iter().all(|line| matches!(line, DiffLine::Same(_))) {
+        println!("{}", t!("edit.no_changes"));
+        return Ok(());
+    }
+    for line in &diff {
+        match line {
+            DiffLine::Same(line) => println!("  {}", line),
+            DiffLine::Removed(line) => println!("{}", paint(&format!("- {}", line), Role::Error)),
+            DiffLine::Added(line) => println!("{}", paint(&format!("+ {}", line), Role::Done)),
+        }
+    }
+    if !yes && !confirm(&t!("edit.confirm"))? {
+        println!("{}", t!("edit.cancelled"));
+        return Ok(());
+    }
+
+    match task.id {
+        Some(id) => {
+            repo.update_task(&task)?;
+            println!("{}", t!("update.done", id = id));
+            println!("{}", format_task(&task, true));
+        }
+        None => {
+            let id = repo.add_task(&task)?;
+            println!("{}", t!("add.done", id = id));
+        }
+    }
+    Ok(())
+}
+
+/// 一時ファイルに書き出した文書をエディタで開き、編集後の内容を読み込む
+///
+/// 一時ファイルは推測できない名前で新しく作成し（所有者のみ読み書き可）、終了時に削除する。
+fn open_in_editor(text: &str, name: &str) -> Result<String> {
+    let mut file = tempfile::Builder::new()
+        .prefix(&format!("task-{}-", name))
+        .suffix(".md")
+        .tempfile()?;
+    file.write_all(text.as_bytes())?;
+    file.flush()?;
+    run_editor(file.path())?;
+    Ok(std::fs::read_to_string(file.path())?)
+}
+
+/// $VISUAL、$EDITOR（どちらもなければ vi）でファイルを開く
+fn run_editor(path: &Path) -> Result<()> {
+    let editor = ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+    let failed = |detail: String| TaskError::InvalidArgument(t!("edit.editor_failed", editor = editor, detail = detail));
+
+    // "code --wait" のように引数を含む指定も受け付ける
+    let words = shell_words::split(&editor).map_err(|e| failed(e.to_string()))?;
+    let (program, args) = words.split_first().ok_or_else(|| failed(String::new()))?;
+    let status = Command::new(program).args(args).arg(path).status().map_err(|e| failed(e.to_string()))?;
+    if !status.success() {
+        return Err(failed(status.to_string()));
+    }
+    Ok(())
+}
@@ -1,52 +1,83 @@
 use crate::db::TaskRepository;
 use crate::error::{Result, TaskError};
+use crate::models::task::Task;
+use crate::models::view::View;
+use serde::{Deserialize, Serialize};
 use serde_json;
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 
+/// JSON でエクスポートするデータ（import はタスクの配列だけの以前の形式も読み込める）
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportData {
+    pub tasks: Vec<Task>,
+    #[serde(default)]
+    pub views: Vec<View>,
+}
+
 /// タスクデータをエクスポートするコマンド
 pub fn export_tasks(file: String, format: String) -> Result<()> {
     let repo = TaskRepository::new()?;
     let tasks = repo.get_all_tasks(true)?;
     
     match format.to_lowercase().as_str() {
-        "json" => export_as_json(&file, &tasks)?,
-        "csv" => export_as_csv(&file, &tasks)?,
+        "json" => export_as_json(&file, &tasks, repo.get_views()?)?,
+        "csv" => {
+            let field_names = repo.get_field_definitions()?
+                .into_iter()
+                .map(|d| d.name)
+                .collect::<Vec<_>>();
+            export_as_csv(&file, &tasks, &field_names)?
+        },
         _ => {
-            return Err(TaskError::InvalidArgument(format!(
-                "不正なフォーマット: {}. 'json' または 'csv' を指定してください", 
-                format
-            )));
+            return Err(TaskError::InvalidArgument(t!("export.invalid_format", format = format)));
         }
     }
     
-    println!("{}件のタスクを {} にエクスポートしました", tasks.len(), file);
+    println!("{}", tn!("export.done", tasks.len(), file = file));
     Ok(())
 }
 
 /// JSONフォーマットでエクスポート
-fn export_as_json(file: &str, tasks: &[crate::models::task::Task]) -> Result<()> {
-    let json = serde_json::to_string_pretty(tasks)?;
+fn export_as_json(file: &str, tasks: &[Task], views: Vec<View>) -> Result<()> {
+    let data = ExportData { tasks: tasks.to_vec(), views };
+    let json = serde_json::to_string_pretty(&data)?;
     let mut file = File::create(Path::new(file))?;
     file.write_all(json.as_bytes())?;
     Ok(())
 }
 
-/// CSVフォーマットでエクスポート
-fn export_as_csv(file: &str, tasks: &[crate::models::task::Task]) -> Result<()> {
+/// CSV の標準の列（この後ろにカスタムフィールドの列が続く。チェックリスト・メモ・添付は JSON で出力）
+pub const CSV_COLUMNS: &[&str] = &[
+    "ID", "Title", "Description", "Created At", "Due Date",
+    "Completed", "Completed At", "Priority", "Tags", "Estimate Minutes",
+    "Project", "Status", "Start Date", "Wait Until", "Depends On",
+    "Checklist", "Notes", "Attachments",
+];
+
+/// CSVフォーマットでエクスポート（カスタムフィールドは追加の列として出力）
+fn export_as_csv(file: &str, tasks: &[Task], field_names: &[String]) -> Result<()> {
     let file = File::create(Path::new(file))?;
     let mut wtr = csv::Writer::from_writer(file);
     
     // ヘッダーの書き込み
-    wtr.write_record(&[
-        "ID", "Title", "Description", "Created At", "Due Date", 
-        "Completed", "Completed At", "Priority", "Tags"
-    ])?;
+    let mut header = CSV_COLUMNS.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    header.extend(field_names.iter().cloned());
+    wtr.write_record(&header)?;
+    
+    // 空のリストは空欄、それ以外は JSON の配列
+    fn json_list<T: Serialize>(items: &[T]) -> Result<String> {
+        if items.is_empty() {
+            Ok(String::new())
+        } else {
+            Ok(serde_json::to_string(items)?)
+        }
+    }
     
     // データの書き込み
     for task in tasks {
-        wtr.write_record(&[
+        let mut record = vec![
             task.id.map(|id| id.to_string()).unwrap_or_default(),
             task.title.clone(),
             task.description.clone().unwrap_or_default(),
@@ -56,7 +87,20 @@ fn export_as_csv(file: &str, tasks: &[crate::models::task::Task]) -> Result<()>
             task.completed_at.map(|date| date.to_rfc3339()).unwrap_or_default(),
             (task.priority as i32).to_string(),
             task.tags.join(","),
-        ])?;
+            task.estimate_minutes.map(|minutes| minutes.to_string()).unwrap_or_default(),
+            task.project.clone().unwrap_or_default(),
+            task.status.clone(),
+            task.start_date.map(|date| date.to_rfc3339()).unwrap_or_default(),
+            task.wait_until.map(|date| date.to_rfc3339()).unwrap_or_default(),
+            task.depends_on.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(","),
+            json_list(&task.checklist)?,
+            json_list(&task.notes)?,
+            json_list(&task.attachments)?,
+        ];
+        for name in field_names {
+            record.push(task.custom_fields.get(name).cloned().unwrap_or_default());
+        }
+        wtr.write_record(&record)?;
     }
     
     wtr.flush()?;
@@ -0,0 +1,61 @@
+use crate::db::TaskRepository;
+use crate::error::{Result, TaskError};
+use crate::models::field::{FieldDefinition, FieldType};
+use crate::utils::theme::{paint, Role};
+
+/// カスタムフィールドを定義するコマンド
+pub fn define_field(name: String, field_type: String, values: Option<String>) -> Result<()> {
+    let field_type = FieldType::parse(&field_type).map_err(TaskError::InvalidArgument)?;
+
+    let values = values
+        .map(|v| {
+            v.split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let definition =
+        FieldDefinition::new(name, field_type, values).map_err(TaskError::InvalidArgument)?;
+
+    let mut repo = TaskRepository::new()?;
+    repo.define_field(&definition)?;
+
+    println!(
+        "{}",
+        t!("field.defined", name = definition.name, field_type = definition.field_type)
+    );
+    Ok(())
+}
+
+/// カスタムフィールドの一覧を表示するコマンド
+pub fn list_fields() -> Result<()> {
+    let repo = TaskRepository::new()?;
+    let definitions = repo.get_field_definitions()?;
+
+    if definitions.is_empty() {
+        println!("{}", t!("field.none"));
+        return Ok(());
+    }
+
+    println!("{}", tn!("field.count", definitions.len()));
+    for definition in definitions {
+        let mut line = format!("  {} {}", paint(&definition.name, Role::Tag), definition.field_type);
+        if !definition.values.is_empty() {
+            line = format!("{} [{}]", line, definition.values.join(", "));
+        }
+        println!("{}", line);
+    }
+
+    Ok(())
+}
+
+/// カスタムフィールドを削除するコマンド
+pub fn remove_field(name: String) -> Result<()> {
+    let mut repo = TaskRepository::new()?;
+    repo.remove_field(&name)?;
+
+    println!("{}", t!("field.removed", name = name));
+    Ok(())
+}
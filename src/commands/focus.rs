@@ -0,0 +1,194 @@
+use chrono::Local;
+use colored::Colorize;
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::db::TaskRepository;
+use crate::error::{Result, TaskError};
+use crate::models::pomodoro::Pomodoro;
+use crate::utils::theme::{glyph, paint, Glyph, Role};
+
+/// 画面の更新間隔
+const TICK: Duration = Duration::from_millis(250);
+
+/// 1 区間（作業または休憩）の終わり方
+enum PhaseResult {
+    /// 最後まで終えた（一時停止していた時間を除いた経過時間）
+    Completed(Duration),
+    Skipped,
+    Quit,
+}
+
+/// ポモドーロで集中して作業するコマンド
+pub fn focus_task(id: i64, minutes: u32, break_minutes: u32, rounds: u32) -> Result<()> {
+    if minutes == 0 || rounds == 0 {
+        return Err(TaskError::InvalidArgument(t!("focus.invalid_arguments")));
+    }
+
+    let mut repo = TaskRepository::new()?;
+    let task = repo.get_task(id)?;
+
+    println!("{}", t!("focus.header", icon = glyph(Glyph::Pomodoro), id = id, title = task.title.bold()));
+    println!(
+        "{}",
+        t!("focus.plan", minutes = minutes, break_minutes = break_minutes, rounds = rounds)
+    );
+    println!("{}\n", t!("focus.controls"));
+
+    let input = spawn_input_reader();
+    let mut completed = 0;
+    let mut total_interruptions = 0;
+
+    for round in 1..=rounds {
+        // 作業
+        let mut interruptions = 0;
+        let label = t!("focus.working", round = round, rounds = rounds);
+        let result = run_phase(&label, minutes, &input, Some(&mut interruptions));
+        total_interruptions += interruptions;
+
+        match result {
+            PhaseResult::Completed(elapsed) => {
+                // 一時停止していた時間を作業時間に含めないよう、終了時刻から作業した時間だけ遡る
+                let ended_at = Local::now();
+                let elapsed = chrono::Duration::from_std(elapsed).unwrap_or_else(|_| chrono::Duration::minutes(minutes as i64));
+                let pomodoro = Pomodoro::new(
+                    id,
+                    ended_at - elapsed,
+                    ended_at,
+                    minutes as i64,
+                    interruptions as i64,
+                );
+                repo.record_pomodoro(&pomodoro)?;
+                completed += 1;
+                println!(
+                    "\x07{}",
+                    t!("focus.recorded", icon = glyph(Glyph::Pomodoro), round = round, rounds = rounds, interruptions = interruptions)
+                );
+            }
+            PhaseResult::Skipped => {
+                println!("{}", t!("focus.skipped", round = round, rounds = rounds));
+            }
+            PhaseResult::Quit => {
+                println!("{}", t!("focus.quit_during_work"));
+                break;
+            }
+        }
+
+        // 休憩（最後の回の後は不要）
+        if round < rounds && break_minutes > 0 {
+            let label = t!("focus.on_break", round = round, rounds = rounds);
+            match run_phase(&label, break_minutes, &input, None) {
+                PhaseResult::Completed(_) => println!("\x07{}", t!("focus.break_over")),
+                PhaseResult::Skipped => println!("{}", t!("focus.break_skipped")),
+                PhaseResult::Quit => {
+                    println!("{}", t!("focus.quit"));
+                    break;
+                }
+            }
+        }
+    }
+
+    println!(
+        "\n{}",
+        t!(
+            "focus.summary",
+            completed = paint(&completed.to_string(), Role::Done),
+            interruptions = total_interruptions,
+            total = repo.get_pomodoros(id)?.len(),
+        )
+    );
+
+    Ok(())
+}
+
+/// 標準入力を別スレッドで読み取り、入力された行を送る
+fn spawn_input_reader() -> Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let stdin = io::stdin();
+        for line in stdin.lock().lines() {
+            match line {
+                Ok(line) => {
+                    if tx.send(line.trim().to_lowercase()).is_err() {
+                        break;
+                    }
+                }
+                Err(_) => break,
+            }
+        }
+    });
+    rx
+}
+
+/// カウントダウンを表示しながら 1 区間を実行
+fn run_phase(
+    label: &str,
+    minutes: u32,
+    input: &Receiver<String>,
+    mut interruptions: Option<&mut u32>,
+) -> PhaseResult {
+    let total = Duration::from_secs(minutes as u64 * 60);
+    let mut elapsed = Duration::ZERO;
+    let mut paused = false;
+    let mut last_tick = Instant::now();
+    let mut input_closed = false;
+
+    loop {
+        let now = Instant::now();
+        if !paused {
+            elapsed += now - last_tick;
+        }
+        last_tick = now;
+
+        if elapsed >= total {
+            print!("\r\x1b[2K");
+            let _ = io::stdout().flush();
+            return PhaseResult::Completed(elapsed);
+        }
+
+        let remaining = total - elapsed;
+        let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+        let mut status = format!("\r\x1b[2K{} {} {:02}:{:02}", glyph(Glyph::Pomodoro), label, secs / 60, secs % 60);
+        if paused {
+            status = format!("{} {}", status, paint(&t!("focus.paused", glyph = glyph(Glyph::Paused)), Role::Warning));
+        }
+        if let Some(count) = interruptions.as_deref() {
+            if *count > 0 {
+                status = format!("{} {}", status, t!("focus.interruptions", count = count));
+            }
+        }
+        print!("{}", status);
+        let _ = io::stdout().flush();
+
+        // 入力が閉じている場合はカウントダウンのみ行う
+        if input_closed {
+            thread::sleep(TICK);
+            continue;
+        }
+
+        match input.recv_timeout(TICK) {
+            Ok(command) => match command.as_str() {
+                "p" | "pause" => paused = true,
+                "r" | "resume" => paused = false,
+                "i" | "interrupt" => {
+                    if let Some(count) = interruptions.as_deref_mut() {
+                        *count += 1;
+                    }
+                }
+                "s" | "skip" => {
+                    println!();
+                    return PhaseResult::Skipped;
+                }
+                "q" | "quit" => {
+                    println!();
+                    return PhaseResult::Quit;
+                }
+                _ => {}
+            },
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => input_closed = true,
+        }
+    }
+}
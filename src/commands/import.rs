@@ -1,6 +1,12 @@
 use crate::db::TaskRepository;
 use crate::error::{Result, TaskError};
-use crate::models::task::Task;
+use crate::commands::export::{ExportData, CSV_COLUMNS};
+use crate::models::task::{parse_project, validate_estimate, Task};
+use crate::models::view::View;
+use chrono::{DateTime, Local};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
@@ -12,131 +18,247 @@ pub fn import_tasks(file: String) -> Result<()> {
     // ファイル拡張子の確認
     let extension = path.extension()
         .and_then(|ext| ext.to_str())
-        .ok_or_else(|| TaskError::InvalidArgument("ファイル拡張子が認識できません".to_string()))?;
+        .ok_or_else(|| TaskError::InvalidArgument(t!("import.unknown_extension")))?;
     
-    let tasks = match extension.to_lowercase().as_str() {
+    let (tasks, views) = match extension.to_lowercase().as_str() {
         "json" => import_from_json(&file)?,
-        "csv" => import_from_csv(&file)?,
+        "csv" => (import_from_csv(&file)?, Vec::new()),
         _ => {
-            return Err(TaskError::InvalidArgument(format!(
-                "不正なファイル形式: {}. .json または .csv ファイルを指定してください", 
-                extension
-            )));
+            return Err(TaskError::InvalidArgument(t!("import.invalid_extension", extension = extension)));
         }
     };
     
     // データベースへのインポート
     let mut repo = TaskRepository::new()?;
     let mut success_count = 0;
+    let mut id_map = HashMap::new();
+    let mut pending_dependencies = Vec::new();
     
-    for task in tasks {
+    let definitions = repo.get_field_definitions()?;
+    
+    for mut task in tasks {
+        // 未定義または不正なカスタムフィールドの値は取り込まない
+        task.custom_fields.retain(|name, value| {
+            match definitions.iter().find(|d| &d.name == name) {
+                Some(definition) => match definition.validate(value) {
+                    Ok(normalized) => {
+                        *value = normalized;
+                        true
+                    },
+                    Err(e) => {
+                        eprintln!("{}", t!("import.warning", message = e));
+                        false
+                    },
+                },
+                None => {
+                    eprintln!("{}", t!("import.unknown_field", name = name));
+                    false
+                },
+            }
+        });
+        
+        // 範囲外の見積もりは取り込まない
+        if let Some(Err(e)) = task.estimate_minutes.map(validate_estimate) {
+            eprintln!("{}", t!("import.warning", message = e));
+            task.estimate_minutes = None;
+        }
+        
+        // 保存先の外を指すパスなど、種類に合わない添付は取り込まない
+        task.attachments.retain(|attachment| match attachment.validate() {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("{}", t!("import.warning", message = e));
+                false
+            },
+        });
+        
+        // 依存関係はインポート元のIDで記録されているため、全タスクの追加後に付け替える
+        let depends_on = std::mem::take(&mut task.depends_on);
+        
         match repo.add_task(&task) {
-            Ok(_) => success_count += 1,
-            Err(e) => eprintln!("タスク '{}' のインポートに失敗しました: {}", task.title, e),
+            Ok(new_id) => {
+                success_count += 1;
+                if let Some(old_id) = task.id {
+                    id_map.insert(old_id, new_id);
+                }
+                if !depends_on.is_empty() {
+                    pending_dependencies.push((new_id, depends_on));
+                }
+            },
+            Err(e) => eprintln!("{}", t!("import.task_failed", title = task.title, error = e)),
         }
     }
     
-    println!("{}件のタスクをインポートしました", success_count);
+    for (new_id, depends_on) in pending_dependencies {
+        let mut task = repo.get_task(new_id)?;
+        task.depends_on = depends_on.iter().filter_map(|id| id_map.get(id).copied()).collect();
+        if let Err(e) = repo.update_task(&task) {
+            eprintln!("{}", t!("import.dependencies_failed", title = task.title, error = e));
+        }
+    }
+    
+    // 表示条件は同じ名前があれば置き換える
+    let mut view_count = 0;
+    for view in views {
+        match View::validate_name(&view.name) {
+            Ok(()) => {
+                repo.save_view(&view)?;
+                view_count += 1;
+            },
+            Err(e) => eprintln!("{}", t!("import.warning", message = e)),
+        }
+    }
+    
+    println!("{}", tn!("import.done", success_count));
+    if view_count > 0 {
+        println!("{}", tn!("import.views_done", view_count));
+    }
     Ok(())
 }
 
+/// JSON ファイルの形式（以前のエクスポートはタスクの配列だけ）
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonImport {
+    Data(ExportData),
+    Tasks(Vec<Task>),
+}
+
 /// JSONファイルからインポート
-fn import_from_json(file: &str) -> Result<Vec<Task>> {
+fn import_from_json(file: &str) -> Result<(Vec<Task>, Vec<View>)> {
     let mut file = File::open(Path::new(file))?;
     let mut content = String::new();
     file.read_to_string(&mut content)?;
     
-    let tasks: Vec<Task> = serde_json::from_str(&content)?;
-    Ok(tasks)
+    match serde_json::from_str(&content)? {
+        JsonImport::Data(data) => Ok((data.tasks, data.views)),
+        JsonImport::Tasks(tasks) => Ok((tasks, Vec::new())),
+    }
 }
 
-/// CSVファイルからインポート
+/// CSVファイルからインポート（列はヘッダーの名前で対応付け、標準の列以外はカスタムフィールドとして扱う）
 fn import_from_csv(file: &str) -> Result<Vec<Task>> {
-    use chrono::DateTime;
     use crate::models::task::Priority;
     
     let file = File::open(Path::new(file))?;
     let mut rdr = csv::Reader::from_reader(file);
     let mut tasks = Vec::new();
     
+    let headers = rdr.headers()?.clone();
+    let column = |name: &str| headers.iter().position(|h| h == name);
+    let title_column = column("Title")
+        .ok_or_else(|| TaskError::InvalidArgument(t!("import.missing_column", column = "Title")))?;
+    let field_columns = headers
+        .iter()
+        .enumerate()
+        .filter(|(_, h)| !CSV_COLUMNS.contains(h))
+        .map(|(i, h)| (i, h.to_string()))
+        .collect::<Vec<_>>();
+    let columns: HashMap<&str, usize> = CSV_COLUMNS
+        .iter()
+        .filter_map(|name| column(name).map(|i| (*name, i)))
+        .collect();
+    
     for result in rdr.records() {
         let record = result?;
         
-        // レコードが十分なフィールドを持っているか確認
-        if record.len() < 9 {
-            eprintln!("警告: 一部のフィールドが不足しているレコードをスキップします");
-            continue;
-        }
-        
-        // 各フィールドの解析
-        let title = record[1].to_string();
-        let description = if record[2].is_empty() { None } else { Some(record[2].to_string()) };
-        
-        let created_at = match DateTime::parse_from_rfc3339(&record[3]) {
-            Ok(dt) => dt.with_timezone(&chrono::Local),
-            Err(_) => {
-                eprintln!("警告: 作成日時の解析に失敗しました: {}", &record[3]);
-                continue;
-            }
+        // 列がない場合や空欄は None
+        let value = |name: &str| {
+            columns
+                .get(name)
+                .and_then(|i| record.get(*i))
+                .filter(|value| !value.is_empty())
         };
-        
-        let due_date = if record[4].is_empty() {
-            None
-        } else {
-            match DateTime::parse_from_rfc3339(&record[4]) {
-                Ok(dt) => Some(dt.with_timezone(&chrono::Local)),
-                Err(_) => {
-                    eprintln!("警告: 期限日時の解析に失敗しました: {}", &record[4]);
-                    None
-                }
-            }
+        let date = |name: &str| {
+            parse(name, value(name), |v| {
+                DateTime::parse_from_rfc3339(v).ok().map(|dt| dt.with_timezone(&Local))
+            })
         };
         
-        let completed = record[5].parse::<bool>().unwrap_or(false);
-        
-        let completed_at = if record[6].is_empty() {
-            None
-        } else {
-            match DateTime::parse_from_rfc3339(&record[6]) {
-                Ok(dt) => Some(dt.with_timezone(&chrono::Local)),
+        // 作成日時が読めないレコードはスキップする
+        let created_at = match value("Created At") {
+            None => Local::now(),
+            Some(v) => match DateTime::parse_from_rfc3339(v) {
+                Ok(dt) => dt.with_timezone(&Local),
                 Err(_) => {
-                    eprintln!("警告: 完了日時の解析に失敗しました: {}", &record[6]);
-                    None
+                    eprintln!("{}", t!("import.invalid_created_at", value = v));
+                    continue;
                 }
-            }
+            },
         };
         
-        let priority = match record[7].parse::<i32>() {
-            Ok(p) => match Priority::from_i32(p) {
-                Ok(priority) => priority,
+        let priority = match value("Priority") {
+            None => Priority::Medium,
+            Some(v) => match v.parse::<i32>() {
+                Ok(p) => match Priority::from_i32(p) {
+                    Ok(priority) => priority,
+                    Err(_) => {
+                        eprintln!("{}", t!("import.invalid_priority", value = v));
+                        Priority::Medium
+                    }
+                },
                 Err(_) => {
-                    eprintln!("警告: 不正な優先度: {}, デフォルト値を使用します", &record[7]);
+                    eprintln!("{}", t!("import.unparsable_priority", value = v));
                     Priority::Medium
                 }
             },
-            Err(_) => {
-                eprintln!("警告: 優先度の解析に失敗しました: {}, デフォルト値を使用します", &record[7]);
-                Priority::Medium
-            }
         };
         
-        let tags = if record[8].is_empty() {
-            Vec::new()
-        } else {
-            record[8].split(',').map(|s| s.to_string()).collect()
+        let ids = |name: &str| {
+            parse(name, value(name), |v| {
+                v.split(',').map(|id| id.trim().parse::<i64>().ok()).collect::<Option<Vec<_>>>()
+            })
+            .unwrap_or_default()
         };
+        let tags = value("Tags")
+            .map(|v| v.split(',').map(|s| s.to_string()).collect())
+            .unwrap_or_default();
         
         // タスクの作成
-        let mut task = Task::new(title, description, None, priority, tags);
+        let title = record.get(title_column).unwrap_or_default().to_string();
+        let description = value("Description").map(|v| v.to_string());
+        let mut task = Task::new(title, description, date("Due Date"), priority, tags);
         
         // 各フィールドを設定
+        task.id = parse("ID", value("ID"), |v| v.parse().ok());
         task.created_at = created_at;
-        task.due_date = due_date;
-        task.completed = completed;
-        task.completed_at = completed_at;
+        task.completed = parse("Completed", value("Completed"), |v| v.parse().ok()).unwrap_or(false);
+        task.completed_at = date("Completed At");
+        task.estimate_minutes = parse("Estimate Minutes", value("Estimate Minutes"), |v| v.parse().ok());
+        task.project = value("Project").and_then(parse_project);
+        if let Some(status) = value("Status") {
+            task.status = status.to_string();
+        }
+        task.start_date = date("Start Date");
+        task.wait_until = date("Wait Until");
+        task.depends_on = ids("Depends On");
+        task.checklist = json("Checklist", value("Checklist"));
+        task.notes = json("Notes", value("Notes"));
+        task.attachments = json("Attachments", value("Attachments"));
+        
+        for (i, name) in &field_columns {
+            if let Some(value) = record.get(*i).filter(|value| !value.is_empty()) {
+                task.custom_fields.insert(name.clone(), value.to_string());
+            }
+        }
         
         tasks.push(task);
     }
     
     Ok(tasks)
-} 
+}
+
+/// CSV の値を解析する（解析できない値は警告して None）
+fn parse<T>(column: &str, value: Option<&str>, parse: impl FnOnce(&str) -> Option<T>) -> Option<T> {
+    let value = value?;
+    let parsed = parse(value);
+    if parsed.is_none() {
+        eprintln!("{}", t!("import.invalid_value", column = column, value = value));
+    }
+    parsed
+}
+
+/// JSON の配列として出力した CSV の値を解析する
+fn json<T: DeserializeOwned>(column: &str, value: Option<&str>) -> Vec<T> {
+    parse(column, value, |v| serde_json::from_str(v).ok()).unwrap_or_default()
+}
@@ -1,19 +1,107 @@
+use chrono::Duration;
+use colored::Colorize;
+
+use crate::commands::context::active_context;
+use crate::config::Config;
 use crate::db::TaskRepository;
 use crate::error::{Result, TaskError};
+use crate::models::field::FieldCondition;
 use crate::models::task::Priority;
-use crate::utils::format::format_task;
+use crate::models::urgency::UrgencyScorer;
+use crate::utils::duration::format_duration;
+use crate::utils::filter::TaskFilter;
+use crate::utils::format::{format_task, Template};
+use crate::utils::sort::sort_tasks;
+use crate::utils::table::{format_table, TableColumn, DEFAULT_COLUMNS};
+use crate::utils::text::terminal_width;
+use crate::utils::theme::{paint, Role};
+
+/// 一覧の表示形式
+enum Layout {
+    Line,
+    Table(Vec<TableColumn>),
+    Template(Template),
+}
 
 /// タスクの一覧を表示するコマンド
+#[allow(clippy::too_many_arguments)]
 pub fn list_tasks(
+    view: Option<String>,
+    filter: Vec<String>,
     all: bool,
     priority: Option<i32>,
     due_today: bool,
     tags: Option<String>,
+    field: Vec<String>,
+    sort: Option<String>,
+    effort: bool,
+    waiting: bool,
+    format: Option<String>,
+    columns: Option<String>,
+    template: Option<String>,
 ) -> Result<()> {
     let repo = TaskRepository::new()?;
     
+    // 保存した表示条件（指定のないオプションを補い、絞り込みの条件は --where と両方を満たすもの）
+    let (all, mut filter, sort, format, columns) = match view {
+        Some(name) => {
+            let name = name
+                .strip_prefix('@')
+                .ok_or_else(|| TaskError::InvalidArgument(t!("view.expected_at", name = name)))?;
+            let view = repo.get_view(name)?;
+            (
+                all || view.all,
+                view.filter.into_iter().chain(filter).collect::<Vec<_>>(),
+                sort.or(view.sort),
+                format.or(view.format),
+                columns.or(view.columns),
+            )
+        },
+        None => (all, filter, sort, format, columns),
+    };
+    // 有効なコンテキストの条件も満たすタスク
+    if let Some(context) = active_context(&repo)? {
+        println!("{}", context.header());
+        filter.extend(context.filter);
+    }
+    let definitions = repo.get_field_definitions()?;
+    let filters = filter
+        .iter()
+        .map(|expression| {
+            TaskFilter::parse(expression, &definitions)?.search_notes(|term| repo.search_note_task_ids(term))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    
+    // 表示形式の検証（表形式の列やテンプレートは表示の前に解析）
+    let layout = match (format.as_deref().unwrap_or("line"), template) {
+        ("line", None) => Layout::Line,
+        ("line", Some(name)) => {
+            let config = Config::load()?;
+            let definitions = repo.get_field_definitions()?;
+            let template = match config.templates.get(&name) {
+                Some(source) => Template::parse(source, &definitions)
+                    .map_err(|e| TaskError::ConfigError(t!("list.template_error", name = name, error = e)))?,
+                // 設定にない場合、波括弧を含んでいればテンプレートそのものとして扱う
+                None if name.contains('{') => Template::parse(&name, &definitions)
+                    .map_err(|e| TaskError::InvalidArgument(t!("list.inline_template_error", error = e)))?,
+                None => return Err(TaskError::ConfigError(t!("list.template_not_found", name = name))),
+            };
+            Layout::Template(template)
+        },
+        ("table", None) => {
+            let definitions = repo.get_field_definitions()?;
+            Layout::Table(TableColumn::parse_list(columns.as_deref().unwrap_or(DEFAULT_COLUMNS), &definitions)?)
+        },
+        ("table", Some(_)) => {
+            return Err(TaskError::InvalidArgument(t!("list.template_with_table")));
+        },
+        (other, _) => {
+            return Err(TaskError::InvalidArgument(t!("list.invalid_format", format = other)));
+        },
+    };
+    
     // 取得方法の選択（優先度、期限日、タグによるフィルタリング）
-    let tasks = if let Some(p) = priority {
+    let mut tasks = if let Some(p) = priority {
         let priority = Priority::from_i32(p).map_err(|_| TaskError::InvalidPriority(p))?;
         repo.get_tasks_by_priority(priority)?
     } else if due_today {
@@ -24,16 +112,101 @@ pub fn list_tasks(
         repo.get_all_tasks(all)?
     };
     
+    // 待機中のタスクは --waiting を指定した場合のみ表示
+    if !waiting {
+        tasks.retain(|task| !task.is_waiting());
+    }
+    tasks.retain(|task| filters.iter().all(|filter| filter.matches(task)));
+    
+    // カスタムフィールドによるフィルタリングと並び替え
+    if !field.is_empty() || sort.is_some() {
+        let definitions = repo.get_field_definitions()?;
+        
+        for condition in field {
+            let condition = FieldCondition::parse(&condition).map_err(TaskError::InvalidArgument)?;
+            let definition = definitions
+                .iter()
+                .find(|d| d.name == condition.name)
+                .ok_or_else(|| TaskError::FieldNotFound(condition.name.clone()))?;
+            tasks.retain(|task| {
+                condition.matches(definition, task.custom_fields.get(&condition.name).map(String::as_str))
+            });
+        }
+        
+        if let Some(key) = sort {
+            let scorer = if key.trim_start_matches('-') == "urgency" {
+                let config = Config::load()?;
+                Some(UrgencyScorer::new(config.urgency, repo.get_blocked_task_ids()?))
+            } else {
+                None
+            };
+            sort_tasks(&mut tasks, &key, &definitions, scorer.as_ref())?;
+        }
+    }
+    
     // タスクがない場合
     if tasks.is_empty() {
-        println!("タスクが見つかりませんでした");
+        println!("{}", t!("list.none"));
         return Ok(());
     }
     
     // タスクの表示
-    println!("全{}件のタスク:", tasks.len());
-    for task in tasks {
-        println!("{}", format_task(&task, false));
+    println!("{}", tn!("list.count", tasks.len()));
+    match layout {
+        Layout::Line => {
+            for task in &tasks {
+                println!("{}", format_task(task, false));
+            }
+        },
+        Layout::Template(template) => {
+            for task in &tasks {
+                println!("{}", template.render(task));
+            }
+        },
+        Layout::Table(columns) => {
+            let scorer = if columns.contains(&TableColumn::Urgency) {
+                let config = Config::load()?;
+                Some(UrgencyScorer::new(config.urgency, repo.get_blocked_task_ids()?))
+            } else {
+                None
+            };
+            
+            let lines = format_table(&tasks, &columns, scorer.as_ref(), terminal_width());
+            println!("{}", lines[0].bold().underline());
+            for (task, line) in tasks.iter().zip(&lines[1..]) {
+                if task.completed {
+                    println!("{}", paint(line, Role::Muted));
+                } else if task.is_overdue() {
+                    println!("{}", paint(line, Role::Overdue));
+                } else {
+                    println!("{}", line);
+                }
+            }
+        },
+    }
+    
+    // 残り見積もり時間の合計（見積もりから記録済みの作業時間を差し引く）
+    if effort {
+        let tracked = repo.get_all_tracked_times()?;
+        let mut remaining = Duration::zero();
+        let mut unestimated = 0;
+        for task in tasks.iter().filter(|t| !t.completed) {
+            match task.estimate() {
+                Some(estimate) => {
+                    let spent = task.id
+                        .and_then(|id| tracked.get(&id).copied())
+                        .unwrap_or_else(Duration::zero);
+                    remaining += (estimate - spent).max(Duration::zero());
+                },
+                None => unestimated += 1,
+            }
+        }
+        
+        let mut effort_str = t!("list.effort", duration = format_duration(remaining).bold());
+        if unestimated > 0 {
+            effort_str = format!("{}{}", effort_str, tn!("list.unestimated", unestimated));
+        }
+        println!("{}", effort_str);
     }
     
     Ok(())
@@ -1,19 +1,231 @@
 pub mod add;
+pub mod bulk;
 pub mod list;
 pub mod show;
+pub mod note;
+pub mod attach;
+pub mod next;
+pub mod agenda;
+pub mod calendar;
+pub mod board;
+pub mod timeline;
+pub mod snooze;
 pub mod update;
+pub mod edit;
 pub mod complete;
 pub mod delete;
 pub mod stats;
 pub mod export;
 pub mod import;
+pub mod field;
+pub mod view;
+pub mod context;
+pub mod check;
+pub mod template;
+pub mod timer;
+pub mod report;
+pub mod focus;
+pub mod tui;
+pub mod shell;
 
 pub use add::add_task;
 pub use list::list_tasks;
 pub use show::show_task;
+pub use note::{add_note, search_notes};
+pub use attach::{attach_file, clean_attachments, detach, link_url, open_attachment};
+pub use next::next_tasks;
+pub use agenda::show_agenda;
+pub use calendar::show_calendar;
+pub use board::{move_task, show_board};
+pub use timeline::show_timeline;
+pub use snooze::snooze_task;
 pub use update::update_task;
+pub use edit::edit_task;
 pub use complete::{complete_task, uncomplete_task};
 pub use delete::delete_task;
 pub use stats::show_stats;
 pub use export::export_tasks;
-pub use import::import_tasks; 
+pub use import::import_tasks;
+pub use field::{define_field, list_fields, remove_field};
+pub use view::{list_views, remove_view, save_view};
+pub use context::{clear_context, set_context, show_context};
+pub use check::{add_check_item, remove_check_item, toggle_check_item};
+pub use template::{apply_template, list_templates, remove_template, save_template};
+pub use timer::{log_time, start_timer, stop_timer, timer_status};
+pub use report::{report_estimate, report_time};
+pub use focus::focus_task;
+pub use tui::run_tui;
+pub use shell::run_shell;
+
+use crate::cli::{Commands, FieldCommands, ReportCommands, TimerCommands, ViewCommands, ContextCommands, CheckCommands, TemplateCommands};
+use crate::error::Result; 
+
+/// コマンドを実行（shell からも同じ関数で実行する）
+pub fn run(command: Commands) -> Result<()> {
+    match command {
+        Commands::Add { title, description, due, priority, tags, project, set, estimate, depends_on, start, wait } => {
+            add_task(title, description, due, priority, tags, project, set, estimate, depends_on, start, wait)
+        },
+        Commands::List { view, filter, all, priority, due_today, tags, field, sort, effort, waiting, format, columns, template } => {
+            list_tasks(view, filter, all, priority, due_today, tags, field, sort, effort, waiting, format, columns, template)
+        },
+        Commands::Next { count } => {
+            next_tasks(count)
+        },
+        Commands::Agenda { days } => {
+            show_agenda(days)
+        },
+        Commands::Calendar { date, week } => {
+            show_calendar(date, week)
+        },
+        Commands::Board { by, all } => {
+            show_board(by, all)
+        },
+        Commands::Move { id, column, by } => {
+            move_task(id, column, by)
+        },
+        Commands::Timeline { from, to, all } => {
+            show_timeline(from, to, all)
+        },
+        Commands::Snooze { id, duration } => {
+            snooze_task(id, duration)
+        },
+        Commands::Note { id, message } => {
+            add_note(id, message)
+        },
+        Commands::Search { query } => {
+            search_notes(query)
+        },
+        Commands::Attach { id, file, no_copy } => {
+            attach_file(id, file, no_copy)
+        },
+        Commands::Link { id, url } => {
+            link_url(id, url)
+        },
+        Commands::Open { id, n } => {
+            open_attachment(id, n)
+        },
+        Commands::Detach { id, n } => {
+            detach(id, n)
+        },
+        Commands::CleanAttachments { dry_run } => {
+            clean_attachments(dry_run)
+        },
+        Commands::Show { id } => {
+            show_task(id)
+        },
+        Commands::Update { ids, filter, dry_run, yes, add_tag, remove_tag, clear_tags, title, description, due, remove_due, priority, tags, project, remove_project, set, estimate, remove_estimate, depends_on, start, remove_start, wait, remove_wait } => {
+            update_task(ids, filter, title, description, due, remove_due, priority, tags, add_tag, remove_tag, clear_tags, project, remove_project, set, estimate, remove_estimate, depends_on, start, remove_start, wait, remove_wait, dry_run, yes)
+        },
+        Commands::Edit { id, new, yes } => {
+            edit_task(id, new, yes)
+        },
+        Commands::Complete { ids, filter, dry_run, yes } => {
+            complete_task(ids, filter, dry_run, yes)
+        },
+        Commands::Uncomplete { ids, filter, dry_run, yes } => {
+            uncomplete_task(ids, filter, dry_run, yes)
+        },
+        Commands::Delete { ids, completed, filter, dry_run, yes } => {
+            delete_task(ids, filter, completed, dry_run, yes)
+        },
+        Commands::Stats => {
+            show_stats()
+        },
+        Commands::Export { file, format } => {
+            export_tasks(file, format)
+        },
+        Commands::Import { file } => {
+            import_tasks(file)
+        },
+        Commands::Field { action } => match action {
+            FieldCommands::Define { name, field_type, values } => {
+                define_field(name, field_type, values)
+            },
+            FieldCommands::List => {
+                list_fields()
+            },
+            FieldCommands::Remove { name } => {
+                remove_field(name)
+            },
+        },
+        Commands::View { action } => match action {
+            ViewCommands::Save { name, filter, sort, format, columns, all } => {
+                save_view(name, filter, sort, format, columns, all)
+            },
+            ViewCommands::List => {
+                list_views()
+            },
+            ViewCommands::Remove { name } => {
+                remove_view(name)
+            },
+        },
+        Commands::Context { action } => match action {
+            ContextCommands::Set { name, filter } => {
+                set_context(name, filter)
+            },
+            ContextCommands::Clear => {
+                clear_context()
+            },
+            ContextCommands::Show => {
+                show_context()
+            },
+        },
+        Commands::Check { action } => match action {
+            CheckCommands::Add { id, text } => {
+                add_check_item(id, text)
+            },
+            CheckCommands::Toggle { id, n } => {
+                toggle_check_item(id, n)
+            },
+            CheckCommands::Remove { id, n } => {
+                remove_check_item(id, n)
+            },
+        },
+        Commands::Template { action } => match action {
+            TemplateCommands::Save { name, id } => {
+                save_template(name, id)
+            },
+            TemplateCommands::Apply { name, due, vars } => {
+                apply_template(name, due, vars)
+            },
+            TemplateCommands::List => {
+                list_templates()
+            },
+            TemplateCommands::Remove { name } => {
+                remove_template(name)
+            },
+        },
+        Commands::Timer { action } => match action {
+            TimerCommands::Start { id } => {
+                start_timer(id)
+            },
+            TimerCommands::Stop => {
+                stop_timer()
+            },
+            TimerCommands::Status => {
+                timer_status()
+            },
+        },
+        Commands::LogTime { id, duration, date } => {
+            log_time(id, duration, date)
+        },
+        Commands::Focus { id, minutes, break_minutes, rounds } => {
+            focus_task(id, minutes, break_minutes, rounds)
+        },
+        Commands::Tui => {
+            run_tui()
+        },
+        Commands::Shell => {
+            run_shell()
+        },
+        Commands::Report { kind } => match kind {
+            ReportCommands::Time { week, from, to } => {
+                report_time(week, from, to)
+            },
+            ReportCommands::Estimate { all } => {
+                report_estimate(all)
+            },
+        },
+    }
+}
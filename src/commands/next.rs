@@ -0,0 +1,53 @@
+use colored::Colorize;
+
+use crate::commands::context::retain_in_context;
+use crate::config::Config;
+use crate::db::TaskRepository;
+use crate::error::Result;
+use crate::models::urgency::UrgencyScorer;
+use crate::utils::format::format_task;
+use crate::utils::theme::{glyph, Glyph};
+
+/// 次にやるべきタスクを緊急度の高い順に表示するコマンド
+pub fn next_tasks(count: usize) -> Result<()> {
+    let repo = TaskRepository::new()?;
+    let config = Config::load()?;
+    let scorer = UrgencyScorer::new(config.urgency, repo.get_blocked_task_ids()?);
+    
+    let mut tasks = repo.get_all_tasks(false)?;
+    if let Some(context) = retain_in_context(&repo, &mut tasks)? {
+        println!("{}", context.header());
+    }
+    
+    // ブロック中・待機中のタスクを除いて緊急度を計算
+    let mut scored = tasks
+        .into_iter()
+        .filter(|task| !scorer.is_blocked(task) && !task.is_waiting())
+        .map(|task| {
+            let urgency = scorer.score(&task);
+            (task, urgency)
+        })
+        .collect::<Vec<_>>();
+    
+    if scored.is_empty() {
+        println!("{}", t!("next.none"));
+        return Ok(());
+    }
+    
+    scored.sort_by(|a, b| b.1.total.total_cmp(&a.1.total));
+    
+    println!("{}", t!("next.header", icon = glyph(Glyph::Target)));
+    for (task, urgency) in scored.iter().take(count) {
+        println!("{}", format_task(task, false));
+        println!(
+            "    {}",
+            t!(
+                "next.urgency",
+                total = format!("{:.1}", urgency.total).bold(),
+                explanation = urgency.explain(),
+            )
+        );
+    }
+    
+    Ok(())
+}
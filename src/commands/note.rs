@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+
+use crate::commands::context::retain_in_context;
+use crate::db::TaskRepository;
+use crate::error::{Result, TaskError};
+use crate::models::note::Note;
+use crate::utils::date::format_datetime;
+use crate::utils::theme::{paint, Role};
+
+/// タスクに日時付きのメモを追加するコマンド
+pub fn add_note(id: i64, message: String) -> Result<()> {
+    let message = message.trim().to_string();
+    if message.is_empty() {
+        return Err(TaskError::InvalidArgument(t!("note.empty")));
+    }
+
+    let mut repo = TaskRepository::new()?;
+    repo.add_note(id, &Note::new(message))?;
+
+    println!("{}", t!("note.added", id = id));
+    Ok(())
+}
+
+/// メモを全文検索するコマンド（有効なコンテキストのタスクのメモを関連度の高い順に表示）
+pub fn search_notes(query: Vec<String>) -> Result<()> {
+    let query = query.join(" ").trim().to_string();
+    if query.is_empty() {
+        return Err(TaskError::InvalidArgument(t!("note.empty_query")));
+    }
+
+    let repo = TaskRepository::new()?;
+    let matches = repo.search_notes(&query)?;
+
+    // 一致したメモのあるタスク（コンテキストの条件に合うものだけ）
+    let mut ids: Vec<i64> = matches.iter().map(|m| m.task_id).collect();
+    ids.sort_unstable();
+    ids.dedup();
+    let mut tasks = ids.into_iter().map(|id| repo.get_task(id)).collect::<Result<Vec<_>>>()?;
+    if let Some(context) = retain_in_context(&repo, &mut tasks)? {
+        println!("{}", context.header());
+    }
+    let titles: HashMap<i64, String> = tasks
+        .into_iter()
+        .filter_map(|task| Some((task.id?, task.title)))
+        .collect();
+    let matches: Vec<_> = matches.into_iter().filter(|m| titles.contains_key(&m.task_id)).collect();
+
+    if matches.is_empty() {
+        println!("{}", t!("note.not_found", query = query));
+        return Ok(());
+    }
+
+    println!("{}", tn!("note.found", matches.len(), query = query));
+    for m in &matches {
+        println!("  {} {}", paint(&format!("{:>3}", m.task_id), Role::Id), titles[&m.task_id]);
+        println!(
+            "      {}  {}",
+            paint(&format_datetime(m.note.created_at), Role::Muted),
+            m.snippet.split_whitespace().collect::<Vec<_>>().join(" ")
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,251 @@
+use chrono::{Duration, Local};
+use colored::Colorize;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::config::Config;
+use crate::db::TaskRepository;
+use crate::error::{Result, TaskError};
+use crate::models::task::Task;
+use crate::utils::date::{format_datetime, parse_date, start_of_day, start_of_week};
+use crate::utils::duration::format_duration;
+use crate::utils::theme::{glyph, paint, Glyph, Role};
+
+/// 作業時間のレポートを表示するコマンド
+pub fn report_time(week: bool, from: Option<String>, to: Option<String>) -> Result<()> {
+    if week && (from.is_some() || to.is_some()) {
+        return Err(TaskError::InvalidArgument(t!("report.week_with_range")));
+    }
+
+    // 集計期間の決定
+    let (from, to) = if week {
+        let config = Config::load()?;
+        let first_day = start_of_week(Local::now().date_naive(), config.week_start);
+        let start = start_of_day(first_day);
+        (Some(start), Some(start_of_day(first_day + Duration::days(7))))
+    } else {
+        (
+            from.map(|d| parse_date(&d)).transpose()?,
+            to.map(|d| parse_date(&d)).transpose()?,
+        )
+    };
+
+    let repo = TaskRepository::new()?;
+    let entries = repo.get_time_entries_between(from, to)?;
+
+    if entries.is_empty() {
+        println!("{}", t!("report.no_entries"));
+        return Ok(());
+    }
+
+    // タスクごとに集計
+    let range_from = from.unwrap_or_else(|| entries[0].started_at);
+    let range_to = to.unwrap_or_else(Local::now);
+    let mut by_task: HashMap<i64, Duration> = HashMap::new();
+    for entry in &entries {
+        *by_task.entry(entry.task_id).or_insert_with(Duration::zero) +=
+            entry.duration_within(range_from, range_to);
+    }
+
+    // タグ・プロジェクトごとに集計（複数のタグを持つタスクは各タグに計上）
+    let mut task_rows = Vec::new();
+    let mut by_tag: BTreeMap<String, Duration> = BTreeMap::new();
+    let mut by_project: BTreeMap<Option<String>, Duration> = BTreeMap::new();
+    let mut total = Duration::zero();
+    for (task_id, duration) in by_task {
+        let task = repo.get_task(task_id)?;
+        if task.tags.is_empty() {
+            *by_tag.entry(t!("report.untagged")).or_insert_with(Duration::zero) += duration;
+        }
+        for tag in &task.tags {
+            *by_tag.entry(format!("#{}", tag)).or_insert_with(Duration::zero) += duration;
+        }
+        *by_project.entry(task.project.clone()).or_insert_with(Duration::zero) += duration;
+        total += duration;
+        task_rows.push((task_id, task.title, duration));
+    }
+    task_rows.sort_by_key(|row| std::cmp::Reverse(row.2));
+
+    println!(
+        "{}",
+        t!("report.time_header", icon = glyph(Glyph::Running), from = format_datetime(range_from), to = format_datetime(range_to))
+    );
+
+    println!("\n{}", t!("report.by_task"));
+    for (task_id, title, duration) in &task_rows {
+        println!(
+            "  {} {:>7} {}",
+            paint(&format!("{:>3}", task_id), Role::Id),
+            format_duration(*duration),
+            title
+        );
+    }
+
+    let mut tag_rows = by_tag.into_iter().collect::<Vec<_>>();
+    tag_rows.sort_by_key(|row| std::cmp::Reverse(row.1));
+    println!("\n{}", t!("report.by_tag"));
+    for (tag, duration) in tag_rows {
+        println!("  {:>7} {}", format_duration(duration), paint(&tag, Role::Tag));
+    }
+
+    // プロジェクトのあるタスクがない場合は省略
+    if by_project.keys().any(Option::is_some) {
+        let mut project_rows = by_project.into_iter().collect::<Vec<_>>();
+        project_rows.sort_by_key(|row| std::cmp::Reverse(row.1));
+        println!("\n{}", t!("report.by_project"));
+        for (project, duration) in project_rows {
+            let label = project.unwrap_or_else(|| t!("report.no_project"));
+            println!("  {:>7} {}", format_duration(duration), label);
+        }
+    }
+
+    println!("\n{}", t!("report.total", duration = format_duration(total).bold()));
+
+    Ok(())
+}
+
+/// 見積もりと実績の集計
+#[derive(Debug, Default)]
+pub struct EstimateSummary {
+    pub count: usize,
+    /// 実績が見積もりを超えたタスクの数
+    pub over_count: usize,
+    pub estimate: Duration,
+    pub actual: Duration,
+}
+
+impl EstimateSummary {
+    fn add(&mut self, estimate: Duration, actual: Duration) {
+        self.count += 1;
+        if actual > estimate {
+            self.over_count += 1;
+        }
+        self.estimate += estimate;
+        self.actual += actual;
+    }
+
+    /// 実績 / 見積もり の比率
+    pub fn ratio(&self) -> f64 {
+        if self.estimate > Duration::zero() {
+            self.actual.num_seconds() as f64 / self.estimate.num_seconds() as f64
+        } else {
+            0.0
+        }
+    }
+
+    /// 実績が見積もりを 2 割以上超え、かつ過半数のタスクで超過している場合は慢性的な過小見積もり
+    pub fn is_chronically_underestimated(&self) -> bool {
+        self.count >= 2 && self.ratio() >= 1.2 && self.over_count * 2 > self.count
+    }
+}
+
+/// 見積もりと実績の比較（全体・タグ別・プロジェクト別）
+#[derive(Debug, Default)]
+pub struct EstimateReport {
+    pub overall: EstimateSummary,
+    pub by_tag: BTreeMap<String, EstimateSummary>,
+    pub by_project: BTreeMap<String, EstimateSummary>,
+}
+
+/// 見積もりのあるタスクの見積もりと実績を集計する（all が false の場合は完了したタスクだけ）
+///
+/// 実績は記録した作業時間、記録がなければ作成から完了までの経過時間。
+pub fn summarize_estimates(tasks: &[Task], tracked: &HashMap<i64, Duration>, all: bool) -> EstimateReport {
+    let mut report = EstimateReport::default();
+
+    for task in tasks {
+        let Some(estimate) = task.estimate() else {
+            continue;
+        };
+
+        let spent = task.id.and_then(|id| tracked.get(&id).copied());
+        let actual = if task.completed {
+            match (spent, task.completed_at) {
+                (Some(spent), _) => spent,
+                (None, Some(completed_at)) => completed_at.signed_duration_since(task.created_at),
+                (None, None) => continue,
+            }
+        } else if all {
+            spent.unwrap_or_else(Duration::zero)
+        } else {
+            continue;
+        };
+
+        report.overall.add(estimate, actual);
+        if task.tags.is_empty() {
+            report.by_tag.entry(t!("report.untagged")).or_default().add(estimate, actual);
+        }
+        for tag in &task.tags {
+            report.by_tag.entry(format!("#{}", tag)).or_default().add(estimate, actual);
+        }
+        if let Some(project) = &task.project {
+            report.by_project.entry(project.clone()).or_default().add(estimate, actual);
+        }
+    }
+
+    report
+}
+
+/// 見積もりと実績を比較するレポートを表示するコマンド
+pub fn report_estimate(all: bool) -> Result<()> {
+    let repo = TaskRepository::new()?;
+    let tasks = repo.get_all_tasks(true)?;
+    let tracked = repo.get_all_tracked_times()?;
+    let EstimateReport { overall, by_tag, by_project } = summarize_estimates(&tasks, &tracked, all);
+
+    if overall.count == 0 {
+        println!("{}", t!("report.no_estimates"));
+        return Ok(());
+    }
+
+    println!("{}\n", t!("report.estimate_header", icon = glyph(Glyph::Ruler)));
+    // 全角の見出しは表示幅が 2 倍になるため、列幅に合わせて空白を調整した見出しをカタログに置く
+    println!("{}", t!("report.estimate_columns"));
+    for (tag, summary) in &by_tag {
+        print_estimate_row(&paint(tag, Role::Tag).to_string(), summary);
+    }
+    // プロジェクトのあるタスクがない場合は省略
+    if !by_project.is_empty() {
+        println!("\n{}", t!("report.estimate_project_columns"));
+        for (project, summary) in &by_project {
+            print_estimate_row(project, summary);
+        }
+    }
+    println!();
+    print_estimate_row(&t!("report.overall").bold().to_string(), &overall);
+
+    let underestimated = by_tag
+        .iter()
+        .chain(&by_project)
+        .filter(|(_, s)| s.is_chronically_underestimated())
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+    if !underestimated.is_empty() {
+        println!(
+            "\n{} {}",
+            paint(&t!("report.underestimated", icon = glyph(Glyph::Warning)), Role::Error),
+            underestimated.join(" ")
+        );
+    }
+
+    Ok(())
+}
+
+/// 見積もりレポートの 1 行を表示
+fn print_estimate_row(label: &str, summary: &EstimateSummary) {
+    let ratio = format!("{:.0}%", summary.ratio() * 100.0);
+    let ratio = if summary.is_chronically_underestimated() {
+        paint(&ratio, Role::Error)
+    } else if summary.ratio() > 1.0 {
+        paint(&ratio, Role::Warning)
+    } else {
+        paint(&ratio, Role::Done)
+    };
+    println!(
+        "  {:>6} {:>10} {:>8} {:>6}  {}",
+        summary.count,
+        format_duration(summary.estimate),
+        format_duration(summary.actual),
+        ratio,
+        label
+    );
+}
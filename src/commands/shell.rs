@@ -0,0 +1,383 @@
+use clap::{Command, CommandFactory, Parser};
+use rustyline::completion::{Completer, Pair};
+use rustyline::config::{CompletionType, Config};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::FileHistory;
+use rustyline::validate::Validator;
+use rustyline::{Editor, Helper};
+
+use crate::cli::{Cli, Commands};
+use crate::db::schema::{data_dir, set_connection_reuse};
+use crate::db::TaskRepository;
+use crate::error::{Result, TaskError};
+use crate::utils::filter::TaskFilter;
+use crate::utils::theme::{paint, Role};
+
+/// 履歴ファイル名（データディレクトリ内）
+const HISTORY_FILE: &str = "shell_history";
+
+/// コマンド以外に入力できる語
+const BUILTINS: [&str; 4] = ["filter", "help", "exit", "quit"];
+
+/// 1 行を実行した後の動作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    Continue,
+    Exit,
+}
+
+/// シェルの状態
+///
+/// 絞り込み（filter set）は list のオプションとして保持し、以降の list の省略したオプションと、
+/// add のタグ・プロジェクトに適用する（シェルを終了すると消える。保存するコンテキストは context set）。
+#[derive(Debug, Default)]
+pub struct Shell {
+    filter: Vec<String>,
+}
+
+impl Shell {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 現在の絞り込み（list のオプション）
+    pub fn filter(&self) -> &[String] {
+        &self.filter
+    }
+
+    /// 入力を促す文字列（絞り込みがあれば表示）
+    pub fn prompt(&self) -> String {
+        match self.filter() {
+            [] => "task> ".to_string(),
+            filter => format!("task [{}]> ", shell_words::join(filter)),
+        }
+    }
+
+    /// 1 行を実行
+    pub fn execute(&mut self, line: &str) -> Result<Flow> {
+        let words = shell_words::split(line)
+            .map_err(|e| TaskError::InvalidArgument(t!("shell.unbalanced_quotes", detail = e)))?;
+        let Some(first) = words.first() else {
+            return Ok(Flow::Continue);
+        };
+
+        match first.as_str() {
+            "exit" | "quit" => return Ok(Flow::Exit),
+            "filter" => {
+                self.run_filter(&words[1..])?;
+                return Ok(Flow::Continue);
+            }
+            _ => {}
+        }
+
+        let cli = match Cli::try_parse_from(std::iter::once("task".to_string()).chain(words)) {
+            Ok(cli) => cli,
+            Err(e) => {
+                // ヘルプや引数の誤りは clap の表示のまま出力する
+                e.print()?;
+                return Ok(Flow::Continue);
+            }
+        };
+        let mut command = cli.command;
+        if matches!(command, Commands::Shell) {
+            return Err(TaskError::InvalidArgument(t!("shell.nested")));
+        }
+        self.apply_filter(&mut command)?;
+        super::run(command)?;
+        Ok(Flow::Continue)
+    }
+
+    /// filter コマンド（保存するコンテキストと同じく set で設定、none で解除、show または引数なしで表示）
+    fn run_filter(&mut self, args: &[String]) -> Result<()> {
+        let (verb, options) = match args.split_first() {
+            Some((verb, options)) => (verb.as_str(), options),
+            None => ("show", args),
+        };
+        match (verb, options) {
+            ("show", []) => match self.filter.as_slice() {
+                [] => println!("{}", t!("shell.filter_none")),
+                filter => println!("{}", t!("shell.filter_current", args = shell_words::join(filter))),
+            },
+            ("none", []) => {
+                self.filter.clear();
+                println!("{}", t!("shell.filter_cleared"));
+            }
+            ("set", options) if !options.is_empty() => {
+                parse_filter(options)?;
+                self.filter = options.to_vec();
+                println!("{}", t!("shell.filter_set", args = shell_words::join(&self.filter)));
+            }
+            _ => return Err(TaskError::InvalidArgument(t!("shell.filter_usage"))),
+        }
+        Ok(())
+    }
+
+    /// 絞り込みをコマンドに適用（list は指定のないオプションを補い、add はタグ・プロジェクトを加える）
+    pub fn apply_filter(&self, command: &mut Commands) -> Result<()> {
+        if self.filter.is_empty() {
+            return Ok(());
+        }
+        let Commands::List {
+            view: shell_view,
+            filter: shell_filter,
+            all: shell_all,
+            priority: shell_priority,
+            due_today: shell_due_today,
+            tags: shell_tags,
+            field: shell_field,
+            sort: shell_sort,
+            effort: shell_effort,
+            waiting: shell_waiting,
+            format: shell_format,
+            columns: shell_columns,
+            template: shell_template,
+        } = parse_filter(&self.filter)?
+        else {
+            unreachable!("絞り込みは list として解析している");
+        };
+
+        match command {
+            Commands::List { view, filter, all, priority, due_today, tags, field, sort, effort, waiting, format, columns, template } => {
+                *view = view.take().or(shell_view);
+                filter.splice(0..0, shell_filter);
+                *all |= shell_all;
+                *priority = priority.or(shell_priority);
+                *due_today |= shell_due_today;
+                *tags = tags.take().or(shell_tags);
+                field.extend(shell_field);
+                *sort = sort.take().or(shell_sort);
+                *effort |= shell_effort;
+                *waiting |= shell_waiting;
+                *format = format.take().or(shell_format);
+                *columns = columns.take().or(shell_columns);
+                *template = template.take().or(shell_template);
+            }
+            Commands::Add { tags, project, .. } => {
+                // 条件でプロジェクトを指定している場合はそのプロジェクトにする
+                if project.is_none() {
+                    let definitions = TaskRepository::new()?.get_field_definitions()?;
+                    for expression in &shell_filter {
+                        *project = project.take().or(TaskFilter::parse(expression, &definitions)?.required_project());
+                    }
+                }
+                if let Some(shell_tags) = shell_tags {
+                    let mut merged: Vec<&str> = Vec::new();
+                    for tag in tags.iter().chain([&shell_tags]).flat_map(|t| t.split(',')).map(str::trim) {
+                        if !tag.is_empty() && !merged.contains(&tag) {
+                            merged.push(tag);
+                        }
+                    }
+                    *tags = Some(merged.join(","));
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// 絞り込みを list のオプションとして解析
+fn parse_filter(args: &[String]) -> Result<Commands> {
+    let argv = ["task", "list"].into_iter().map(String::from).chain(args.iter().cloned());
+    Cli::try_parse_from(argv).map(|cli| cli.command).map_err(|e| {
+        let detail = e.to_string().lines().next().unwrap_or_default().to_string();
+        TaskError::InvalidArgument(t!("shell.invalid_filter", detail = detail))
+    })
+}
+
+/// 対話的にコマンドを実行するコマンド
+pub fn run_shell() -> Result<()> {
+    // 最初に接続してスキーマを確認し、以降のコマンドでは同じ接続を使う
+    set_connection_reuse(true);
+    let result = TaskRepository::new().and_then(|repo| {
+        drop(repo);
+        repl()
+    });
+    set_connection_reuse(false);
+    result
+}
+
+fn repl() -> Result<()> {
+    let config = Config::builder().completion_type(CompletionType::List).auto_add_history(true).build();
+    let mut editor: Editor<ShellHelper, FileHistory> = Editor::with_config(config).map_err(readline_error)?;
+    editor.set_helper(Some(ShellHelper { command: Cli::command(), data: CompletionData::default() }));
+
+    let history = data_dir()?.join(HISTORY_FILE);
+    // 初回は履歴ファイルがない
+    let _ = editor.load_history(&history);
+
+    println!("{}", t!("shell.welcome"));
+    let mut shell = Shell::new();
+    loop {
+        // 前の行のコマンドで変わったタスク・タグを候補に反映する
+        if let Some(helper) = editor.helper_mut() {
+            helper.data = CompletionData::load();
+        }
+        match editor.readline(&shell.prompt()) {
+            Ok(line) => match shell.execute(&line) {
+                Ok(Flow::Continue) => {}
+                Ok(Flow::Exit) => break,
+                Err(e) => eprintln!("{} {}", paint(&t!("error.prefix"), Role::Error), e),
+            },
+            // Ctrl-C は入力中の行を破棄する
+            Err(ReadlineError::Interrupted) => {}
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(readline_error(e)),
+        }
+    }
+
+    editor.save_history(&history).map_err(readline_error)?;
+    Ok(())
+}
+
+fn readline_error(e: ReadlineError) -> TaskError {
+    match e {
+        ReadlineError::Io(e) => TaskError::IoError(e),
+        e => TaskError::Unknown(e.to_string()),
+    }
+}
+
+/// 補完の候補に使うデータ
+#[derive(Debug, Clone, Default)]
+pub struct CompletionData {
+    /// 未完了のタスクのIDとタイトル
+    pub tasks: Vec<(i64, String)>,
+    pub tags: Vec<String>,
+    pub projects: Vec<String>,
+}
+
+impl CompletionData {
+    /// データベースから読み込む（失敗した場合は候補なし）
+    fn load() -> Self {
+        let load = || -> Result<Self> {
+            let repo = TaskRepository::new()?;
+            let tasks = repo
+                .get_all_tasks(false)?
+                .into_iter()
+                .filter_map(|task| task.id.map(|id| (id, task.title)))
+                .collect();
+            let tags = repo.get_all_tags()?.into_iter().map(|tag| tag.name).collect();
+            let projects = repo.get_all_projects()?;
+            Ok(Self { tasks, tags, projects })
+        };
+        load().unwrap_or_default()
+    }
+}
+
+/// 行末の語の補完候補（置き換える位置と候補）
+///
+/// 先頭の語はコマンド、`-` で始まる語はオプション、タグ・依存先のオプションの後はタグ・ID
+/// （カンマ区切りの最後の要素）、プロジェクトのオプションの後はプロジェクト、
+/// ID を取るコマンドの引数は未完了のタスクのIDを補完する。
+pub fn complete_line(command: &Command, line: &str, data: &CompletionData) -> (usize, Vec<Pair>) {
+    let current_start = line.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+    let current = &line[current_start..];
+    let previous: Vec<&str> = line[..current_start].split_whitespace().collect();
+
+    let pair = |replacement: String, display: String| Pair { display, replacement };
+    let by_prefix = |names: Vec<String>, prefix: &str| -> Vec<Pair> {
+        names.into_iter().filter(|n| n.starts_with(prefix)).map(|n| pair(n.clone(), n)).collect()
+    };
+
+    // コマンド名
+    let Some(name) = previous.first() else {
+        let mut names: Vec<String> = command.get_subcommands().map(|c| c.get_name().to_string()).collect();
+        for builtin in BUILTINS {
+            if !names.iter().any(|name| name == builtin) {
+                names.push(builtin.to_string());
+            }
+        }
+        return (current_start, by_prefix(names, current));
+    };
+    let Some(mut subcommand) = command.find_subcommand(name) else {
+        return (current_start, Vec::new());
+    };
+    let mut rest = &previous[1..];
+
+    // field / timer / report のサブコマンド
+    if subcommand.has_subcommands() {
+        match rest.first() {
+            None => {
+                let names = subcommand.get_subcommands().map(|c| c.get_name().to_string()).collect();
+                return (current_start, by_prefix(names, current));
+            }
+            Some(name) => match subcommand.find_subcommand(name) {
+                Some(nested) => {
+                    subcommand = nested;
+                    rest = &rest[1..];
+                }
+                None => return (current_start, Vec::new()),
+            },
+        }
+    }
+
+    // オプション名
+    if current.starts_with('-') {
+        let names = subcommand
+            .get_arguments()
+            .filter_map(|arg| arg.get_long())
+            .map(|long| format!("--{}", long))
+            .collect();
+        return (current_start, by_prefix(names, current));
+    }
+
+    // オプションの値（カンマ区切りの最後の要素を補完する）
+    let value_start = current_start + current.rfind(',').map_or(0, |i| i + 1);
+    let value = &line[value_start..];
+    let option = rest.last().and_then(|word| {
+        subcommand.get_arguments().find(|arg| {
+            arg.get_long().is_some_and(|long| *word == format!("--{}", long))
+                || arg.get_short().is_some_and(|short| *word == format!("-{}", short))
+        })
+    });
+    let id_pairs = || {
+        data.tasks
+            .iter()
+            .filter(|(id, _)| id.to_string().starts_with(value))
+            .map(|(id, title)| pair(id.to_string(), format!("{}  {}", id, title)))
+            .collect::<Vec<_>>()
+    };
+    match option.map(|arg| arg.get_id().as_str()) {
+        Some("tags") => return (value_start, by_prefix(data.tags.clone(), value)),
+        Some("project") => return (current_start, by_prefix(data.projects.clone(), current)),
+        Some("depends_on") => return (value_start, id_pairs()),
+        Some(_) => return (current_start, Vec::new()),
+        None => {}
+    }
+
+    // ID を取るコマンドの最初の引数（ID の一覧を取るコマンドはすべての引数）
+    let positional = subcommand.get_positionals().next().map(|arg| arg.get_id().as_str());
+    let has_positional = rest.iter().any(|word| !word.starts_with('-'));
+    let takes_id = positional == Some("ids") || (positional == Some("id") && !has_positional);
+    if takes_id && current.chars().all(|c| c.is_ascii_digit()) {
+        return (current_start, id_pairs());
+    }
+    (current_start, Vec::new())
+}
+
+/// 行の編集の補完を担当する
+struct ShellHelper {
+    command: Command,
+    /// 補完の候補（Tab を押すたびではなく、行を入力する前に読み込む）
+    data: CompletionData,
+}
+
+impl Completer for ShellHelper {
+    type Candidate = Pair;
+
+    fn complete(&self, line: &str, pos: usize, _ctx: &rustyline::Context<'_>) -> rustyline::Result<(usize, Vec<Pair>)> {
+        Ok(complete_line(&self.command, &line[..pos], &self.data))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
@@ -1,6 +1,16 @@
+use std::path::Path;
+
+use crate::commands::attach::{attachments_dir, describe};
+use crate::config::Config;
 use crate::db::TaskRepository;
 use crate::error::Result;
+use crate::models::attachment::AttachmentKind;
+use crate::models::urgency::UrgencyScorer;
+use crate::utils::date::format_datetime;
+use crate::utils::duration::format_duration;
 use crate::utils::format::format_task;
+use crate::utils::text::display_width;
+use crate::utils::theme::{glyph, paint, Glyph, Role};
 
 /// 特定のタスクの詳細を表示するコマンド
 pub fn show_task(id: i64) -> Result<()> {
@@ -9,5 +19,72 @@ pub fn show_task(id: i64) -> Result<()> {
     
     println!("{}", format_task(&task, true));
     
+    // 緊急度の表示（未完了のタスクのみ）
+    if !task.completed {
+        let config = Config::load()?;
+        let scorer = UrgencyScorer::new(config.urgency, repo.get_blocked_task_ids()?);
+        let urgency = scorer.score(&task);
+        let mut urgency_str = format!(
+            "    {}",
+            t!("show.urgency", total = format!("{:.1}", urgency.total), explanation = urgency.explain())
+        );
+        if scorer.is_blocked(&task) {
+            urgency_str = format!("{} {}", urgency_str, paint(&t!("show.blocked", glyph = glyph(Glyph::Blocked)), Role::Error));
+        }
+        println!("{}", urgency_str);
+    }
+    
+    // 作業時間の表示
+    let entries = repo.get_time_entries(id)?;
+    if !entries.is_empty() {
+        let tracked = repo.get_tracked_time(id)?;
+        let mut tracked_str = format!(
+            "    {}",
+            tn!("show.tracked", entries.len(), duration = format_duration(tracked))
+        );
+        if entries.iter().any(|e| e.is_running()) {
+            tracked_str = format!("{} {}", tracked_str, paint(&t!("show.timer_running", glyph = glyph(Glyph::Running)), Role::Done));
+        }
+        println!("{}", tracked_str);
+    }
+    
+    // ポモドーロの表示
+    let pomodoros = repo.get_pomodoros(id)?;
+    if !pomodoros.is_empty() {
+        let interruptions: i64 = pomodoros.iter().map(|p| p.interruptions).sum();
+        println!(
+            "    {}",
+            t!("show.pomodoros", icon = glyph(Glyph::Pomodoro), count = pomodoros.len(), interruptions = interruptions)
+        );
+    }
+    
+    // 添付の表示（番号は open・detach で指定する）
+    if !task.attachments.is_empty() {
+        let dir = attachments_dir()?;
+        println!("    {}", tn!("show.attachments", task.attachments.len()));
+        for (index, attachment) in task.attachments.iter().enumerate() {
+            let mut line = format!("      {:>2}. {}", index + 1, describe(attachment));
+            if attachment.kind != AttachmentKind::Link && !attachment.location(&dir).is_some_and(|location| Path::new(&location).exists()) {
+                line = format!("{} {}", line, paint(&t!("show.missing"), Role::Error));
+            }
+            println!("{}", line);
+        }
+    }
+    
+    // メモの表示（古い順）
+    if !task.notes.is_empty() {
+        println!("    {}", tn!("show.notes", task.notes.len()));
+        for note in &task.notes {
+            // 複数行のメモは 2 行目以降を本文の位置にそろえる
+            let date = format_datetime(note.created_at);
+            let indent = " ".repeat(display_width(&date));
+            let mut lines = note.text.lines();
+            println!("      {}  {}", paint(&date, Role::Muted), lines.next().unwrap_or_default());
+            for line in lines {
+                println!("      {}  {}", indent, line);
+            }
+        }
+    }
+    
     Ok(())
-} 
+}
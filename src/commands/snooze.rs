@@ -0,0 +1,26 @@
+use crate::db::TaskRepository;
+use crate::error::Result;
+use crate::utils::date::format_datetime;
+use crate::utils::duration::{add_duration, parse_duration};
+use crate::utils::theme::{paint, Role};
+use chrono::Local;
+
+/// タスクの待機日時を先に延ばすコマンド
+///
+/// 待機中のタスクは現在の待機日時から、それ以外は現在時刻から延ばす。
+pub fn snooze_task(id: i64, duration: String) -> Result<()> {
+    let duration = parse_duration(&duration)?;
+    let mut repo = TaskRepository::new()?;
+    let mut task = repo.get_task(id)?;
+
+    let base = task.wait_until.unwrap_or_else(Local::now).max(Local::now());
+    let wait_until = add_duration(base, duration)?;
+    task.wait_until = Some(wait_until);
+    repo.update_task(&task)?;
+
+    println!(
+        "{}",
+        paint(&t!("snooze.done", id = id, date = format_datetime(wait_until)), Role::Done)
+    );
+    Ok(())
+}
@@ -1,13 +1,75 @@
+use chrono::Local;
+use std::collections::HashMap;
+
+use crate::commands::context::retain_in_context;
 use crate::db::TaskRepository;
 use crate::error::Result;
+use crate::models::task::Task;
 use crate::utils::format::format_stats;
+use crate::utils::theme::{paint, Role};
 
-/// タスクの統計情報を表示するコマンド
+/// タスクの統計情報を表示するコマンド（コンテキストが有効な場合はその条件に合うタスクの統計）
 pub fn show_stats() -> Result<()> {
     let repo = TaskRepository::new()?;
-    let stats = repo.get_stats()?;
+    let mut stats = repo.get_stats()?;
+
+    let mut tasks = repo.get_all_tasks(true)?;
+    let context = retain_in_context(&repo, &mut tasks)?;
+    if let Some(context) = &context {
+        println!("{}", context.header());
+        stats.extend(task_counts(&tasks));
+        stats.extend(pomodoro_counts(&repo, &tasks)?);
+    }
     
     println!("{}", format_stats(&stats));
     
+    // タスクごとのポモドーロ数
+    let pomodoro_counts = match &context {
+        Some(_) => repo
+            .get_pomodoro_counts(usize::MAX)?
+            .into_iter()
+            .filter(|(task_id, _, _)| tasks.iter().any(|task| task.id == Some(*task_id)))
+            .take(5)
+            .collect(),
+        None => repo.get_pomodoro_counts(5)?,
+    };
+    for (task_id, title, count) in pomodoro_counts {
+        println!("  {} {:>3} {}", paint(&format!("{:>3}", task_id), Role::Id), count, title);
+    }
+    
     Ok(())
-} 
+}
+
+/// タスクの件数の統計（get_stats のタスクに関する項目と同じキー）
+fn task_counts(tasks: &[Task]) -> HashMap<String, i64> {
+    let count = |predicate: &dyn Fn(&Task) -> bool| tasks.iter().filter(|task| predicate(task)).count() as i64;
+    let mut stats = HashMap::new();
+    stats.insert("total".to_string(), tasks.len() as i64);
+    stats.insert("completed".to_string(), count(&|task| task.completed));
+    stats.insert("active".to_string(), count(&|task| !task.completed));
+    for priority in 1..=3 {
+        stats.insert(
+            format!("priority_{}", priority),
+            count(&|task| !task.completed && task.priority as i32 == priority),
+        );
+    }
+    stats.insert("overdue".to_string(), count(&|task| task.is_overdue()));
+    stats.insert("due_today".to_string(), count(&|task| !task.completed && task.is_due_today()));
+    stats
+}
+
+/// タスクのポモドーロ数の統計（get_stats のポモドーロに関する項目と同じキー）
+fn pomodoro_counts(repo: &TaskRepository, tasks: &[Task]) -> Result<HashMap<String, i64>> {
+    let today = Local::now().date_naive();
+    let mut total = 0;
+    let mut today_count = 0;
+    for id in tasks.iter().filter_map(|task| task.id) {
+        let pomodoros = repo.get_pomodoros(id)?;
+        total += pomodoros.len() as i64;
+        today_count += pomodoros.iter().filter(|p| p.ended_at.date_naive() == today).count() as i64;
+    }
+    Ok(HashMap::from([
+        ("pomodoros".to_string(), total),
+        ("pomodoros_today".to_string(), today_count),
+    ]))
+}
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use crate::db::TaskRepository;
+use crate::error::{Result, TaskError};
+use crate::models::field::parse_assignment;
+use crate::models::template::TaskTemplate;
+use crate::utils::date::parse_date;
+use crate::utils::format::format_task;
+use crate::utils::theme::{paint, Role};
+
+/// タスクとその依存先をテンプレートとして保存するコマンド（同じ名前があれば置き換え）
+pub fn save_template(name: String, id: i64) -> Result<()> {
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        return Err(TaskError::InvalidArgument(t!("task_template.invalid_name", name = name)));
+    }
+    let mut repo = TaskRepository::new()?;
+    let root = repo.get_task(id)?;
+    let tasks: HashMap<i64, _> = repo
+        .get_all_tasks(true)?
+        .into_iter()
+        .filter_map(|task| task.id.map(|id| (id, task)))
+        .collect();
+
+    let template = TaskTemplate::capture(name, &root, &tasks);
+    let message = if repo.save_template(&template)? { "task_template.replaced" } else { "task_template.saved" };
+    println!("{}", tn!(message, template.tasks.len(), name = template.name));
+    Ok(())
+}
+
+/// テンプレートからタスクを作成するコマンド（すべてのタスクを 1 つのトランザクションで作成）
+pub fn apply_template(name: String, due: Option<String>, vars: Vec<String>) -> Result<()> {
+    let mut repo = TaskRepository::new()?;
+    let template = repo.get_template(&name)?;
+
+    let due = due.map(|due| parse_date(&due)).transpose()?;
+    let vars = vars
+        .iter()
+        .map(|var| parse_assignment(var).map_err(TaskError::InvalidArgument))
+        .collect::<Result<HashMap<_, _>>>()?;
+    let tasks = template.instantiate(due, &vars).map_err(TaskError::InvalidArgument)?;
+
+    // 依存関係はテンプレート内の位置で記録されているため、全タスクの追加後に付け替える
+    let ids = repo.transaction(|repo| {
+        let ids = tasks.iter().map(|task| repo.add_task(task)).collect::<Result<Vec<_>>>()?;
+        for (template_task, id) in template.tasks.iter().zip(&ids) {
+            if !template_task.depends_on.is_empty() {
+                let mut task = repo.get_task(*id)?;
+                task.depends_on = template_task.depends_on.iter().map(|&index| ids[index]).collect();
+                repo.update_task(&task)?;
+            }
+        }
+        Ok(ids)
+    })?;
+
+    println!("{}", tn!("task_template.applied", ids.len(), name = template.name));
+    for id in ids {
+        println!("{}", format_task(&repo.get_task(id)?, false));
+    }
+    Ok(())
+}
+
+/// テンプレートの一覧を表示するコマンド
+pub fn list_templates() -> Result<()> {
+    let repo = TaskRepository::new()?;
+    let templates = repo.get_templates()?;
+
+    if templates.is_empty() {
+        println!("{}", t!("task_template.none"));
+        return Ok(());
+    }
+
+    println!("{}", tn!("task_template.count", templates.len()));
+    for template in templates {
+        let mut line = format!(
+            "  {} {}",
+            paint(&template.name, Role::Tag),
+            tn!("task_template.task_count", template.tasks.len(), title = template.tasks[0].title)
+        );
+        let variables = template.variables();
+        if !variables.is_empty() {
+            line = format!("{} {}", line, t!("task_template.variables", names = variables.join(", ")));
+        }
+        println!("{}", line);
+    }
+
+    Ok(())
+}
+
+/// テンプレートを削除するコマンド
+pub fn remove_template(name: String) -> Result<()> {
+    let mut repo = TaskRepository::new()?;
+    repo.remove_template(&name)?;
+
+    println!("{}", t!("task_template.removed", name = name));
+    Ok(())
+}
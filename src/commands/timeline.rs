@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, Local, NaiveDate};
+use colored::*;
+
+use crate::config::Config;
+use crate::db::TaskRepository;
+use crate::error::{Result, TaskError};
+use crate::models::task::Task;
+use crate::utils::date::{parse_date, start_of_week};
+use crate::utils::format::color_by_priority;
+use crate::utils::text::{pad_to_width, terminal_width, truncate_to_width};
+use crate::utils::theme::{glyph, paint, Glyph, Role};
+
+/// タスク名の列の幅
+const LABEL_WIDTH: usize = 24;
+
+/// 依存関係の表示のために右側に残しておく幅
+const SUFFIX_WIDTH: usize = 14;
+
+/// 既定の表示期間（日数）
+const DEFAULT_DAYS: i64 = 28;
+
+/// タスクの期間（開始日〜期限日）を取得
+///
+/// 片方しかない場合はその 1 日、どちらもない場合は None。
+pub fn timeline_span(task: &Task) -> Option<(NaiveDate, NaiveDate)> {
+    let start = task.start_date.map(|d| d.date_naive());
+    let due = task.due_date.map(|d| d.date_naive());
+    match (start, due) {
+        (Some(start), Some(due)) => Some((start, due)),
+        (Some(date), None) | (None, Some(date)) => Some((date, date)),
+        (None, None) => None,
+    }
+}
+
+/// 1 タスク分のバーを作成
+///
+/// 1 列は days_per_column 日分で、開始日と期限日がある場合は █、期限日のみは ◆、
+/// 開始日のみは ▶ で表し、タスクのない今日の列には │ を表示する。
+pub fn render_bar(
+    task: &Task,
+    from: NaiveDate,
+    columns: usize,
+    days_per_column: i64,
+    today: NaiveDate,
+) -> String {
+    let span = timeline_span(task);
+    let marker = match (task.start_date, task.due_date) {
+        (Some(_), Some(_)) => glyph(Glyph::Span),
+        (None, Some(_)) => glyph(Glyph::DueMark),
+        _ => glyph(Glyph::StartMark),
+    };
+
+    (0..columns)
+        .map(|column| {
+            let column_start = from + Duration::days(column as i64 * days_per_column);
+            let column_end = column_start + Duration::days(days_per_column - 1);
+            match span {
+                Some((start, end)) if start <= column_end && end >= column_start => marker,
+                _ if column_start <= today && today <= column_end => glyph(Glyph::TodayLine),
+                _ => " ",
+            }
+        })
+        .collect()
+}
+
+/// 依存先のうち、このタスクの開始日より後に終わる予定のものを取得
+pub fn dependency_conflicts(task: &Task, tasks: &HashMap<i64, Task>) -> Vec<i64> {
+    let start = match timeline_span(task) {
+        Some((start, _)) => start,
+        None => return Vec::new(),
+    };
+
+    task.depends_on
+        .iter()
+        .filter(|id| {
+            tasks.get(id).is_some_and(|dependency| {
+                !dependency.completed
+                    && timeline_span(dependency).is_some_and(|(_, end)| end > start)
+            })
+        })
+        .copied()
+        .collect()
+}
+
+/// 開始日・期限日のあるタスクをガントチャートで表示するコマンド
+pub fn show_timeline(from: Option<String>, to: Option<String>, all: bool) -> Result<()> {
+    let today = Local::now().date_naive();
+    let from = match from {
+        Some(from) => parse_date(&from)?.date_naive(),
+        None => start_of_week(today, Config::load()?.week_start),
+    };
+    let to = match to {
+        Some(to) => parse_date(&to)?.date_naive(),
+        None => from + Duration::days(DEFAULT_DAYS - 1),
+    };
+    if to < from {
+        return Err(TaskError::InvalidArgument(t!("timeline.invalid_range")));
+    }
+
+    let repo = TaskRepository::new()?;
+    let all_tasks: HashMap<i64, Task> = repo
+        .get_all_tasks(true)?
+        .into_iter()
+        .filter_map(|task| task.id.map(|id| (id, task)))
+        .collect();
+
+    // 表示期間と重なるタスクを開始日順に並べる
+    let mut tasks: Vec<&Task> = all_tasks
+        .values()
+        .filter(|task| all || !task.completed)
+        .filter(|task| timeline_span(task).is_some_and(|(start, end)| start <= to && end >= from))
+        .collect();
+    tasks.sort_by_key(|task| (timeline_span(task), task.id));
+
+    if tasks.is_empty() {
+        println!("{}", t!("timeline.none"));
+        return Ok(());
+    }
+
+    // 端末の幅に収まるように 1 列あたりの日数を決める
+    let days = (to - from).num_days() + 1;
+    let available = terminal_width()
+        .saturating_sub(LABEL_WIDTH + 1 + SUFFIX_WIDTH)
+        .max(7) as i64;
+    let days_per_column = (days + available - 1) / available;
+    let columns = ((days + days_per_column - 1) / days_per_column) as usize;
+
+    println!(
+        "{}",
+        t!(
+            "timeline.header",
+            icon = glyph(Glyph::Chart),
+            from = from.format("%Y-%m-%d"),
+            to = to.format("%Y-%m-%d"),
+            days = days_per_column,
+        )
+        .bold()
+    );
+    print_header(from, columns, days_per_column, today);
+
+    for task in tasks {
+        let id = task.id.unwrap_or(0);
+        let label = pad_to_width(
+            &truncate_to_width(&format!("{:>3} {}", id, task.title), LABEL_WIDTH),
+            LABEL_WIDTH,
+        );
+        let bar = render_bar(task, from, columns, days_per_column, today);
+        let bar = if task.completed {
+            paint(&bar, Role::Muted)
+        } else if task.is_overdue() {
+            paint(&bar, Role::Overdue)
+        } else {
+            color_by_priority(&bar, task.priority)
+        };
+
+        // 依存先の表示（開始日より後に終わる予定の依存先は警告）
+        let conflicts = dependency_conflicts(task, &all_tasks);
+        let dependencies = task
+            .depends_on
+            .iter()
+            .map(|dependency| {
+                let text = format!("#{}", dependency);
+                if conflicts.contains(dependency) {
+                    paint(&format!("{}{}", glyph(Glyph::Warning), text), Role::Overdue).to_string()
+                } else {
+                    text
+                }
+            })
+            .collect::<Vec<_>>();
+        let suffix = if dependencies.is_empty() {
+            String::new()
+        } else {
+            format!(" {} {}", glyph(Glyph::DependsOn), dependencies.join(","))
+        };
+
+        println!("{} {}{}", label, bar, suffix);
+    }
+
+    println!(
+        "\n{}",
+        paint(
+            &t!(
+                "timeline.legend",
+                span = glyph(Glyph::Span),
+                due = glyph(Glyph::DueMark),
+                start = glyph(Glyph::StartMark),
+                today = glyph(Glyph::TodayLine),
+                warning = glyph(Glyph::Warning),
+                depends_on = glyph(Glyph::DependsOn),
+            ),
+            Role::Muted
+        )
+    );
+
+    Ok(())
+}
+
+/// 日付の目盛りと今日の位置を表示
+fn print_header(from: NaiveDate, columns: usize, days_per_column: i64, today: NaiveDate) {
+    let mut ticks = vec![' '; columns];
+    let mut today_line = vec![" "; columns];
+
+    // 約 1 週間ごとに日付を表示（重ならない位置のみ）
+    let tick_every = (7 / days_per_column).max(1) as usize;
+    let mut next_free = 0;
+    for column in (0..columns).step_by(tick_every) {
+        let date = from + Duration::days(column as i64 * days_per_column);
+        let label: Vec<char> = date.format("%m/%d").to_string().chars().collect();
+        if column >= next_free && column + label.len() <= columns {
+            ticks[column..column + label.len()].copy_from_slice(&label);
+            next_free = column + label.len() + 1;
+        }
+    }
+
+    let today_offset = (today - from).num_days();
+    if today_offset >= 0 {
+        let column = (today_offset / days_per_column) as usize;
+        if column < columns {
+            today_line[column] = glyph(Glyph::TodayMarker);
+        }
+    }
+
+    let padding = " ".repeat(LABEL_WIDTH + 1);
+    println!("{}{}", padding, ticks.iter().collect::<String>().trim_end());
+    println!(
+        "{}{}",
+        padding,
+        paint(today_line.concat().trim_end(), Role::DueToday)
+    );
+}
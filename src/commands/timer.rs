@@ -0,0 +1,91 @@
+use chrono::Local;
+
+use crate::db::TaskRepository;
+use crate::error::Result;
+use crate::models::time_entry::TimeEntry;
+use crate::utils::date::{format_datetime, parse_date};
+use crate::utils::duration::{add_duration, format_duration, parse_duration, sub_duration};
+use crate::utils::theme::{paint, Role};
+
+/// タイマーを開始するコマンド
+pub fn start_timer(id: i64) -> Result<()> {
+    let mut repo = TaskRepository::new()?;
+    let entry = repo.start_timer(id)?;
+    let task = repo.get_task(id)?;
+
+    println!(
+        "{}",
+        t!("timer.started", id = id, title = task.title, date = format_datetime(entry.started_at))
+    );
+    Ok(())
+}
+
+/// 実行中のタイマーを停止するコマンド
+pub fn stop_timer() -> Result<()> {
+    let mut repo = TaskRepository::new()?;
+    let entry = repo.stop_timer()?;
+    let task = repo.get_task(entry.task_id)?;
+
+    println!(
+        "{}",
+        t!(
+            "timer.stopped",
+            id = entry.task_id,
+            title = task.title,
+            duration = paint(&format_duration(entry.duration()), Role::Done),
+        )
+    );
+    println!(
+        "{}",
+        t!("timer.total", duration = format_duration(repo.get_tracked_time(entry.task_id)?))
+    );
+    Ok(())
+}
+
+/// 実行中のタイマーの状態を表示するコマンド
+pub fn timer_status() -> Result<()> {
+    let repo = TaskRepository::new()?;
+
+    match repo.get_running_timer()? {
+        Some(entry) => {
+            let task = repo.get_task(entry.task_id)?;
+            println!(
+                "{}",
+                t!(
+                    "timer.running",
+                    id = entry.task_id,
+                    title = task.title,
+                    duration = paint(&format_duration(entry.duration()), Role::Done),
+                    date = format_datetime(entry.started_at),
+                )
+            );
+        }
+        None => println!("{}", t!("timer.none")),
+    }
+
+    Ok(())
+}
+
+/// 作業時間を手動で記録するコマンド
+pub fn log_time(id: i64, duration: String, date: Option<String>) -> Result<()> {
+    let duration = parse_duration(&duration)?;
+
+    // 日時を指定した場合はその時刻から開始、省略した場合は現在時刻で終了したものとする
+    let entry = match date {
+        Some(date_str) => {
+            let started_at = parse_date(&date_str)?;
+            TimeEntry::new(id, started_at, Some(add_duration(started_at, duration)?))
+        }
+        None => {
+            let ended_at = Local::now();
+            TimeEntry::new(id, sub_duration(ended_at, duration)?, Some(ended_at))
+        }
+    };
+
+    let mut repo = TaskRepository::new()?;
+    repo.add_time_entry(&entry)?;
+
+    println!("{}", t!("timer.logged", id = id, duration = format_duration(duration)));
+    println!("{}", t!("timer.total", duration = format_duration(repo.get_tracked_time(id)?)));
+    Ok(())
+}
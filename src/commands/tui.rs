@@ -0,0 +1,29 @@
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+
+use crate::db::TaskRepository;
+use crate::error::Result;
+use crate::tui::{self, App};
+
+/// 全画面の対話的なインターフェースを起動するコマンド
+pub fn run_tui() -> Result<()> {
+    let mut app = App::new(TaskRepository::new()?)?;
+
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &mut app);
+    // エラーで終了する場合も端末の状態を元に戻す
+    ratatui::restore();
+    result
+}
+
+fn run(terminal: &mut ratatui::DefaultTerminal, app: &mut App) -> Result<()> {
+    while !app.should_quit() {
+        terminal.draw(|frame| tui::draw(frame, app))?;
+        if let Event::Key(key) = event::read()? {
+            // Windows では離したときのイベントも届くため、押したときのみ処理する
+            if key.kind == KeyEventKind::Press {
+                app.handle_key(key);
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,57 +1,171 @@
+use crate::commands::bulk::{confirm_bulk, select_tasks, BulkAction};
+use crate::commands::edit::{diff_tasks, DiffLine};
 use crate::db::TaskRepository;
 use crate::error::{Result, TaskError};
-use crate::models::task::Priority;
+use crate::models::field::parse_assignment;
+use crate::models::tag::TagChanges;
+use crate::models::task::{parse_project, Priority, Task};
 use crate::utils::date::parse_date;
+use crate::utils::duration::parse_estimate;
+use crate::utils::ids::parse_id_list;
 use crate::utils::format::format_task;
+use crate::utils::theme::{paint, Role};
 
-/// タスクを更新するコマンド
+/// タスクを更新するコマンド（ID・範囲の一覧または --where の条件で複数指定可）
+#[allow(clippy::too_many_arguments)]
 pub fn update_task(
-    id: i64,
+    ids: Vec<String>,
+    filter: Option<String>,
     title: Option<String>,
     description: Option<String>,
     due: Option<String>,
     remove_due: bool,
     priority: Option<i32>,
     tags: Option<String>,
+    add_tags: Vec<String>,
+    remove_tags: Vec<String>,
+    clear_tags: bool,
+    project: Option<String>,
+    remove_project: bool,
+    set: Vec<String>,
+    estimate: Option<String>,
+    remove_estimate: bool,
+    depends_on: Option<String>,
+    start: Option<String>,
+    remove_start: bool,
+    wait: Option<String>,
+    remove_wait: bool,
+    dry_run: bool,
+    yes: bool,
 ) -> Result<()> {
+    // 値はタスクごとではなく最初に一度だけ解析する
+    let due = if remove_due { Some(None) } else { due.map(|s| parse_date(&s)).transpose()?.map(Some) };
+    let start = if remove_start { Some(None) } else { start.map(|s| parse_date(&s)).transpose()?.map(Some) };
+    let wait = if remove_wait { Some(None) } else { wait.map(|s| parse_date(&s)).transpose()?.map(Some) };
+    let estimate = if remove_estimate {
+        Some(None)
+    } else {
+        estimate.map(|s| parse_estimate(&s)).transpose()?.map(Some)
+    };
+    let project = if remove_project { Some(None) } else { project.map(|p| parse_project(&p)) };
+    let depends_on = depends_on.map(|ids| parse_id_list(&ids)).transpose()?;
+    let priority = priority.map(|p| Priority::from_i32(p).map_err(|_| TaskError::InvalidPriority(p))).transpose()?;
+    let tags: Option<Vec<String>> = tags.map(|s| s.split(',').map(|s| s.trim().to_string()).collect());
+    let split_tags = |values: Vec<String>| -> Vec<String> {
+        values
+            .iter()
+            .flat_map(|v| v.split(','))
+            .map(|s| s.trim().trim_start_matches('#').to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    };
+    let tag_changes = TagChanges { clear: clear_tags, add: split_tags(add_tags), remove: split_tags(remove_tags) };
+    let assignments = set
+        .iter()
+        .map(|assignment| parse_assignment(assignment).map_err(TaskError::InvalidArgument))
+        .collect::<Result<Vec<_>>>()?;
+
     let mut repo = TaskRepository::new()?;
-    let mut task = repo.get_task(id)?;
-    
-    // タイトルの更新
-    if let Some(new_title) = title {
-        task.title = new_title;
-    }
-    
-    // 説明の更新
-    if let Some(new_desc) = description {
-        task.description = Some(new_desc);
+    let apply = |repo: &TaskRepository, before: &Task| -> Result<Task> {
+        let mut task = before.clone();
+        if let Some(title) = &title {
+            task.title = title.clone();
+        }
+        if let Some(description) = &description {
+            task.description = Some(description.clone());
+        }
+        if let Some(due) = due {
+            task.due_date = due;
+        }
+        if let Some(start) = start {
+            task.start_date = start;
+        }
+        if task.starts_after_due() {
+            return Err(TaskError::InvalidArgument(t!("task.start_after_due")));
+        }
+        if let Some(wait) = wait {
+            task.wait_until = wait;
+        }
+        if let Some(estimate) = estimate {
+            task.estimate_minutes = estimate;
+        }
+        if let Some(project) = &project {
+            task.project = project.clone();
+        }
+        if let Some(depends_on) = &depends_on {
+            task.depends_on = depends_on.clone();
+        }
+        if let Some(priority) = priority {
+            task.priority = priority;
+        }
+
+        // タグは置き換えの後に追加・削除を適用
+        if let Some(tags) = &tags {
+            task.tags = tags.clone();
+        }
+        tag_changes.apply(&mut task.tags);
+
+        // カスタムフィールドの更新（空の値は削除）
+        if !assignments.is_empty() {
+            let mut custom_fields = task.custom_fields.clone();
+            for (key, value) in &assignments {
+                if value.is_empty() {
+                    custom_fields.remove(key);
+                } else {
+                    custom_fields.insert(key.clone(), value.clone());
+                }
+            }
+            task.custom_fields = repo.validate_custom_fields(&custom_fields)?;
+        }
+        Ok(task)
+    };
+
+    let selected = select_tasks(&repo, &ids, filter.as_deref())?;
+    let definitions = repo.get_field_definitions()?;
+    let mut before = Vec::new();
+    let mut after = Vec::new();
+    for task in selected.iter() {
+        let updated = apply(&repo, task)?;
+        if !diff_tasks(task, &updated, &definitions).is_empty() {
+            before.push(task.clone());
+            after.push(updated);
+        }
     }
-    
-    // 期限の更新
-    if remove_due {
-        task.due_date = None;
-    } else if let Some(due_str) = due {
-        task.due_date = Some(parse_date(&due_str)?);
+    if !selected.is_empty() && after.is_empty() {
+        println!("{}", t!("bulk.no_changes"));
+        return Ok(());
     }
-    
-    // 優先度の更新
-    if let Some(p) = priority {
-        task.priority = Priority::from_i32(p).map_err(|_| TaskError::InvalidPriority(p))?;
+
+    // --dry-run では変更される行を表示
+    let details = |task: &Task| -> Vec<String> {
+        let index = before.iter().position(|t| t.id == task.id).unwrap_or_default();
+        diff_tasks(&before[index], &after[index], &definitions)
+            .into_iter()
+            .map(|line| match line {
+                DiffLine::Removed(line) => paint(&format!("- {}", line), Role::Error).to_string(),
+                DiffLine::Added(line) => paint(&format!("+ {}", line), Role::Done).to_string(),
+                DiffLine::Same(line) => format!("  {}", line),
+            })
+            .collect()
+    };
+    if !confirm_bulk(BulkAction::Update, &before, filter.is_some(), dry_run, yes, details)? {
+        return Ok(());
     }
-    
-    // タグの更新
-    if let Some(tags_str) = tags {
-        task.tags = tags_str
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .collect();
+
+    repo.transaction(|repo| after.iter().try_for_each(|task| repo.update_task(task)))?;
+
+    match after.as_slice() {
+        [task] => {
+            println!("{}", t!("update.done", id = task.id.unwrap_or_default()));
+            println!("{}", format_task(task, true));
+        }
+        tasks => {
+            for task in tasks {
+                println!("{}", format_task(task, false));
+            }
+            println!("{}", BulkAction::Update.done_message(tasks.len()));
+        }
     }
-    
-    // タスクの保存
-    repo.update_task(&task)?;
-    
-    println!("タスクを更新しました（ID: {}）", id);
-    println!("{}", format_task(&task, true));
-    
+
     Ok(())
-} 
+}
@@ -0,0 +1,90 @@
+use crate::db::TaskRepository;
+use crate::error::{Result, TaskError};
+use crate::models::view::View;
+use crate::utils::filter::TaskFilter;
+use crate::utils::sort::sort_tasks;
+use crate::utils::table::{TableColumn, DEFAULT_COLUMNS};
+use crate::utils::theme::{paint, Role};
+
+/// 一覧の表示条件を保存するコマンド（--columns だけを指定した場合は表形式）
+pub fn save_view(
+    name: String,
+    filter: Option<String>,
+    sort: Option<String>,
+    format: Option<String>,
+    columns: Option<String>,
+    all: bool,
+) -> Result<()> {
+    View::validate_name(&name).map_err(TaskError::InvalidArgument)?;
+    let mut repo = TaskRepository::new()?;
+    let definitions = repo.get_field_definitions()?;
+
+    // 呼び出したときに失敗しないよう、保存する前に検証する
+    if let Some(filter) = &filter {
+        TaskFilter::parse(filter, &definitions)?;
+    }
+    if let Some(key) = &sort {
+        if key.trim_start_matches('-') != "urgency" {
+            sort_tasks(&mut [], key, &definitions, None)?;
+        }
+    }
+    let format = format.or_else(|| columns.as_ref().map(|_| "table".to_string()));
+    match format.as_deref() {
+        None | Some("line") => {}
+        Some("table") => {
+            TableColumn::parse_list(columns.as_deref().unwrap_or(DEFAULT_COLUMNS), &definitions)?;
+        }
+        Some(other) => return Err(TaskError::InvalidArgument(t!("list.invalid_format", format = other))),
+    }
+
+    let view = View { name, filter, sort, format, columns, all };
+    if repo.save_view(&view)? {
+        println!("{}", t!("view.replaced", name = view.name));
+    } else {
+        println!("{}", t!("view.saved", name = view.name));
+    }
+    Ok(())
+}
+
+/// 保存した表示条件の一覧を表示するコマンド
+pub fn list_views() -> Result<()> {
+    let repo = TaskRepository::new()?;
+    let views = repo.get_views()?;
+
+    if views.is_empty() {
+        println!("{}", t!("view.none"));
+        return Ok(());
+    }
+
+    println!("{}", tn!("view.count", views.len()));
+    for view in views {
+        let mut options = Vec::new();
+        if let Some(filter) = &view.filter {
+            options.push(format!("\"{}\"", filter));
+        }
+        if view.all {
+            options.push("--all".to_string());
+        }
+        if let Some(sort) = &view.sort {
+            options.push(format!("--sort {}", sort));
+        }
+        if let Some(format) = &view.format {
+            options.push(format!("--format {}", format));
+        }
+        if let Some(columns) = &view.columns {
+            options.push(format!("--columns {}", columns));
+        }
+        println!("  {} {}", paint(&format!("@{}", view.name), Role::Tag), options.join(" "));
+    }
+
+    Ok(())
+}
+
+/// 表示条件を削除するコマンド
+pub fn remove_view(name: String) -> Result<()> {
+    let mut repo = TaskRepository::new()?;
+    repo.remove_view(name.trim_start_matches('@'))?;
+
+    println!("{}", t!("view.removed", name = name.trim_start_matches('@')));
+    Ok(())
+}
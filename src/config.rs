@@ -0,0 +1,118 @@
+use chrono::Weekday;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+
+use crate::db::schema::data_dir;
+use crate::error::{Result, TaskError};
+use crate::utils::theme::{ColorChoice, Palette};
+
+/// 設定ファイル名（データディレクトリに置く）
+const CONFIG_FILE: &str = "config.toml";
+
+/// 設定ファイルの内容を表す構造体（省略した項目は既定値）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// 表示言語（"ja", "en"）
+    pub lang: Option<String>,
+    /// 色を付けるか（"auto", "always", "never"）
+    pub color: ColorChoice,
+    /// 色のパレット（"default", "high-contrast", "colorblind"）
+    pub theme: Palette,
+    /// 記号を ASCII 文字のみで表示する
+    pub ascii: bool,
+    /// 週の始まりの曜日（例: "monday", "sunday"）
+    pub week_start: Weekday,
+    pub urgency: UrgencyConfig,
+    pub board: BoardConfig,
+    /// 一覧表示のテンプレート（名前 → テンプレート）
+    pub templates: BTreeMap<String, String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            lang: None,
+            color: ColorChoice::Auto,
+            theme: Palette::Default,
+            ascii: false,
+            week_start: Weekday::Mon,
+            urgency: UrgencyConfig::default(),
+            board: BoardConfig::default(),
+            templates: BTreeMap::new(),
+        }
+    }
+}
+
+/// 緊急度の計算に使う重み
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct UrgencyConfig {
+    /// 優先度「高」のときの値（中・低は比例して小さくなる）
+    pub priority: f64,
+    /// 期限が迫っているときの最大値
+    pub due: f64,
+    /// 期限を過ぎた日数 1 日あたりの値（14 日分まで）
+    pub overdue: f64,
+    /// 作成から 1 年経過したときの値
+    pub age: f64,
+    /// 未完了の依存タスクがあるときの値
+    pub blocked: f64,
+    /// タグごとの値
+    pub tags: BTreeMap<String, f64>,
+}
+
+impl Default for UrgencyConfig {
+    fn default() -> Self {
+        Self {
+            priority: 6.0,
+            due: 12.0,
+            overdue: 0.5,
+            age: 2.0,
+            blocked: -5.0,
+            tags: BTreeMap::new(),
+        }
+    }
+}
+
+/// カンバンボードの設定
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct BoardConfig {
+    /// ステータスの列（左から順に表示、完了は "done" 列）
+    pub statuses: Vec<String>,
+    /// --by tag で列として使うタグ（空の場合は使用中のすべてのタグ）
+    pub tags: Vec<String>,
+    /// 列ごとの WIP 制限（列名 → 最大タスク数）
+    pub wip: BTreeMap<String, usize>,
+}
+
+impl Default for BoardConfig {
+    fn default() -> Self {
+        Self {
+            statuses: vec!["todo".to_string(), "doing".to_string()],
+            tags: Vec::new(),
+            wip: BTreeMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// 設定ファイルを読み込む（存在しない場合は既定値）
+    pub fn load() -> Result<Self> {
+        let path = data_dir()?.join(CONFIG_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        Self::parse(&content)
+            .map_err(|e| TaskError::ConfigError(format!("{}: {}", path.display(), e)))
+    }
+
+    /// TOML 文字列から設定を作成
+    pub fn parse(content: &str) -> std::result::Result<Self, String> {
+        toml::from_str(content).map_err(|e| e.to_string())
+    }
+}
@@ -1,39 +1,97 @@
-use chrono::Local;
-use rusqlite::{params, Connection};
-use std::collections::HashMap;
+use chrono::{DateTime, Duration, Local};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use crate::error::{Result, TaskError};
+use crate::models::field::{FieldDefinition, FieldType};
 use crate::models::task::{Priority, Task};
-use crate::models::tag::Tag;
+use crate::models::pomodoro::Pomodoro;
+use crate::models::tag::{Tag, TagChanges};
+use crate::models::attachment::{Attachment, AttachmentKind};
+use crate::models::checklist::ChecklistItem;
+use crate::models::note::{Note, NoteMatch};
+use crate::models::template::TaskTemplate;
+use crate::models::time_entry::TimeEntry;
+use crate::models::view::View;
 
 use super::schema;
 
+/// タスク取得クエリの列（順序は query_tasks での読み取り順に対応）
+const TASK_COLUMNS: &str =
+    "t.id, t.title, t.description, t.created_at, t.due_date, t.completed, t.completed_at, t.priority,
+     GROUP_CONCAT(tags.name, ',') as tags, t.estimate, t.status, t.start_date, t.wait_until, t.project";
+
+/// 削除したタスクを同じIDで元に戻すための記録
+#[derive(Debug, Clone)]
+pub struct DeletedTask {
+    pub task: Task,
+    /// このタスクに依存していたタスクのID
+    pub dependents: Vec<i64>,
+    pub time_entries: Vec<TimeEntry>,
+    pub pomodoros: Vec<Pomodoro>,
+}
+
 /// タスクリポジトリ - データベース操作を行うための構造体
+///
+/// 接続の再利用が有効な場合（shell の実行中）は、破棄するときに接続を返して次のインスタンスで使う。
 pub struct TaskRepository {
-    conn: Connection,
+    conn: Option<Connection>,
 }
 
 impl TaskRepository {
     /// 新しいリポジトリインスタンスを作成
     pub fn new() -> Result<Self> {
-        let conn = schema::get_connection()?;
-        Ok(Self { conn })
+        let conn = schema::acquire_connection()?;
+        Ok(Self { conn: Some(conn) })
+    }
+
+    fn conn(&self) -> &Connection {
+        self.conn.as_ref().expect("破棄されるまで接続を保持している")
+    }
+
+    fn conn_mut(&mut self) -> &mut Connection {
+        self.conn.as_mut().expect("破棄されるまで接続を保持している")
+    }
+
+    /// 複数の操作を1つのトランザクションで実行（失敗した場合はすべて取り消す）
+    ///
+    /// 各メソッドの内部の処理はセーブポイントを使うため、この中で呼び出しても入れ子にできる。
+    pub fn transaction<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        self.conn().execute_batch("SAVEPOINT bulk")?;
+        match f(self) {
+            Ok(value) => {
+                self.conn().execute_batch("RELEASE bulk")?;
+                Ok(value)
+            }
+            Err(e) => {
+                self.conn().execute_batch("ROLLBACK TO bulk; RELEASE bulk")?;
+                Err(e)
+            }
+        }
     }
 
     /// タスクを追加
     pub fn add_task(&mut self, task: &Task) -> Result<i64> {
+        self.validate_dependencies(None, &task.depends_on)?;
+        self.insert_task(task, None)
+    }
+
+    /// タスクを挿入（id を省略した場合は新しいIDを割り当てる）
+    fn insert_task(&mut self, task: &Task, id: Option<i64>) -> Result<i64> {
         // トランザクション開始
-        let tx = self.conn.transaction()?;
+        let tx = self.conn_mut().savepoint()?;
 
         // Unix タイムスタンプを取得（秒単位）
         let created_at = task.created_at.timestamp();
         let due_date = task.due_date.map(|date| date.timestamp());
         let completed_at = task.completed_at.map(|date| date.timestamp());
+        let start_date = task.start_date.map(|date| date.timestamp());
+        let wait_until = task.wait_until.map(|date| date.timestamp());
 
         // タスクをデータベースに挿入
         tx.execute(
-            "INSERT INTO tasks (title, description, created_at, due_date, completed, completed_at, priority)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            "INSERT INTO tasks (title, description, created_at, due_date, completed, completed_at, priority, estimate, status, start_date, wait_until, project, id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
             params![
                 task.title,
                 task.description,
@@ -41,7 +99,13 @@ impl TaskRepository {
                 due_date,
                 task.completed,
                 completed_at,
-                task.priority as i32
+                task.priority as i32,
+                task.estimate_minutes,
+                task.status,
+                start_date,
+                wait_until,
+                task.project,
+                id
             ],
         )?;
 
@@ -57,6 +121,17 @@ impl TaskRepository {
             )?;
         }
 
+        // 依存関係・カスタムフィールド・チェックリストを処理
+        save_dependencies(&tx, task_id, &task.depends_on)?;
+        save_custom_fields(&tx, task_id, &task.custom_fields)?;
+        save_checklist(&tx, task_id, &task.checklist)?;
+        for note in &task.notes {
+            save_note(&tx, task_id, note)?;
+        }
+        for attachment in &task.attachments {
+            save_attachment(&tx, task_id, attachment)?;
+        }
+
         // トランザクションコミット
         tx.commit()?;
 
@@ -66,86 +141,45 @@ impl TaskRepository {
     /// 全タスクを取得
     pub fn get_all_tasks(&self, include_completed: bool) -> Result<Vec<Task>> {
         let sql = if include_completed {
-            "SELECT t.id, t.title, t.description, t.created_at, t.due_date, t.completed, t.completed_at, t.priority, 
-                    GROUP_CONCAT(tags.name, ',') as tags
+            format!(
+                "SELECT {}
              FROM tasks t
              LEFT JOIN task_tags ON t.id = task_tags.task_id
              LEFT JOIN tags ON task_tags.tag_id = tags.id
              GROUP BY t.id
-             ORDER BY t.created_at DESC"
+             ORDER BY t.created_at DESC",
+                TASK_COLUMNS
+            )
         } else {
-            "SELECT t.id, t.title, t.description, t.created_at, t.due_date, t.completed, t.completed_at, t.priority, 
-                    GROUP_CONCAT(tags.name, ',') as tags
+            format!(
+                "SELECT {}
              FROM tasks t
              LEFT JOIN task_tags ON t.id = task_tags.task_id
              LEFT JOIN tags ON task_tags.tag_id = tags.id
              WHERE t.completed = 0
              GROUP BY t.id
-             ORDER BY t.created_at DESC"
+             ORDER BY t.created_at DESC",
+                TASK_COLUMNS
+            )
         };
 
-        let mut stmt = self.conn.prepare(sql)?;
-        let rows = stmt.query_map([], |row| {
-            Ok(Task::from_row(
-                row.get(0)?,
-                row.get(1)?,
-                row.get(2)?,
-                row.get(3)?,
-                row.get(4)?,
-                row.get(5)?,
-                row.get(6)?,
-                row.get(7)?,
-                row.get(8)?,
-            ).map_err(|_e| rusqlite::Error::ExecuteReturnedResults))
-        })?;
-
-        let mut tasks = Vec::new();
-        for task_result in rows {
-            match task_result? {
-                Ok(task) => tasks.push(task),
-                Err(e) => return Err(TaskError::Unknown(e.to_string())),
-            }
-        }
-
-        Ok(tasks)
+        self.query_tasks(&sql, [])
     }
 
     /// 優先度でフィルタリングしたタスクを取得
     pub fn get_tasks_by_priority(&self, priority: Priority) -> Result<Vec<Task>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT t.id, t.title, t.description, t.created_at, t.due_date, t.completed, t.completed_at, t.priority, 
-                    GROUP_CONCAT(tags.name, ',') as tags
+        let sql = format!(
+            "SELECT {}
              FROM tasks t
              LEFT JOIN task_tags ON t.id = task_tags.task_id
              LEFT JOIN tags ON task_tags.tag_id = tags.id
              WHERE t.priority = ?1 AND t.completed = 0
              GROUP BY t.id
-             ORDER BY t.created_at DESC"
-        )?;
-
-        let rows = stmt.query_map(params![priority as i32], |row| {
-            Ok(Task::from_row(
-                row.get(0)?,
-                row.get(1)?,
-                row.get(2)?,
-                row.get(3)?,
-                row.get(4)?,
-                row.get(5)?,
-                row.get(6)?,
-                row.get(7)?,
-                row.get(8)?,
-            ).map_err(|_e| rusqlite::Error::ExecuteReturnedResults))
-        })?;
+             ORDER BY t.created_at DESC",
+            TASK_COLUMNS
+        );
 
-        let mut tasks = Vec::new();
-        for task_result in rows {
-            match task_result? {
-                Ok(task) => tasks.push(task),
-                Err(e) => return Err(TaskError::Unknown(e.to_string())),
-            }
-        }
-
-        Ok(tasks)
+        self.query_tasks(&sql, params![priority as i32])
     }
 
     /// 今日が期限のタスクを取得
@@ -157,122 +191,66 @@ impl TaskRepository {
         let start_ts = start_of_day.and_local_timezone(Local).unwrap().timestamp();
         let end_ts = end_of_day.and_local_timezone(Local).unwrap().timestamp();
 
-        let mut stmt = self.conn.prepare(
-            "SELECT t.id, t.title, t.description, t.created_at, t.due_date, t.completed, t.completed_at, t.priority, 
-                    GROUP_CONCAT(tags.name, ',') as tags
+        let sql = format!(
+            "SELECT {}
              FROM tasks t
              LEFT JOIN task_tags ON t.id = task_tags.task_id
              LEFT JOIN tags ON task_tags.tag_id = tags.id
              WHERE t.due_date BETWEEN ?1 AND ?2 AND t.completed = 0
              GROUP BY t.id
-             ORDER BY t.due_date ASC"
-        )?;
-
-        let rows = stmt.query_map(params![start_ts, end_ts], |row| {
-            Ok(Task::from_row(
-                row.get(0)?,
-                row.get(1)?,
-                row.get(2)?,
-                row.get(3)?,
-                row.get(4)?,
-                row.get(5)?,
-                row.get(6)?,
-                row.get(7)?,
-                row.get(8)?,
-            ).map_err(|_e| rusqlite::Error::ExecuteReturnedResults))
-        })?;
-
-        let mut tasks = Vec::new();
-        for task_result in rows {
-            match task_result? {
-                Ok(task) => tasks.push(task),
-                Err(e) => return Err(TaskError::Unknown(e.to_string())),
-            }
-        }
+             ORDER BY t.due_date ASC",
+            TASK_COLUMNS
+        );
 
-        Ok(tasks)
+        self.query_tasks(&sql, params![start_ts, end_ts])
     }
 
     /// タグでフィルタリングしたタスクを取得
     pub fn get_tasks_by_tag(&self, tag: &str) -> Result<Vec<Task>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT t.id, t.title, t.description, t.created_at, t.due_date, t.completed, t.completed_at, t.priority, 
-                    GROUP_CONCAT(tags.name, ',') as tags
+        let sql = format!(
+            "SELECT {}
              FROM tasks t
              JOIN task_tags ON t.id = task_tags.task_id
              JOIN tags ON task_tags.tag_id = tags.id
              WHERE tags.name = ?1 AND t.completed = 0
              GROUP BY t.id
-             ORDER BY t.created_at DESC"
-        )?;
-
-        let rows = stmt.query_map(params![tag], |row| {
-            Ok(Task::from_row(
-                row.get(0)?,
-                row.get(1)?,
-                row.get(2)?,
-                row.get(3)?,
-                row.get(4)?,
-                row.get(5)?,
-                row.get(6)?,
-                row.get(7)?,
-                row.get(8)?,
-            ).map_err(|_e| rusqlite::Error::ExecuteReturnedResults))
-        })?;
-
-        let mut tasks = Vec::new();
-        for task_result in rows {
-            match task_result? {
-                Ok(task) => tasks.push(task),
-                Err(e) => return Err(TaskError::Unknown(e.to_string())),
-            }
-        }
+             ORDER BY t.created_at DESC",
+            TASK_COLUMNS
+        );
 
-        Ok(tasks)
+        self.query_tasks(&sql, params![tag])
     }
 
     /// 特定のタスクを取得
     pub fn get_task(&self, id: i64) -> Result<Task> {
-        let mut stmt = self.conn.prepare(
-            "SELECT t.id, t.title, t.description, t.created_at, t.due_date, t.completed, t.completed_at, t.priority, 
-                    GROUP_CONCAT(tags.name, ',') as tags
+        let sql = format!(
+            "SELECT {}
              FROM tasks t
              LEFT JOIN task_tags ON t.id = task_tags.task_id
              LEFT JOIN tags ON task_tags.tag_id = tags.id
              WHERE t.id = ?1
-             GROUP BY t.id"
-        )?;
-
-        let task_result = stmt.query_row(params![id], |row| {
-            Ok(Task::from_row(
-                row.get(0)?,
-                row.get(1)?,
-                row.get(2)?,
-                row.get(3)?,
-                row.get(4)?,
-                row.get(5)?,
-                row.get(6)?,
-                row.get(7)?,
-                row.get(8)?,
-            ).map_err(|_e| rusqlite::Error::ExecuteReturnedResults))
-        });
+             GROUP BY t.id",
+            TASK_COLUMNS
+        );
 
-        match task_result {
-            Ok(Ok(task)) => Ok(task),
-            Ok(Err(e)) => Err(TaskError::Unknown(e.to_string())),
-            Err(_) => Err(TaskError::TaskNotFound(id)),
-        }
+        self.query_tasks(&sql, params![id])?
+            .into_iter()
+            .next()
+            .ok_or(TaskError::TaskNotFound(id))
     }
 
     /// タスクを更新
     pub fn update_task(&mut self, task: &Task) -> Result<()> {
-        let id = task.id.ok_or_else(|| TaskError::InvalidArgument("タスクIDが指定されていません".into()))?;
+        let id = task.id.ok_or_else(|| TaskError::InvalidArgument(t!("repository.missing_id")))?;
+        self.validate_dependencies(Some(id), &task.depends_on)?;
 
         // トランザクション開始
-        let tx = self.conn.transaction()?;
+        let tx = self.conn_mut().savepoint()?;
 
         let due_date = task.due_date.map(|date| date.timestamp());
         let completed_at = task.completed_at.map(|date| date.timestamp());
+        let start_date = task.start_date.map(|date| date.timestamp());
+        let wait_until = task.wait_until.map(|date| date.timestamp());
 
         tx.execute(
             "UPDATE tasks SET 
@@ -281,8 +259,13 @@ impl TaskRepository {
                 due_date = ?3, 
                 completed = ?4, 
                 completed_at = ?5, 
-                priority = ?6
-             WHERE id = ?7",
+                priority = ?6,
+                estimate = ?7,
+                status = ?8,
+                start_date = ?9,
+                wait_until = ?10,
+                project = ?11
+             WHERE id = ?12",
             params![
                 task.title,
                 task.description,
@@ -290,23 +273,58 @@ impl TaskRepository {
                 task.completed,
                 completed_at,
                 task.priority as i32,
+                task.estimate_minutes,
+                task.status,
+                start_date,
+                wait_until,
+                task.project,
                 id
             ],
         )?;
 
-        // 既存のタグ関連付けを削除
-        tx.execute("DELETE FROM task_tags WHERE task_id = ?1", params![id])?;
+        // タグの関連付けを置き換え（変わらないタグの行はそのまま）
+        replace_tags(&tx, id, &task.tags)?;
 
-        // 新しいタグを関連付け
-        for tag_name in &task.tags {
+        // 依存関係を置き換え
+        tx.execute("DELETE FROM task_dependencies WHERE task_id = ?1", params![id])?;
+        save_dependencies(&tx, id, &task.depends_on)?;
+
+        // カスタムフィールドを置き換え
+        tx.execute("DELETE FROM task_fields WHERE task_id = ?1", params![id])?;
+        save_custom_fields(&tx, id, &task.custom_fields)?;
+
+        // チェックリストを置き換え
+        tx.execute("DELETE FROM task_checklist WHERE task_id = ?1", params![id])?;
+        save_checklist(&tx, id, &task.checklist)?;
+
+        // トランザクションコミット
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// タスクのタグを変更（すべて削除・追加・削除。関連付けの行は変更するタグの分だけ書き換える）
+    pub fn update_tags(&mut self, id: i64, changes: &TagChanges) -> Result<()> {
+        // タスクの存在を確認
+        self.get_task(id)?;
+
+        let tx = self.conn_mut().savepoint()?;
+        if changes.clear {
+            tx.execute("DELETE FROM task_tags WHERE task_id = ?1", params![id])?;
+        }
+        for tag_name in changes.add.iter().filter(|tag| !changes.remove.contains(tag)) {
             let tag_id = get_or_create_tag(&tx, tag_name)?;
             tx.execute(
-                "INSERT INTO task_tags (task_id, tag_id) VALUES (?1, ?2)",
+                "INSERT OR IGNORE INTO task_tags (task_id, tag_id) VALUES (?1, ?2)",
                 params![id, tag_id],
             )?;
         }
-
-        // トランザクションコミット
+        for tag_name in &changes.remove {
+            tx.execute(
+                "DELETE FROM task_tags WHERE task_id = ?1 AND tag_id IN (SELECT id FROM tags WHERE name = ?2)",
+                params![id, tag_name],
+            )?;
+        }
         tx.commit()?;
 
         Ok(())
@@ -340,7 +358,7 @@ impl TaskRepository {
 
     /// タスクを削除
     pub fn delete_task(&mut self, id: i64) -> Result<()> {
-        let rows_affected = self.conn.execute("DELETE FROM tasks WHERE id = ?1", params![id])?;
+        let rows_affected = self.conn().execute("DELETE FROM tasks WHERE id = ?1", params![id])?;
         
         if rows_affected == 0 {
             return Err(TaskError::TaskNotFound(id));
@@ -349,16 +367,68 @@ impl TaskRepository {
         Ok(())
     }
 
-    /// 完了済みタスクをすべて削除
-    pub fn delete_completed_tasks(&mut self) -> Result<usize> {
-        let rows_affected = self.conn.execute("DELETE FROM tasks WHERE completed = 1", [])?;
-        Ok(rows_affected)
+    /// タスクを削除し、`restore_task` で元に戻すための記録を返す
+    pub fn delete_task_restorable(&mut self, id: i64) -> Result<DeletedTask> {
+        let task = self.get_task(id)?;
+        let mut stmt = self.conn().prepare("SELECT task_id FROM task_dependencies WHERE depends_on = ?1")?;
+        let dependents = stmt.query_map(params![id], |row| row.get(0))?.collect::<rusqlite::Result<Vec<i64>>>()?;
+        drop(stmt);
+        let deleted = DeletedTask {
+            task,
+            dependents,
+            time_entries: self.get_time_entries(id)?,
+            pomodoros: self.get_pomodoros(id)?,
+        };
+        self.delete_task(id)?;
+        Ok(deleted)
+    }
+
+    /// 削除したタスクを同じIDで元に戻す（作業時間・ポモドーロ・依存関係も戻す。
+    /// その後に削除されたタスクとの依存関係は戻さない）
+    pub fn restore_task(&mut self, deleted: &DeletedTask) -> Result<()> {
+        let id = deleted.task.id.ok_or_else(|| TaskError::InvalidArgument(t!("repository.missing_id")))?;
+        let exists = |repo: &Self, id: &i64| repo.get_task(*id).is_ok();
+        let mut task = deleted.task.clone();
+        task.depends_on.retain(|dependency| exists(self, dependency));
+        let dependents: Vec<i64> = deleted.dependents.iter().copied().filter(|dependent| exists(self, dependent)).collect();
+
+        self.transaction(|repo| {
+            repo.insert_task(&task, Some(id))?;
+            let conn = repo.conn();
+            for dependent in &dependents {
+                conn.execute(
+                    "INSERT OR IGNORE INTO task_dependencies (task_id, depends_on) VALUES (?1, ?2)",
+                    params![dependent, id],
+                )?;
+            }
+            for entry in &deleted.time_entries {
+                conn.execute(
+                    "INSERT INTO time_entries (id, task_id, started_at, ended_at) VALUES (?1, ?2, ?3, ?4)",
+                    params![entry.id, id, entry.started_at.timestamp(), entry.ended_at.map(|date| date.timestamp())],
+                )?;
+            }
+            for pomodoro in &deleted.pomodoros {
+                conn.execute(
+                    "INSERT INTO pomodoros (id, task_id, started_at, ended_at, minutes, interruptions)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![
+                        pomodoro.id,
+                        id,
+                        pomodoro.started_at.timestamp(),
+                        pomodoro.ended_at.timestamp(),
+                        pomodoro.minutes,
+                        pomodoro.interruptions
+                    ],
+                )?;
+            }
+            Ok(())
+        })
     }
 
     /// 全タグを取得
     #[allow(dead_code)]
     pub fn get_all_tags(&self) -> Result<Vec<Tag>> {
-        let mut stmt = self.conn.prepare("SELECT id, name FROM tags ORDER BY name")?;
+        let mut stmt = self.conn().prepare("SELECT id, name FROM tags ORDER BY name")?;
         let rows = stmt.query_map([], |row| {
             Ok(Tag {
                 id: Some(row.get(0)?),
@@ -374,12 +444,582 @@ impl TaskRepository {
         Ok(tags)
     }
 
+    /// タスクに設定されているプロジェクトの一覧（名前順、重複なし）
+    pub fn get_all_projects(&self) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn()
+            .prepare("SELECT DISTINCT project FROM tasks WHERE project IS NOT NULL ORDER BY project")?;
+        let projects = stmt.query_map([], |row| row.get(0))?.collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(projects)
+    }
+
+    /// 依存関係を検証（存在しないタスク・自分自身・循環する依存は不可）
+    pub fn validate_dependencies(&self, task_id: Option<i64>, depends_on: &[i64]) -> Result<()> {
+        if depends_on.is_empty() {
+            return Ok(());
+        }
+
+        let graph = self.get_dependency_graph()?;
+        for &dependency in depends_on {
+            if Some(dependency) == task_id {
+                return Err(TaskError::InvalidArgument(t!("repository.self_dependency")));
+            }
+            self.get_task(dependency)?;
+
+            // 依存先から辿って自分自身に戻る場合は循環
+            if let Some(id) = task_id {
+                let mut stack = vec![dependency];
+                let mut visited = HashSet::new();
+                while let Some(current) = stack.pop() {
+                    if current == id {
+                        return Err(TaskError::InvalidArgument(t!(
+                            "repository.circular_dependency",
+                            id = id,
+                            dependency = dependency,
+                        )));
+                    }
+                    if visited.insert(current) {
+                        stack.extend(graph.get(&current).into_iter().flatten().copied());
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 未完了の依存タスクを持つ（ブロックされている）タスクのIDを取得
+    pub fn get_blocked_task_ids(&self) -> Result<HashSet<i64>> {
+        let mut stmt = self.conn().prepare(
+            "SELECT DISTINCT d.task_id
+             FROM task_dependencies d
+             JOIN tasks t ON d.depends_on = t.id
+             WHERE t.completed = 0",
+        )?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+
+        let mut blocked = HashSet::new();
+        for row in rows {
+            blocked.insert(row?);
+        }
+
+        Ok(blocked)
+    }
+
+    /// カスタムフィールドを定義
+    pub fn define_field(&mut self, definition: &FieldDefinition) -> Result<()> {
+        let exists: bool = self.conn().query_row(
+            "SELECT EXISTS(SELECT 1 FROM field_definitions WHERE name = ?1)",
+            params![definition.name],
+            |row| row.get(0),
+        )?;
+        if exists {
+            return Err(TaskError::InvalidArgument(t!("repository.field_exists", name = definition.name)));
+        }
+
+        let enum_values = if definition.values.is_empty() {
+            None
+        } else {
+            Some(definition.values.join(","))
+        };
+        self.conn().execute(
+            "INSERT INTO field_definitions (name, field_type, enum_values) VALUES (?1, ?2, ?3)",
+            params![definition.name, definition.field_type.as_str(), enum_values],
+        )?;
+
+        Ok(())
+    }
+
+    /// 全カスタムフィールド定義を取得
+    pub fn get_field_definitions(&self) -> Result<Vec<FieldDefinition>> {
+        let mut stmt = self.conn().prepare(
+            "SELECT name, field_type, enum_values FROM field_definitions ORDER BY name",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+            ))
+        })?;
+
+        let mut definitions = Vec::new();
+        for row in rows {
+            let (name, field_type, enum_values) = row?;
+            let field_type = FieldType::parse(&field_type).map_err(TaskError::Unknown)?;
+            let values = enum_values
+                .map(|v| v.split(',').map(|s| s.to_string()).collect())
+                .unwrap_or_default();
+            definitions.push(FieldDefinition { name, field_type, values });
+        }
+
+        Ok(definitions)
+    }
+
+    /// カスタムフィールド定義を削除（設定済みの値も削除される）
+    pub fn remove_field(&mut self, name: &str) -> Result<()> {
+        let rows_affected = self.conn().execute(
+            "DELETE FROM field_definitions WHERE name = ?1",
+            params![name],
+        )?;
+
+        if rows_affected == 0 {
+            return Err(TaskError::FieldNotFound(name.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// 一覧の表示条件を保存（同じ名前があれば置き換え、置き換えた場合は true）
+    pub fn save_view(&mut self, view: &View) -> Result<bool> {
+        let exists: bool = self.conn().query_row(
+            "SELECT EXISTS(SELECT 1 FROM views WHERE name = ?1)",
+            params![view.name],
+            |row| row.get(0),
+        )?;
+        self.conn().execute(
+            "INSERT OR REPLACE INTO views (name, filter, sort, format, columns, show_all)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![view.name, view.filter, view.sort, view.format, view.columns, view.all],
+        )?;
+        Ok(exists)
+    }
+
+    /// 名前を指定して表示条件を取得
+    pub fn get_view(&self, name: &str) -> Result<View> {
+        self.query_views("WHERE name = ?1", params![name])?
+            .into_iter()
+            .next()
+            .ok_or_else(|| TaskError::ViewNotFound(name.to_string()))
+    }
+
+    /// 全表示条件を名前順に取得
+    pub fn get_views(&self) -> Result<Vec<View>> {
+        self.query_views("", [])
+    }
+
+    fn query_views(&self, condition: &str, params: impl rusqlite::Params) -> Result<Vec<View>> {
+        let sql = format!(
+            "SELECT name, filter, sort, format, columns, show_all FROM views {} ORDER BY name",
+            condition
+        );
+        let mut stmt = self.conn().prepare(&sql)?;
+        let rows = stmt.query_map(params, |row| {
+            Ok(View {
+                name: row.get(0)?,
+                filter: row.get(1)?,
+                sort: row.get(2)?,
+                format: row.get(3)?,
+                columns: row.get(4)?,
+                all: row.get(5)?,
+            })
+        })?;
+        Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+    }
+
+    /// 表示条件を削除
+    pub fn remove_view(&mut self, name: &str) -> Result<()> {
+        let rows_affected = self.conn().execute("DELETE FROM views WHERE name = ?1", params![name])?;
+        if rows_affected == 0 {
+            return Err(TaskError::ViewNotFound(name.to_string()));
+        }
+        Ok(())
+    }
+
+    /// テンプレートを保存（同じ名前があれば置き換え、置き換えた場合は true）
+    pub fn save_template(&mut self, template: &TaskTemplate) -> Result<bool> {
+        let exists: bool = self.conn().query_row(
+            "SELECT EXISTS(SELECT 1 FROM task_templates WHERE name = ?1)",
+            params![template.name],
+            |row| row.get(0),
+        )?;
+        self.conn().execute(
+            "INSERT OR REPLACE INTO task_templates (name, body) VALUES (?1, ?2)",
+            params![template.name, serde_json::to_string(template)?],
+        )?;
+        Ok(exists)
+    }
+
+    /// 名前を指定してテンプレートを取得
+    pub fn get_template(&self, name: &str) -> Result<TaskTemplate> {
+        let body: Option<String> = self
+            .conn()
+            .query_row("SELECT body FROM task_templates WHERE name = ?1", params![name], |row| row.get(0))
+            .optional()?;
+        let body = body.ok_or_else(|| TaskError::TemplateNotFound(name.to_string()))?;
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    /// 全テンプレートを名前順に取得
+    pub fn get_templates(&self) -> Result<Vec<TaskTemplate>> {
+        let mut stmt = self.conn().prepare("SELECT body FROM task_templates ORDER BY name")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        let mut templates = Vec::new();
+        for body in rows {
+            templates.push(serde_json::from_str(&body?)?);
+        }
+        Ok(templates)
+    }
+
+    /// テンプレートを削除
+    pub fn remove_template(&mut self, name: &str) -> Result<()> {
+        let rows_affected = self.conn().execute("DELETE FROM task_templates WHERE name = ?1", params![name])?;
+        if rows_affected == 0 {
+            return Err(TaskError::TemplateNotFound(name.to_string()));
+        }
+        Ok(())
+    }
+
+    /// タスクにメモを追加
+    pub fn add_note(&mut self, task_id: i64, note: &Note) -> Result<()> {
+        // タスクの存在確認
+        self.get_task(task_id)?;
+        save_note(self.conn(), task_id, note)
+    }
+
+    /// メモを全文検索する（空白で区切った語をすべて含むメモを関連度の高い順に返す）
+    ///
+    /// 索引は 3 文字ずつに分割しているため、2 文字以下の語は索引を使わずに照合する。
+    pub fn search_notes(&self, query: &str) -> Result<Vec<NoteMatch>> {
+        let (indexed, short): (Vec<&str>, Vec<&str>) =
+            query.split_whitespace().partition(|term| term.chars().count() >= 3);
+        if indexed.is_empty() && short.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut conditions = Vec::new();
+        let mut values = Vec::new();
+        if !indexed.is_empty() {
+            // 各語を引用符で囲み、FTS5 の演算子として解釈させない
+            conditions.push("task_notes_fts MATCH ?");
+            values.push(
+                indexed
+                    .iter()
+                    .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            );
+        }
+        for term in &short {
+            conditions.push("task_notes_fts.text LIKE ? ESCAPE '\\'");
+            let escaped = term.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+            values.push(format!("%{}%", escaped));
+        }
+        let (snippet, order) = if indexed.is_empty() {
+            ("task_notes_fts.text", "n.created_at DESC, n.id DESC")
+        } else {
+            ("snippet(task_notes_fts, 0, '', '', '…', 64)", "rank")
+        };
+
+        let sql = format!(
+            "SELECT n.task_id, n.created_at, n.text, {}
+             FROM task_notes_fts JOIN task_notes n ON n.id = task_notes_fts.rowid
+             WHERE {}
+             ORDER BY {}",
+            snippet,
+            conditions.join(" AND "),
+            order
+        );
+        let mut stmt = self.conn().prepare(&sql)?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(&values), |row| {
+            Ok(NoteMatch {
+                task_id: row.get(0)?,
+                note: Note { created_at: timestamp_to_local(row.get(1)?)?, text: row.get(2)? },
+                snippet: row.get(3)?,
+            })
+        })?;
+
+        let mut matches = Vec::new();
+        for row in rows {
+            matches.push(row?);
+        }
+        Ok(matches)
+    }
+
+    /// 全文検索でメモが一致したタスクのID
+    pub fn search_note_task_ids(&self, query: &str) -> Result<HashSet<i64>> {
+        Ok(self.search_notes(query)?.into_iter().map(|m| m.task_id).collect())
+    }
+
+    /// タスクに URL・ファイルを関連付ける
+    pub fn add_attachment(&mut self, task_id: i64, attachment: &Attachment) -> Result<()> {
+        // タスクの存在確認
+        self.get_task(task_id)?;
+        save_attachment(self.conn(), task_id, attachment)
+    }
+
+    /// タスクの index 番目（0 始まり、追加順）の添付を外す
+    pub fn remove_attachment(&mut self, task_id: i64, index: usize) -> Result<()> {
+        self.conn().execute(
+            "DELETE FROM task_attachments WHERE id =
+                (SELECT id FROM task_attachments WHERE task_id = ?1 ORDER BY id LIMIT 1 OFFSET ?2)",
+            params![task_id, index as i64],
+        )?;
+        Ok(())
+    }
+
+    /// コピーしたファイルのうち、いずれかのタスクから参照されているもののファイル名
+    pub fn get_stored_attachment_targets(&self) -> Result<HashSet<String>> {
+        let mut stmt = self.conn().prepare("SELECT DISTINCT target FROM task_attachments WHERE kind = ?1")?;
+        let rows = stmt.query_map(params![AttachmentKind::Stored.as_str()], |row| row.get::<_, String>(0))?;
+        let mut targets = HashSet::new();
+        for target in rows {
+            targets.insert(target?);
+        }
+        Ok(targets)
+    }
+
+    /// 設定の値を取得
+    pub fn get_setting(&self, key: &str) -> Result<Option<String>> {
+        Ok(self
+            .conn()
+            .query_row("SELECT value FROM settings WHERE key = ?1", params![key], |row| row.get(0))
+            .optional()?)
+    }
+
+    /// 設定の値を保存（None の場合は削除）
+    pub fn set_setting(&mut self, key: &str, value: Option<&str>) -> Result<()> {
+        match value {
+            Some(value) => self.conn().execute(
+                "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+                params![key, value],
+            )?,
+            None => self.conn().execute("DELETE FROM settings WHERE key = ?1", params![key])?,
+        };
+        Ok(())
+    }
+
+    /// カスタムフィールドの値を定義に従って検証・正規化
+    pub fn validate_custom_fields(
+        &self,
+        fields: &BTreeMap<String, String>,
+    ) -> Result<BTreeMap<String, String>> {
+        let definitions = self.get_field_definitions()?;
+        let mut validated = BTreeMap::new();
+
+        for (name, value) in fields {
+            let definition = definitions
+                .iter()
+                .find(|d| &d.name == name)
+                .ok_or_else(|| TaskError::FieldNotFound(name.clone()))?;
+            let value = definition.validate(value).map_err(TaskError::InvalidFieldValue)?;
+            validated.insert(name.clone(), value);
+        }
+
+        Ok(validated)
+    }
+
+    /// タイマーを開始（実行中のタイマーは同時に 1 つまで）
+    pub fn start_timer(&mut self, task_id: i64) -> Result<TimeEntry> {
+        // タスクの存在を確認
+        self.get_task(task_id)?;
+
+        let tx = self.conn_mut().savepoint()?;
+
+        let running: Option<i64> = tx.query_row(
+            "SELECT task_id FROM time_entries WHERE ended_at IS NULL",
+            [],
+            |row| row.get(0),
+        ).optional()?;
+        if let Some(running_task_id) = running {
+            return Err(TaskError::TimerAlreadyRunning(running_task_id));
+        }
+
+        let mut entry = TimeEntry::new(task_id, Local::now(), None);
+        tx.execute(
+            "INSERT INTO time_entries (task_id, started_at, ended_at) VALUES (?1, ?2, NULL)",
+            params![task_id, entry.started_at.timestamp()],
+        )?;
+        entry.id = Some(tx.last_insert_rowid());
+
+        tx.commit()?;
+
+        Ok(entry)
+    }
+
+    /// 実行中のタイマーを停止
+    pub fn stop_timer(&mut self) -> Result<TimeEntry> {
+        let mut entry = self.get_running_timer()?.ok_or(TaskError::NoTimerRunning)?;
+        let ended_at = Local::now();
+
+        self.conn().execute(
+            "UPDATE time_entries SET ended_at = ?1 WHERE id = ?2",
+            params![ended_at.timestamp(), entry.id],
+        )?;
+        entry.ended_at = Some(ended_at);
+
+        Ok(entry)
+    }
+
+    /// 実行中のタイマーを取得
+    pub fn get_running_timer(&self) -> Result<Option<TimeEntry>> {
+        let entries = self.query_time_entries(
+            "SELECT id, task_id, started_at, ended_at FROM time_entries WHERE ended_at IS NULL",
+            [],
+        )?;
+        Ok(entries.into_iter().next())
+    }
+
+    /// 終了済みの作業時間を記録
+    pub fn add_time_entry(&mut self, entry: &TimeEntry) -> Result<i64> {
+        let ended_at = entry.ended_at.ok_or_else(|| {
+            TaskError::InvalidArgument(t!("repository.missing_end"))
+        })?;
+        if ended_at <= entry.started_at {
+            return Err(TaskError::InvalidArgument(t!("repository.end_before_start")));
+        }
+
+        // タスクの存在を確認
+        self.get_task(entry.task_id)?;
+
+        self.conn().execute(
+            "INSERT INTO time_entries (task_id, started_at, ended_at) VALUES (?1, ?2, ?3)",
+            params![entry.task_id, entry.started_at.timestamp(), ended_at.timestamp()],
+        )?;
+
+        Ok(self.conn().last_insert_rowid())
+    }
+
+    /// タスクの作業時間を取得
+    pub fn get_time_entries(&self, task_id: i64) -> Result<Vec<TimeEntry>> {
+        self.query_time_entries(
+            "SELECT id, task_id, started_at, ended_at FROM time_entries
+             WHERE task_id = ?1 ORDER BY started_at",
+            params![task_id],
+        )
+    }
+
+    /// 指定した期間と重なる作業時間を取得（期間を省略した場合はすべて）
+    pub fn get_time_entries_between(
+        &self,
+        from: Option<DateTime<Local>>,
+        to: Option<DateTime<Local>>,
+    ) -> Result<Vec<TimeEntry>> {
+        let from_ts = from.map(|d| d.timestamp()).unwrap_or(i64::MIN);
+        let to_ts = to.map(|d| d.timestamp()).unwrap_or(i64::MAX);
+        self.query_time_entries(
+            "SELECT id, task_id, started_at, ended_at FROM time_entries
+             WHERE started_at < ?2 AND (ended_at IS NULL OR ended_at > ?1)
+             ORDER BY started_at",
+            params![from_ts, to_ts],
+        )
+    }
+
+    /// タスクの合計作業時間を取得
+    pub fn get_tracked_time(&self, task_id: i64) -> Result<Duration> {
+        Ok(self.get_time_entries(task_id)?
+            .iter()
+            .map(|e| e.duration())
+            .fold(Duration::zero(), |acc, d| acc + d))
+    }
+
+    /// 完了したポモドーロを記録（作業時間としても記録する）
+    pub fn record_pomodoro(&mut self, pomodoro: &Pomodoro) -> Result<i64> {
+        // タスクの存在を確認
+        self.get_task(pomodoro.task_id)?;
+
+        let tx = self.conn_mut().savepoint()?;
+
+        tx.execute(
+            "INSERT INTO pomodoros (task_id, started_at, ended_at, minutes, interruptions)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                pomodoro.task_id,
+                pomodoro.started_at.timestamp(),
+                pomodoro.ended_at.timestamp(),
+                pomodoro.minutes,
+                pomodoro.interruptions
+            ],
+        )?;
+        let pomodoro_id = tx.last_insert_rowid();
+
+        tx.execute(
+            "INSERT INTO time_entries (task_id, started_at, ended_at) VALUES (?1, ?2, ?3)",
+            params![
+                pomodoro.task_id,
+                pomodoro.started_at.timestamp(),
+                pomodoro.ended_at.timestamp()
+            ],
+        )?;
+
+        tx.commit()?;
+
+        Ok(pomodoro_id)
+    }
+
+    /// タスクのポモドーロを取得
+    pub fn get_pomodoros(&self, task_id: i64) -> Result<Vec<Pomodoro>> {
+        let mut stmt = self.conn().prepare(
+            "SELECT id, task_id, started_at, ended_at, minutes, interruptions
+             FROM pomodoros WHERE task_id = ?1 ORDER BY started_at",
+        )?;
+        let rows = stmt.query_map(params![task_id], |row| {
+            Ok(Pomodoro {
+                id: Some(row.get(0)?),
+                task_id: row.get(1)?,
+                started_at: timestamp_to_local(row.get(2)?)?,
+                ended_at: timestamp_to_local(row.get(3)?)?,
+                minutes: row.get(4)?,
+                interruptions: row.get(5)?,
+            })
+        })?;
+
+        let mut pomodoros = Vec::new();
+        for pomodoro in rows {
+            pomodoros.push(pomodoro?);
+        }
+
+        Ok(pomodoros)
+    }
+
+    /// タスクごとのポモドーロ数を多い順に取得（タスクID, タイトル, 回数）
+    pub fn get_pomodoro_counts(&self, limit: usize) -> Result<Vec<(i64, String, i64)>> {
+        let mut stmt = self.conn().prepare(
+            "SELECT t.id, t.title, COUNT(p.id) as count
+             FROM pomodoros p
+             JOIN tasks t ON p.task_id = t.id
+             GROUP BY t.id
+             ORDER BY count DESC, t.id
+             LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(params![limit as i64], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })?;
+
+        let mut counts = Vec::new();
+        for row in rows {
+            counts.push(row?);
+        }
+
+        Ok(counts)
+    }
+
+    /// タスクごとの合計作業時間を取得
+    pub fn get_all_tracked_times(&self) -> Result<HashMap<i64, Duration>> {
+        let now = Local::now().timestamp();
+        let mut stmt = self.conn().prepare(
+            "SELECT task_id, SUM(COALESCE(ended_at, ?1) - started_at)
+             FROM time_entries GROUP BY task_id",
+        )?;
+        let rows = stmt.query_map(params![now], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?))
+        })?;
+
+        let mut tracked = HashMap::new();
+        for row in rows {
+            let (task_id, seconds) = row?;
+            tracked.insert(task_id, Duration::seconds(seconds));
+        }
+
+        Ok(tracked)
+    }
+
     /// 統計情報を取得
     pub fn get_stats(&self) -> Result<HashMap<String, i64>> {
         let mut stats = HashMap::new();
         
         // 総タスク数
-        let total_count: i64 = self.conn.query_row(
+        let total_count: i64 = self.conn().query_row(
             "SELECT COUNT(*) FROM tasks", 
             [], 
             |row| row.get(0)
@@ -387,7 +1027,7 @@ impl TaskRepository {
         stats.insert("total".to_string(), total_count);
         
         // 完了済みタスク数
-        let completed_count: i64 = self.conn.query_row(
+        let completed_count: i64 = self.conn().query_row(
             "SELECT COUNT(*) FROM tasks WHERE completed = 1", 
             [], 
             |row| row.get(0)
@@ -395,7 +1035,7 @@ impl TaskRepository {
         stats.insert("completed".to_string(), completed_count);
         
         // 未完了タスク数
-        let active_count: i64 = self.conn.query_row(
+        let active_count: i64 = self.conn().query_row(
             "SELECT COUNT(*) FROM tasks WHERE completed = 0", 
             [], 
             |row| row.get(0)
@@ -404,7 +1044,7 @@ impl TaskRepository {
         
         // 優先度ごとの数
         for priority in 1..=3 {
-            let priority_count: i64 = self.conn.query_row(
+            let priority_count: i64 = self.conn().query_row(
                 "SELECT COUNT(*) FROM tasks WHERE priority = ?1 AND completed = 0", 
                 params![priority], 
                 |row| row.get(0)
@@ -414,7 +1054,7 @@ impl TaskRepository {
         
         // 期限切れのタスク数
         let now = Local::now().timestamp();
-        let overdue_count: i64 = self.conn.query_row(
+        let overdue_count: i64 = self.conn().query_row(
             "SELECT COUNT(*) FROM tasks WHERE due_date < ?1 AND due_date IS NOT NULL AND completed = 0", 
             params![now], 
             |row| row.get(0)
@@ -429,19 +1069,335 @@ impl TaskRepository {
         let start_ts = start_of_day.and_local_timezone(Local).unwrap().timestamp();
         let end_ts = end_of_day.and_local_timezone(Local).unwrap().timestamp();
         
-        let due_today_count: i64 = self.conn.query_row(
+        let due_today_count: i64 = self.conn().query_row(
             "SELECT COUNT(*) FROM tasks WHERE due_date BETWEEN ?1 AND ?2 AND completed = 0", 
             params![start_ts, end_ts], 
             |row| row.get(0)
         )?;
         stats.insert("due_today".to_string(), due_today_count);
         
+        // ポモドーロ数
+        let pomodoro_count: i64 = self.conn().query_row(
+            "SELECT COUNT(*) FROM pomodoros", 
+            [], 
+            |row| row.get(0)
+        )?;
+        stats.insert("pomodoros".to_string(), pomodoro_count);
+        
+        let pomodoro_today_count: i64 = self.conn().query_row(
+            "SELECT COUNT(*) FROM pomodoros WHERE ended_at BETWEEN ?1 AND ?2", 
+            params![start_ts, end_ts], 
+            |row| row.get(0)
+        )?;
+        stats.insert("pomodoros_today".to_string(), pomodoro_today_count);
+        
         Ok(stats)
     }
+
+    /// タスクを取得するクエリを実行し、カスタムフィールドを付加して返す
+    fn query_tasks<P: rusqlite::Params>(&self, sql: &str, params: P) -> Result<Vec<Task>> {
+        let mut stmt = self.conn().prepare(sql)?;
+        let rows = stmt.query_map(params, |row| {
+            let estimate_minutes: Option<i64> = row.get(9)?;
+            let status: String = row.get(10)?;
+            let start_date = row.get::<_, Option<i64>>(11)?.map(timestamp_to_local).transpose()?;
+            let wait_until = row.get::<_, Option<i64>>(12)?.map(timestamp_to_local).transpose()?;
+            let project: Option<String> = row.get(13)?;
+            Ok(Task::from_row(
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+                row.get(5)?,
+                row.get(6)?,
+                row.get(7)?,
+                row.get(8)?,
+            ).map(|mut task| {
+                task.estimate_minutes = estimate_minutes;
+                task.status = status;
+                task.start_date = start_date;
+                task.wait_until = wait_until;
+                task.project = project;
+                task
+            }).map_err(|_e| rusqlite::Error::ExecuteReturnedResults))
+        })?;
+
+        let mut tasks = Vec::new();
+        for task_result in rows {
+            match task_result? {
+                Ok(task) => tasks.push(task),
+                Err(e) => return Err(TaskError::Unknown(e.to_string())),
+            }
+        }
+
+        self.load_dependencies(&mut tasks)?;
+        self.load_custom_fields(&mut tasks)?;
+        self.load_checklists(&mut tasks)?;
+        self.load_notes(&mut tasks)?;
+        self.load_attachments(&mut tasks)?;
+
+        Ok(tasks)
+    }
+
+    /// 依存関係の一覧を取得（タスクID → 依存先のID）
+    fn get_dependency_graph(&self) -> Result<HashMap<i64, Vec<i64>>> {
+        let mut stmt = self.conn().prepare(
+            "SELECT task_id, depends_on FROM task_dependencies ORDER BY task_id, depends_on",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?;
+
+        let mut graph: HashMap<i64, Vec<i64>> = HashMap::new();
+        for row in rows {
+            let (task_id, depends_on) = row?;
+            graph.entry(task_id).or_default().push(depends_on);
+        }
+
+        Ok(graph)
+    }
+
+    /// タスクに依存関係を読み込む
+    fn load_dependencies(&self, tasks: &mut [Task]) -> Result<()> {
+        let mut graph = self.query_for_tasks(
+            tasks,
+            "SELECT task_id, depends_on FROM task_dependencies WHERE task_id IN ({ids}) ORDER BY task_id, depends_on",
+            |row| Ok((row.get(0)?, row.get::<_, i64>(1)?)),
+        )?;
+        for task in tasks.iter_mut() {
+            if let Some(depends_on) = task.id.and_then(|id| graph.remove(&id)) {
+                task.depends_on = depends_on;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 作業時間を取得するクエリを実行
+    fn query_time_entries<P: rusqlite::Params>(&self, sql: &str, params: P) -> Result<Vec<TimeEntry>> {
+        let mut stmt = self.conn().prepare(sql)?;
+        let rows = stmt.query_map(params, |row| {
+            Ok(TimeEntry {
+                id: Some(row.get(0)?),
+                task_id: row.get(1)?,
+                started_at: timestamp_to_local(row.get(2)?)?,
+                ended_at: row.get::<_, Option<i64>>(3)?.map(timestamp_to_local).transpose()?,
+            })
+        })?;
+
+        let mut entries = Vec::new();
+        for entry in rows {
+            entries.push(entry?);
+        }
+
+        Ok(entries)
+    }
+
+    /// 読み込んだタスクの分だけ副テーブルの行を取得する（タスクID → 行）
+    ///
+    /// `sql` の `{ids}` を ID のプレースホルダーに置き換え、変数の上限を超えないよう分けて問い合わせる。
+    fn query_for_tasks<T>(
+        &self,
+        tasks: &[Task],
+        sql: &str,
+        mut map: impl FnMut(&rusqlite::Row) -> rusqlite::Result<(i64, T)>,
+    ) -> Result<HashMap<i64, Vec<T>>> {
+        const CHUNK: usize = 500;
+
+        let ids: Vec<i64> = tasks.iter().filter_map(|task| task.id).collect();
+        let mut values: HashMap<i64, Vec<T>> = HashMap::new();
+        for chunk in ids.chunks(CHUNK) {
+            let placeholders = vec!["?"; chunk.len()].join(", ");
+            let mut stmt = self.conn().prepare(&sql.replace("{ids}", &placeholders))?;
+            let rows = stmt.query_map(rusqlite::params_from_iter(chunk), &mut map)?;
+            for row in rows {
+                let (task_id, value) = row?;
+                values.entry(task_id).or_default().push(value);
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// タスクにカスタムフィールドの値を読み込む
+    fn load_custom_fields(&self, tasks: &mut [Task]) -> Result<()> {
+        let mut fields = self.query_for_tasks(
+            tasks,
+            "SELECT task_id, name, value FROM task_fields WHERE task_id IN ({ids})",
+            |row| Ok((row.get(0)?, (row.get::<_, String>(1)?, row.get::<_, String>(2)?))),
+        )?;
+        for task in tasks.iter_mut() {
+            if let Some(values) = task.id.and_then(|id| fields.remove(&id)) {
+                task.custom_fields = values.into_iter().collect();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// タスクにチェックリストを読み込む
+    fn load_checklists(&self, tasks: &mut [Task]) -> Result<()> {
+        let mut checklists = self.query_for_tasks(
+            tasks,
+            "SELECT task_id, text, done FROM task_checklist WHERE task_id IN ({ids}) ORDER BY task_id, position",
+            |row| Ok((row.get(0)?, ChecklistItem { text: row.get(1)?, done: row.get(2)? })),
+        )?;
+        for task in tasks.iter_mut() {
+            if let Some(items) = task.id.and_then(|id| checklists.remove(&id)) {
+                task.checklist = items;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// タスクにメモを古い順に読み込む
+    fn load_notes(&self, tasks: &mut [Task]) -> Result<()> {
+        let mut notes = self.query_for_tasks(
+            tasks,
+            "SELECT task_id, created_at, text FROM task_notes WHERE task_id IN ({ids}) ORDER BY task_id, created_at, id",
+            |row| Ok((row.get(0)?, Note { created_at: timestamp_to_local(row.get(1)?)?, text: row.get(2)? })),
+        )?;
+        for task in tasks.iter_mut() {
+            if let Some(values) = task.id.and_then(|id| notes.remove(&id)) {
+                task.notes = values;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// タスクに添付を追加順に読み込む
+    fn load_attachments(&self, tasks: &mut [Task]) -> Result<()> {
+        let mut rows = self.query_for_tasks(
+            tasks,
+            "SELECT task_id, kind, target, name, added_at FROM task_attachments WHERE task_id IN ({ids}) ORDER BY task_id, id",
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    (
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, Option<String>>(3)?,
+                        timestamp_to_local(row.get(4)?)?,
+                    ),
+                ))
+            },
+        )?;
+        for task in tasks.iter_mut() {
+            let Some(values) = task.id.and_then(|id| rows.remove(&id)) else {
+                continue;
+            };
+            task.attachments = values
+                .into_iter()
+                .map(|(kind, target, name, added_at)| {
+                    let kind = AttachmentKind::parse(&kind)
+                        .ok_or_else(|| TaskError::Unknown(t!("attach.invalid_kind", kind = kind)))?;
+                    Ok(Attachment { kind, target, name, added_at })
+                })
+                .collect::<Result<_>>()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for TaskRepository {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            schema::release_connection(conn);
+        }
+    }
+}
+
+/// Unix タイムスタンプから DateTime<Local> に変換
+fn timestamp_to_local(ts: i64) -> rusqlite::Result<DateTime<Local>> {
+    DateTime::from_timestamp(ts, 0)
+        .map(|dt| dt.with_timezone(&Local))
+        .ok_or(rusqlite::Error::IntegralValueOutOfRange(0, ts))
+}
+
+/// 依存関係を保存 - トランザクション内で完結するためのヘルパー関数
+fn save_dependencies(tx: &Connection, task_id: i64, depends_on: &[i64]) -> Result<()> {
+    for dependency in depends_on {
+        tx.execute(
+            "INSERT OR IGNORE INTO task_dependencies (task_id, depends_on) VALUES (?1, ?2)",
+            params![task_id, dependency],
+        )?;
+    }
+    Ok(())
+}
+
+/// カスタムフィールドの値を保存 - トランザクション内で完結するためのヘルパー関数
+fn save_custom_fields(
+    tx: &Connection,
+    task_id: i64,
+    fields: &BTreeMap<String, String>,
+) -> Result<()> {
+    for (name, value) in fields {
+        tx.execute(
+            "INSERT INTO task_fields (task_id, name, value) VALUES (?1, ?2, ?3)",
+            params![task_id, name, value],
+        )?;
+    }
+    Ok(())
+}
+
+/// チェックリストを保存 - トランザクション内で完結するためのヘルパー関数
+fn save_checklist(tx: &Connection, task_id: i64, items: &[ChecklistItem]) -> Result<()> {
+    for (position, item) in items.iter().enumerate() {
+        tx.execute(
+            "INSERT INTO task_checklist (task_id, position, text, done) VALUES (?1, ?2, ?3, ?4)",
+            params![task_id, position as i64, item.text, item.done],
+        )?;
+    }
+    Ok(())
+}
+
+/// メモを保存 - トランザクション内で完結するためのヘルパー関数
+fn save_note(tx: &Connection, task_id: i64, note: &Note) -> Result<()> {
+    tx.execute(
+        "INSERT INTO task_notes (task_id, created_at, text) VALUES (?1, ?2, ?3)",
+        params![task_id, note.created_at.timestamp(), note.text],
+    )?;
+    Ok(())
+}
+
+/// 添付を保存 - トランザクション内で完結するためのヘルパー関数
+fn save_attachment(tx: &Connection, task_id: i64, attachment: &Attachment) -> Result<()> {
+    tx.execute(
+        "INSERT INTO task_attachments (task_id, kind, target, name, added_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![task_id, attachment.kind.as_str(), attachment.target, attachment.name, attachment.added_at.timestamp()],
+    )?;
+    Ok(())
+}
+
+/// タスクのタグの関連付けを tags に置き換える（なくなったタグの行を削除し、新しいタグの行だけを追加）
+fn replace_tags(tx: &Connection, task_id: i64, tags: &[String]) -> Result<()> {
+    let mut stmt = tx.prepare(
+        "SELECT tags.name FROM task_tags JOIN tags ON task_tags.tag_id = tags.id WHERE task_tags.task_id = ?1",
+    )?;
+    let current = stmt
+        .query_map(params![task_id], |row| row.get::<_, String>(0))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    for tag_name in current.iter().filter(|name| !tags.contains(name)) {
+        tx.execute(
+            "DELETE FROM task_tags WHERE task_id = ?1 AND tag_id IN (SELECT id FROM tags WHERE name = ?2)",
+            params![task_id, tag_name],
+        )?;
+    }
+    for tag_name in tags.iter().filter(|name| !current.contains(name)) {
+        let tag_id = get_or_create_tag(tx, tag_name)?;
+        tx.execute(
+            "INSERT OR IGNORE INTO task_tags (task_id, tag_id) VALUES (?1, ?2)",
+            params![task_id, tag_id],
+        )?;
+    }
+    Ok(())
 }
 
 /// タグの取得または作成 - トランザクション内で完結するためのヘルパー関数
-fn get_or_create_tag(tx: &rusqlite::Transaction, tag_name: &str) -> Result<i64> {
+fn get_or_create_tag(tx: &Connection, tag_name: &str) -> Result<i64> {
     // タグが存在するか確認
     let mut stmt = tx.prepare("SELECT id FROM tags WHERE name = ?1")?;
     let tag_id = stmt.query_row(params![tag_name], |row| row.get(0));
@@ -1,8 +1,16 @@
 use rusqlite::{Connection, Result};
 use crate::error::TaskError;
+use std::cell::{Cell, RefCell};
 use std::env;
 use std::path::PathBuf;
 
+thread_local! {
+    /// 再利用のために保持している接続
+    static SHARED_CONNECTION: RefCell<Option<Connection>> = const { RefCell::new(None) };
+    /// 接続を再利用するか（shell の実行中のみ有効）
+    static REUSE_CONNECTION: Cell<bool> = const { Cell::new(false) };
+}
+
 // テスト用のDBパスを環境変数から取得する関数
 fn get_test_db_path() -> Option<PathBuf> {
     env::var("TASK_MANAGER_TEST_DB").ok().map(PathBuf::from)
@@ -28,6 +36,13 @@ pub fn initialize_db(conn: &mut Connection) -> Result<()> {
         [],
     )?;
 
+    // 既存のデータベースに追加された列を補う
+    add_column_if_missing(&tx, "tasks", "estimate", "INTEGER")?;
+    add_column_if_missing(&tx, "tasks", "status", "TEXT NOT NULL DEFAULT 'todo'")?;
+    add_column_if_missing(&tx, "tasks", "start_date", "INTEGER")?;
+    add_column_if_missing(&tx, "tasks", "wait_until", "INTEGER")?;
+    add_column_if_missing(&tx, "tasks", "project", "TEXT")?;
+
     // タグテーブル作成
     tx.execute(
         "CREATE TABLE IF NOT EXISTS tags (
@@ -49,6 +64,168 @@ pub fn initialize_db(conn: &mut Connection) -> Result<()> {
         [],
     )?;
 
+    // タスクの依存関係テーブル作成（task_id は depends_on の完了を待つ）
+    tx.execute(
+        "CREATE TABLE IF NOT EXISTS task_dependencies (
+            task_id INTEGER NOT NULL,
+            depends_on INTEGER NOT NULL,
+            PRIMARY KEY (task_id, depends_on),
+            FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE,
+            FOREIGN KEY (depends_on) REFERENCES tasks (id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // タスクのチェックリストテーブル作成（position は 0 始まりの表示順）
+    tx.execute(
+        "CREATE TABLE IF NOT EXISTS task_checklist (
+            task_id INTEGER NOT NULL,
+            position INTEGER NOT NULL,
+            text TEXT NOT NULL,
+            done INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (task_id, position),
+            FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // タスクのメモテーブル作成
+    tx.execute(
+        "CREATE TABLE IF NOT EXISTS task_notes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id INTEGER NOT NULL,
+            created_at INTEGER NOT NULL,
+            text TEXT NOT NULL,
+            FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // メモの全文検索テーブル作成（task_notes を元にした索引。日本語のように空白で区切らない文も
+    // 検索できるよう 3 文字ずつに分割する）
+    let fts_exists: bool = tx.query_row(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE name = 'task_notes_fts')",
+        [],
+        |row| row.get(0),
+    )?;
+    tx.execute(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS task_notes_fts USING fts5(
+            text, content = 'task_notes', content_rowid = 'id', tokenize = 'trigram'
+        )",
+        [],
+    )?;
+    if !fts_exists {
+        // 既存のメモを索引に加える
+        tx.execute("INSERT INTO task_notes_fts (task_notes_fts) VALUES ('rebuild')", [])?;
+    }
+    // メモの追加・削除・変更に合わせて索引を更新する（タスクの削除による連鎖削除も含む）
+    tx.execute_batch(
+        "CREATE TRIGGER IF NOT EXISTS task_notes_fts_insert AFTER INSERT ON task_notes BEGIN
+            INSERT INTO task_notes_fts (rowid, text) VALUES (new.id, new.text);
+         END;
+         CREATE TRIGGER IF NOT EXISTS task_notes_fts_delete AFTER DELETE ON task_notes BEGIN
+            INSERT INTO task_notes_fts (task_notes_fts, rowid, text) VALUES ('delete', old.id, old.text);
+         END;
+         CREATE TRIGGER IF NOT EXISTS task_notes_fts_update AFTER UPDATE ON task_notes BEGIN
+            INSERT INTO task_notes_fts (task_notes_fts, rowid, text) VALUES ('delete', old.id, old.text);
+            INSERT INTO task_notes_fts (rowid, text) VALUES (new.id, new.text);
+         END;",
+    )?;
+
+    // タスクの添付テーブル作成（kind は link・file・stored）
+    tx.execute(
+        "CREATE TABLE IF NOT EXISTS task_attachments (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id INTEGER NOT NULL,
+            kind TEXT NOT NULL,
+            target TEXT NOT NULL,
+            name TEXT,
+            added_at INTEGER NOT NULL,
+            FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // カスタムフィールド定義テーブル作成
+    tx.execute(
+        "CREATE TABLE IF NOT EXISTS field_definitions (
+            name TEXT PRIMARY KEY,
+            field_type TEXT NOT NULL,
+            enum_values TEXT
+        )",
+        [],
+    )?;
+
+    // タスクとカスタムフィールド値の関連テーブル作成
+    tx.execute(
+        "CREATE TABLE IF NOT EXISTS task_fields (
+            task_id INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            value TEXT NOT NULL,
+            PRIMARY KEY (task_id, name),
+            FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE,
+            FOREIGN KEY (name) REFERENCES field_definitions (name) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // 作業時間テーブル作成
+    tx.execute(
+        "CREATE TABLE IF NOT EXISTS time_entries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id INTEGER NOT NULL,
+            started_at INTEGER NOT NULL,
+            ended_at INTEGER,
+            FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // ポモドーロテーブル作成
+    tx.execute(
+        "CREATE TABLE IF NOT EXISTS pomodoros (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id INTEGER NOT NULL,
+            started_at INTEGER NOT NULL,
+            ended_at INTEGER NOT NULL,
+            minutes INTEGER NOT NULL,
+            interruptions INTEGER NOT NULL DEFAULT 0,
+            FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // 保存した一覧の表示条件テーブル作成
+    tx.execute(
+        "CREATE TABLE IF NOT EXISTS views (
+            name TEXT PRIMARY KEY,
+            filter TEXT,
+            sort TEXT,
+            format TEXT,
+            columns TEXT,
+            show_all INTEGER NOT NULL DEFAULT 0
+        )",
+        [],
+    )?;
+
+    // タスクのテンプレートテーブル作成（内容は JSON）
+    tx.execute(
+        "CREATE TABLE IF NOT EXISTS task_templates (
+            name TEXT PRIMARY KEY,
+            body TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    // 設定テーブル作成（有効なコンテキストなど）
+    tx.execute(
+        "CREATE TABLE IF NOT EXISTS settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL
+        )",
+        [],
+    )?;
+
     // インデックス作成
     tx.execute(
         "CREATE INDEX IF NOT EXISTS idx_tasks_completed ON tasks (completed)",
@@ -66,6 +243,28 @@ pub fn initialize_db(conn: &mut Connection) -> Result<()> {
         "CREATE INDEX IF NOT EXISTS idx_tags_name ON tags (name)",
         [],
     )?;
+    tx.execute(
+        "CREATE INDEX IF NOT EXISTS idx_time_entries_task_id ON time_entries (task_id)",
+        [],
+    )?;
+    tx.execute(
+        "CREATE INDEX IF NOT EXISTS idx_task_notes_task_id ON task_notes (task_id)",
+        [],
+    )?;
+    tx.execute(
+        "CREATE INDEX IF NOT EXISTS idx_task_attachments_task_id ON task_attachments (task_id)",
+        [],
+    )?;
+    tx.execute(
+        "CREATE INDEX IF NOT EXISTS idx_pomodoros_task_id ON pomodoros (task_id)",
+        [],
+    )?;
+    // 実行中のタイマー（ended_at が NULL）は同時に 1 つまで
+    tx.execute(
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_time_entries_running
+         ON time_entries ((ended_at IS NULL)) WHERE ended_at IS NULL",
+        [],
+    )?;
 
     // トランザクションコミット
     tx.commit()?;
@@ -73,24 +272,55 @@ pub fn initialize_db(conn: &mut Connection) -> Result<()> {
     Ok(())
 }
 
-/// データベース接続の取得
-pub fn get_connection() -> crate::error::Result<Connection> {
-    // テスト用のDBパスが環境変数で設定されている場合はそれを使用
+/// テーブルに列が存在しない場合は追加する（既存データベースのマイグレーション用）
+fn add_column_if_missing(
+    tx: &rusqlite::Transaction,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<()> {
+    let mut stmt = tx.prepare(&format!("PRAGMA table_info({})", table))?;
+    let exists = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<Result<Vec<_>>>()?
+        .iter()
+        .any(|name| name == column);
+
+    if !exists {
+        tx.execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+            [],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// データディレクトリ（データベース・設定ファイルの保存場所）の取得
+pub fn data_dir() -> crate::error::Result<PathBuf> {
+    // テスト用のDBパスが環境変数で設定されている場合はその親ディレクトリを使用
     if let Some(test_path) = get_test_db_path() {
-        let mut conn = Connection::open(test_path)?;
-        // 外部キー制約を有効化
-        conn.execute("PRAGMA foreign_keys = ON", [])?;
-        // データベーススキーマの初期化
-        initialize_db(&mut conn)?;
-        return Ok(conn);
+        return Ok(test_path
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(|| PathBuf::from(".")));
     }
 
     // 通常の動作：ホームディレクトリを使用
-    let home_dir = home::home_dir().ok_or_else(|| TaskError::Unknown("ホームディレクトリを特定できません".to_string()))?;
+    let home_dir = home::home_dir().ok_or_else(|| TaskError::Unknown(t!("repository.no_home_dir")))?;
     let db_dir = home_dir.join(".task-manager-rs");
-    std::fs::create_dir_all(&db_dir).map_err(|e| TaskError::IoError(e))?;
-    
-    let db_path = db_dir.join("tasks.db");
+    std::fs::create_dir_all(&db_dir).map_err(TaskError::IoError)?;
+    Ok(db_dir)
+}
+
+/// データベース接続の取得
+pub fn get_connection() -> crate::error::Result<Connection> {
+    // テスト用のDBパスが環境変数で設定されている場合はそれを使用
+    let db_path = match get_test_db_path() {
+        Some(test_path) => test_path,
+        None => data_dir()?.join("tasks.db"),
+    };
+
     let mut conn = Connection::open(db_path)?;
     
     // 外部キー制約を有効化
@@ -100,4 +330,34 @@ pub fn get_connection() -> crate::error::Result<Connection> {
     initialize_db(&mut conn)?;
     
     Ok(conn)
-} 
+}
+
+/// 接続の再利用を切り替える（無効にすると保持している接続を閉じる）
+///
+/// 有効な間は `TaskRepository` を破棄しても接続を閉じず、次に作成するインスタンスで使う。
+pub fn set_connection_reuse(enabled: bool) {
+    REUSE_CONNECTION.with(|reuse| reuse.set(enabled));
+    if !enabled {
+        SHARED_CONNECTION.with(|shared| shared.borrow_mut().take());
+    }
+}
+
+/// 接続を取得（保持している接続があればそれを使う）
+pub fn acquire_connection() -> crate::error::Result<Connection> {
+    match SHARED_CONNECTION.with(|shared| shared.borrow_mut().take()) {
+        Some(conn) => Ok(conn),
+        None => get_connection(),
+    }
+}
+
+/// 使い終わった接続を返す（再利用しない場合や、すでに保持している場合は閉じる）
+pub fn release_connection(conn: Connection) {
+    if REUSE_CONNECTION.with(Cell::get) {
+        SHARED_CONNECTION.with(|shared| {
+            let mut shared = shared.borrow_mut();
+            if shared.is_none() {
+                *shared = Some(conn);
+            }
+        });
+    }
+}
@@ -1,37 +1,52 @@
+use std::fmt;
 use thiserror::Error;
 
+/// アプリケーションのエラー（メッセージはカタログの error.* から取得）
 #[derive(Error, Debug)]
 pub enum TaskError {
-    #[error("データベースエラー: {0}")]
     DatabaseError(#[from] rusqlite::Error),
-
-    #[error("IOエラー: {0}")]
     IoError(#[from] std::io::Error),
-
-    #[error("不正な日付形式: {0}")]
     InvalidDateFormat(String),
-
-    #[error("不正な優先度: {0}. 優先度は 1, 2, 3 のいずれかである必要があります")]
     InvalidPriority(i32),
-
-    #[error("タスクが見つかりません: ID {0}")]
     TaskNotFound(i64),
-
-    #[error("CSVエラー: {0}")]
     CsvError(#[from] csv::Error),
-
-    #[error("JSONエラー: {0}")]
     JsonError(#[from] serde_json::Error),
-
-    #[error("不正な引数: {0}")]
     InvalidArgument(String),
-
-    #[error("タグが見つかりません: {0}")]
     #[allow(dead_code)]
     TagNotFound(String),
-
-    #[error("不明なエラー: {0}")]
+    FieldNotFound(String),
+    ViewNotFound(String),
+    TemplateNotFound(String),
+    InvalidFieldValue(String),
+    TimerAlreadyRunning(i64),
+    NoTimerRunning,
+    ConfigError(String),
     Unknown(String),
 }
 
+impl fmt::Display for TaskError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            TaskError::DatabaseError(e) => t!("error.database", detail = e),
+            TaskError::IoError(e) => t!("error.io", detail = e),
+            TaskError::InvalidDateFormat(detail) => t!("error.invalid_date_format", detail = detail),
+            TaskError::InvalidPriority(value) => t!("error.invalid_priority", value = value),
+            TaskError::TaskNotFound(id) => t!("error.task_not_found", id = id),
+            TaskError::CsvError(e) => t!("error.csv", detail = e),
+            TaskError::JsonError(e) => t!("error.json", detail = e),
+            TaskError::InvalidArgument(detail) => t!("error.invalid_argument", detail = detail),
+            TaskError::TagNotFound(tag) => t!("error.tag_not_found", tag = tag),
+            TaskError::FieldNotFound(name) => t!("error.field_not_found", name = name),
+            TaskError::ViewNotFound(name) => t!("error.view_not_found", name = name),
+            TaskError::TemplateNotFound(name) => t!("error.template_not_found", name = name),
+            TaskError::InvalidFieldValue(detail) => t!("error.invalid_field_value", detail = detail),
+            TaskError::TimerAlreadyRunning(id) => t!("error.timer_already_running", id = id),
+            TaskError::NoTimerRunning => t!("error.no_timer_running"),
+            TaskError::ConfigError(detail) => t!("error.config", detail = detail),
+            TaskError::Unknown(detail) => t!("error.unknown", detail = detail),
+        };
+        write!(f, "{}", message)
+    }
+}
+
 pub type Result<T> = std::result::Result<T, TaskError>; 
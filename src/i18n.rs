@@ -0,0 +1,232 @@
+//! メッセージの多言語化
+//!
+//! メッセージは `locales/<言語>.toml` のカタログに置き、`t!` マクロでキーを指定して取得する。
+//! 値の `{name}` は引数で置き換えられる。件数によって表現が変わるメッセージは
+//! `one` / `other` を持つテーブルとして定義し、`tn!` マクロで取得する。
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+/// 日本語のカタログ
+const JA_CATALOG: &str = include_str!("../locales/ja.toml");
+
+/// 英語のカタログ
+const EN_CATALOG: &str = include_str!("../locales/en.toml");
+
+/// 表示言語
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Locale {
+    Ja,
+    En,
+}
+
+impl Locale {
+    /// すべての言語
+    pub const ALL: [Locale; 2] = [Locale::Ja, Locale::En];
+
+    /// 言語名を解析（"ja", "en", "en_US.UTF-8" など）
+    pub fn parse(value: &str) -> Option<Self> {
+        let language = value
+            .split(['_', '-', '.'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        match language.as_str() {
+            "ja" => Some(Locale::Ja),
+            "en" => Some(Locale::En),
+            _ => None,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::Ja => "ja",
+            Locale::En => "en",
+        }
+    }
+
+    fn source(&self) -> &'static str {
+        match self {
+            Locale::Ja => JA_CATALOG,
+            Locale::En => EN_CATALOG,
+        }
+    }
+}
+
+/// プロセス全体の表示言語（0: 日本語, 1: 英語）
+static LOCALE: AtomicU8 = AtomicU8::new(0);
+
+thread_local! {
+    /// スレッドごとに一時的に切り替えた表示言語
+    static LOCALE_OVERRIDE: Cell<Option<Locale>> = const { Cell::new(None) };
+}
+
+/// 表示言語を設定
+pub fn set_locale(locale: Locale) {
+    LOCALE.store(locale as u8, Ordering::Relaxed);
+}
+
+/// 現在の表示言語を取得
+pub fn locale() -> Locale {
+    LOCALE_OVERRIDE.with(Cell::get).unwrap_or_else(|| {
+        match LOCALE.load(Ordering::Relaxed) {
+            1 => Locale::En,
+            _ => Locale::Ja,
+        }
+    })
+}
+
+/// このスレッドでのみ表示言語を切り替えて処理を実行
+pub fn with_locale<R>(locale: Locale, f: impl FnOnce() -> R) -> R {
+    let previous = LOCALE_OVERRIDE.with(|l| l.replace(Some(locale)));
+    let result = f();
+    LOCALE_OVERRIDE.with(|l| l.set(previous));
+    result
+}
+
+/// 表示言語を決定して設定する
+///
+/// 優先順位は --lang、設定ファイルの lang、環境変数 LC_ALL / LC_MESSAGES / LANG の順で、
+/// いずれもない（または対応していない言語の）場合は日本語。
+pub fn init(lang: Option<&str>, config_lang: Option<&str>) {
+    let from_env = || {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Locale::parse(&value))
+    };
+
+    let locale = lang
+        .and_then(Locale::parse)
+        .or_else(|| config_lang.and_then(Locale::parse))
+        .or_else(from_env)
+        .unwrap_or(Locale::Ja);
+    set_locale(locale);
+}
+
+/// 言語ごとのカタログ（"add.done" のような平坦なキー → メッセージ）
+fn catalogs() -> &'static HashMap<Locale, HashMap<String, String>> {
+    static CATALOGS: OnceLock<HashMap<Locale, HashMap<String, String>>> = OnceLock::new();
+    CATALOGS.get_or_init(|| {
+        Locale::ALL
+            .iter()
+            .map(|locale| (*locale, catalog_entries(*locale)))
+            .collect()
+    })
+}
+
+/// カタログを平坦なキーの一覧に変換
+pub fn catalog_entries(locale: Locale) -> HashMap<String, String> {
+    let table: toml::Table = toml::from_str(locale.source())
+        .unwrap_or_else(|e| panic!("locales/{}.toml の解析に失敗しました: {}", locale.code(), e));
+    let mut entries = HashMap::new();
+    flatten("", &toml::Value::Table(table), &mut entries);
+    entries
+}
+
+fn flatten(prefix: &str, value: &toml::Value, entries: &mut HashMap<String, String>) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table {
+                let key = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten(&key, value, entries);
+            }
+        }
+        toml::Value::String(message) => {
+            entries.insert(prefix.to_string(), message.clone());
+        }
+        other => {
+            entries.insert(prefix.to_string(), other.to_string());
+        }
+    }
+}
+
+/// メッセージを取得し、{name} を引数で置き換える（キーがない場合はキーそのもの）
+pub fn translate(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    match lookup(key) {
+        Some(message) => interpolate(message, args),
+        None => key.to_string(),
+    }
+}
+
+/// 件数に応じたメッセージを取得（{count} も置き換える）
+pub fn translate_plural(key: &str, count: i64, args: &[(&str, &dyn Display)]) -> String {
+    let category = if count == 1 { "one" } else { "other" };
+    let message = lookup(&format!("{}.{}", key, category))
+        .or_else(|| lookup(&format!("{}.other", key)));
+
+    match message {
+        Some(message) => {
+            let mut args = args.to_vec();
+            args.push(("count", &count));
+            interpolate(message, &args)
+        }
+        None => key.to_string(),
+    }
+}
+
+fn lookup(key: &str) -> Option<&'static str> {
+    catalogs()
+        .get(&locale())
+        .and_then(|catalog| catalog.get(key))
+        .map(String::as_str)
+}
+
+fn interpolate(message: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut result = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after
+            .find('}')
+            .and_then(|end| args.iter().find(|(name, _)| *name == &after[..end]).map(|arg| (end, arg.1)));
+        match value {
+            Some((end, value)) => {
+                result.push_str(&value.to_string());
+                rest = &after[end + 1..];
+            }
+            None => {
+                result.push('{');
+                rest = after;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// メッセージを取得する（例: `t!("add.done", id = 3)`）
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::translate($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::translate(
+            $key,
+            &[$((stringify!($name), &$value as &dyn std::fmt::Display)),+],
+        )
+    };
+}
+
+/// 件数に応じたメッセージを取得する（例: `tn!("list.count", tasks.len())`）
+macro_rules! tn {
+    ($key:expr, $count:expr) => {
+        $crate::i18n::translate_plural($key, $count as i64, &[])
+    };
+    ($key:expr, $count:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::translate_plural(
+            $key,
+            $count as i64,
+            &[$((stringify!($name), &$value as &dyn std::fmt::Display)),+],
+        )
+    };
+}
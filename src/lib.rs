@@ -1,7 +1,11 @@
 // 外部からアクセス可能なモジュールを公開
+#[macro_use]
+pub mod i18n;
 pub mod cli;
 pub mod commands;
+pub mod config;
 pub mod db;
 pub mod error;
 pub mod models;
+pub mod tui;
 pub mod utils; 
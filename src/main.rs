@@ -1,55 +1,24 @@
-mod cli;
-mod commands;
-mod db;
-mod error;
-mod models;
-mod utils;
-
+// モジュールはライブラリクレートのものを使う
 use clap::Parser;
-use cli::{Cli, Commands};
-use colored::Colorize;
+use task_manager_rs::cli::Cli;
+use task_manager_rs::utils::theme::{self, Role};
+use task_manager_rs::{commands, config, i18n};
 
 fn main() {
     // コマンドライン引数の解析
     let cli = Cli::parse();
     
+    // 表示言語と色の決定（設定ファイルの誤りはコマンドの実行時に報告する）
+    let config = config::Config::load().unwrap_or_default();
+    i18n::init(cli.lang.as_deref(), config.lang.as_deref());
+    theme::init(cli.color.as_deref(), config.color, config.theme, cli.ascii || config.ascii);
+    
     // コマンドの実行
-    let result = match cli.command {
-        Commands::Add { title, description, due, priority, tags } => {
-            commands::add_task(title, description, due, priority, tags)
-        },
-        Commands::List { all, priority, due_today, tags } => {
-            commands::list_tasks(all, priority, due_today, tags)
-        },
-        Commands::Show { id } => {
-            commands::show_task(id)
-        },
-        Commands::Update { id, title, description, due, remove_due, priority, tags } => {
-            commands::update_task(id, title, description, due, remove_due, priority, tags)
-        },
-        Commands::Complete { id } => {
-            commands::complete_task(id)
-        },
-        Commands::Uncomplete { id } => {
-            commands::uncomplete_task(id)
-        },
-        Commands::Delete { id, completed } => {
-            commands::delete_task(id, completed)
-        },
-        Commands::Stats => {
-            commands::show_stats()
-        },
-        Commands::Export { file, format } => {
-            commands::export_tasks(file, format)
-        },
-        Commands::Import { file } => {
-            commands::import_tasks(file)
-        },
-    };
+    let result = commands::run(cli.command);
     
     // エラーハンドリング
     if let Err(e) = result {
-        eprintln!("{} {}", "エラー:".red().bold(), e);
+        eprintln!("{} {}", theme::paint(&i18n::translate("error.prefix", &[]), Role::Error), e);
         std::process::exit(1);
     }
 }
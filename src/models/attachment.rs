@@ -0,0 +1,120 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// コピーしたファイルの名前に使うハッシュ（SHA-256 の 16 進表記）の桁数
+const HASH_LEN: usize = 64;
+
+/// 添付の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AttachmentKind {
+    /// URL
+    Link,
+    /// 元の場所を参照するファイル（target は絶対パス）
+    File,
+    /// データの保存先にコピーしたファイル（target は内容のハッシュによるファイル名）
+    Stored,
+}
+
+impl AttachmentKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AttachmentKind::Link => "link",
+            AttachmentKind::File => "file",
+            AttachmentKind::Stored => "stored",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "link" => Some(AttachmentKind::Link),
+            "file" => Some(AttachmentKind::File),
+            "stored" => Some(AttachmentKind::Stored),
+            _ => None,
+        }
+    }
+}
+
+/// タスクに関連付けた URL またはファイル
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attachment {
+    pub kind: AttachmentKind,
+    pub target: String,
+    /// 表示名（ファイルは元のファイル名、URL は None）
+    #[serde(default)]
+    pub name: Option<String>,
+    pub added_at: DateTime<Local>,
+}
+
+impl Attachment {
+    pub fn new(kind: AttachmentKind, target: String, name: Option<String>) -> Self {
+        Self { kind, target, name, added_at: Local::now() }
+    }
+
+    /// 種類に合った対象か（インポートしたデータは保存先の外を指すパスなどを含みうる）
+    pub fn validate(&self) -> Result<(), String> {
+        let valid = match self.kind {
+            AttachmentKind::Link => is_url(&self.target),
+            AttachmentKind::File => Path::new(&self.target).is_absolute(),
+            AttachmentKind::Stored => is_stored_name(&self.target),
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(t!("attach.invalid_target", kind = self.kind.as_str(), target = self.target))
+        }
+    }
+
+    /// 開く対象（URL またはファイルのパス）。コピーしたファイルは dir（添付ファイルの保存先）の下
+    ///
+    /// コピーしたファイルの名前が不正な場合は None
+    pub fn location(&self, dir: &Path) -> Option<String> {
+        match self.kind {
+            AttachmentKind::Link | AttachmentKind::File => Some(self.target.clone()),
+            AttachmentKind::Stored if is_stored_name(&self.target) => {
+                Some(dir.join(&self.target).to_string_lossy().into_owned())
+            }
+            AttachmentKind::Stored => None,
+        }
+    }
+}
+
+/// コピーしたファイルの名前（内容のハッシュと、英数字だけの場合は元の拡張子）
+pub fn stored_name(hash: &str, extension: Option<&str>) -> String {
+    match extension {
+        Some(extension) if is_extension(extension) => format!("{}.{}", hash, extension),
+        _ => hash.to_string(),
+    }
+}
+
+/// コピーしたファイルの名前として正しいか（小文字の 16 進のハッシュと、任意の英数字の拡張子）
+pub fn is_stored_name(name: &str) -> bool {
+    let (hash, extension) = match name.split_once('.') {
+        Some((hash, extension)) => (hash, Some(extension)),
+        None => (name, None),
+    };
+    hash.len() == HASH_LEN
+        && hash.chars().all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+        && extension.is_none_or(is_extension)
+}
+
+fn is_extension(extension: &str) -> bool {
+    !extension.is_empty() && extension.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// scheme://... または mailto: の形式か
+pub fn is_url(text: &str) -> bool {
+    if let Some(address) = text.strip_prefix("mailto:") {
+        return !address.is_empty();
+    }
+    match text.split_once("://") {
+        Some((scheme, rest)) => {
+            scheme.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+                && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+                && !rest.is_empty()
+                && !text.contains(char::is_whitespace)
+        }
+        None => false,
+    }
+}
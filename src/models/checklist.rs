@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// タスク内のチェックリストの項目（タスクにするほどではない小さな手順）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChecklistItem {
+    pub text: String,
+    #[serde(default)]
+    pub done: bool,
+}
+
+impl ChecklistItem {
+    pub fn new(text: String) -> Self {
+        Self { text, done: false }
+    }
+}
+
+/// チェックリストの進み具合（完了した項目数, 全項目数）。項目がない場合は None
+pub fn progress(items: &[ChecklistItem]) -> Option<(usize, usize)> {
+    if items.is_empty() {
+        return None;
+    }
+    Some((items.iter().filter(|item| item.done).count(), items.len()))
+}
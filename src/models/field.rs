@@ -0,0 +1,216 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::utils::date::parse_date;
+
+/// タスク本体のフィールド名（大文字・小文字を問わずカスタムフィールド名として使用できない）
+const RESERVED_NAMES: &[&str] = &[
+    "id", "title", "description", "created", "created_at", "due", "due_date",
+    "completed", "completed_at", "priority", "tags", "estimate", "status",
+    "start", "start_date", "scheduled", "wait", "wait_until", "depends_on", "project",
+    "checklist", "notes", "attachments",
+];
+
+/// カスタムフィールドの型を表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    String,
+    Number,
+    Date,
+    Enum,
+    Url,
+}
+
+impl FieldType {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.to_lowercase().as_str() {
+            "string" => Ok(FieldType::String),
+            "number" => Ok(FieldType::Number),
+            "date" => Ok(FieldType::Date),
+            "enum" => Ok(FieldType::Enum),
+            "url" => Ok(FieldType::Url),
+            _ => Err(t!("field.invalid_type", value = value)),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FieldType::String => "string",
+            FieldType::Number => "number",
+            FieldType::Date => "date",
+            FieldType::Enum => "enum",
+            FieldType::Url => "url",
+        }
+    }
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// カスタムフィールドの定義を表す構造体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldDefinition {
+    pub name: String,
+    pub field_type: FieldType,
+    /// enum 型の選択肢
+    #[serde(default)]
+    pub values: Vec<String>,
+}
+
+impl FieldDefinition {
+    pub fn new(name: String, field_type: FieldType, values: Vec<String>) -> Result<Self, String> {
+        if name.is_empty()
+            || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(t!("field.invalid_name", name = name));
+        }
+        if RESERVED_NAMES.contains(&name.to_lowercase().as_str()) {
+            return Err(t!("field.reserved_name", name = name));
+        }
+        if field_type == FieldType::Enum && values.is_empty() {
+            return Err(t!("field.enum_without_values"));
+        }
+
+        Ok(Self { name, field_type, values })
+    }
+
+    /// 値を検証し、保存用に正規化した値を返す
+    pub fn validate(&self, value: &str) -> Result<String, String> {
+        let value = value.trim();
+        match self.field_type {
+            FieldType::String => Ok(value.to_string()),
+            FieldType::Number => value
+                .parse::<f64>()
+                .map(|_| value.to_string())
+                .map_err(|_| t!("field.not_a_number", name = self.name, value = value)),
+            FieldType::Date => parse_date(value)
+                .map(|_| value.to_string())
+                .map_err(|e| format!("{}: {}", self.name, e)),
+            FieldType::Enum => {
+                if self.values.iter().any(|v| v == value) {
+                    Ok(value.to_string())
+                } else {
+                    Err(t!(
+                        "field.not_in_values",
+                        name = self.name,
+                        value = value,
+                        values = self.values.join(", "),
+                    ))
+                }
+            }
+            FieldType::Url => {
+                if (value.starts_with("http://") || value.starts_with("https://"))
+                    && !value.contains(char::is_whitespace)
+                {
+                    Ok(value.to_string())
+                } else {
+                    Err(t!("field.invalid_url", name = self.name, value = value))
+                }
+            }
+        }
+    }
+
+    /// フィールドの型に従って 2 つの値を比較
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        match self.field_type {
+            FieldType::Number => {
+                let a = a.parse::<f64>().unwrap_or(f64::NAN);
+                let b = b.parse::<f64>().unwrap_or(f64::NAN);
+                a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+            }
+            FieldType::Enum => {
+                // 選択肢の定義順で比較
+                let pos = |v: &str| self.values.iter().position(|x| x == v);
+                pos(a).cmp(&pos(b))
+            }
+            FieldType::Date => match (parse_date(a), parse_date(b)) {
+                (Ok(a), Ok(b)) => a.cmp(&b),
+                _ => a.cmp(b),
+            },
+            FieldType::String | FieldType::Url => a.cmp(b),
+        }
+    }
+}
+
+/// カスタムフィールドの比較演算子
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldOperator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// カスタムフィールドによる絞り込み条件（例: "story_points>=3"）
+#[derive(Debug, Clone)]
+pub struct FieldCondition {
+    pub name: String,
+    pub operator: FieldOperator,
+    pub value: String,
+}
+
+impl FieldCondition {
+    pub fn parse(condition: &str) -> Result<Self, String> {
+        // 2 文字の演算子を先に判定する
+        const OPERATORS: &[(&str, FieldOperator)] = &[
+            ("!=", FieldOperator::Ne),
+            (">=", FieldOperator::Ge),
+            ("<=", FieldOperator::Le),
+            ("=", FieldOperator::Eq),
+            (">", FieldOperator::Gt),
+            ("<", FieldOperator::Lt),
+        ];
+
+        for (symbol, operator) in OPERATORS {
+            if let Some(pos) = condition.find(symbol) {
+                let name = condition[..pos].trim();
+                let value = condition[pos + symbol.len()..].trim();
+                if name.is_empty() {
+                    break;
+                }
+                return Ok(Self {
+                    name: name.to_string(),
+                    operator: *operator,
+                    value: value.to_string(),
+                });
+            }
+        }
+
+        Err(t!("field.invalid_condition", condition = condition))
+    }
+
+    /// 値が条件を満たすか判定（値が未設定の場合は != のみ真）
+    pub fn matches(&self, definition: &FieldDefinition, value: Option<&str>) -> bool {
+        let value = match value {
+            Some(v) => v,
+            None => return self.operator == FieldOperator::Ne,
+        };
+
+        let ordering = definition.compare(value, &self.value);
+        match self.operator {
+            FieldOperator::Eq => ordering == Ordering::Equal,
+            FieldOperator::Ne => ordering != Ordering::Equal,
+            FieldOperator::Lt => ordering == Ordering::Less,
+            FieldOperator::Le => ordering != Ordering::Greater,
+            FieldOperator::Gt => ordering == Ordering::Greater,
+            FieldOperator::Ge => ordering != Ordering::Less,
+        }
+    }
+}
+
+/// "KEY=VALUE" 形式の文字列を分解
+pub fn parse_assignment(assignment: &str) -> Result<(String, String), String> {
+    match assignment.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(t!("field.invalid_assignment", assignment = assignment)),
+    }
+}
@@ -1,2 +1,11 @@
 pub mod task;
-pub mod tag; 
+pub mod tag;
+pub mod checklist;
+pub mod note;
+pub mod attachment;
+pub mod field;
+pub mod time_entry;
+pub mod pomodoro;
+pub mod urgency;
+pub mod view;
+pub mod template; 
\ No newline at end of file
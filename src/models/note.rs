@@ -0,0 +1,24 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+/// タスクに書き足していくメモ（説明と違い、上書きせずに日時付きで残す）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Note {
+    pub created_at: DateTime<Local>,
+    pub text: String,
+}
+
+impl Note {
+    pub fn new(text: String) -> Self {
+        Self { created_at: Local::now(), text }
+    }
+}
+
+/// メモの全文検索の結果
+#[derive(Debug, Clone)]
+pub struct NoteMatch {
+    pub task_id: i64,
+    pub note: Note,
+    /// メモの一致した箇所の前後（長いメモは省略する）
+    pub snippet: String,
+}
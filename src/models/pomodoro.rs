@@ -0,0 +1,34 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+/// 完了したポモドーロ（集中して作業した 1 区間）を表す構造体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pomodoro {
+    pub id: Option<i64>,
+    pub task_id: i64,
+    pub started_at: DateTime<Local>,
+    pub ended_at: DateTime<Local>,
+    /// 作業時間の設定（分）
+    pub minutes: i64,
+    /// 作業中に記録した中断の回数
+    pub interruptions: i64,
+}
+
+impl Pomodoro {
+    pub fn new(
+        task_id: i64,
+        started_at: DateTime<Local>,
+        ended_at: DateTime<Local>,
+        minutes: i64,
+        interruptions: i64,
+    ) -> Self {
+        Self {
+            id: None,
+            task_id,
+            started_at,
+            ended_at,
+            minutes,
+            interruptions,
+        }
+    }
+}
@@ -12,4 +12,53 @@ impl Tag {
     pub fn new(name: String) -> Self {
         Self { id: None, name }
     }
-} 
+}
+
+/// タグの変更（すべて削除 → 追加 → 削除の順に適用）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagChanges {
+    pub clear: bool,
+    pub add: Vec<String>,
+    pub remove: Vec<String>,
+}
+
+impl TagChanges {
+    pub fn is_empty(&self) -> bool {
+        !self.clear && self.add.is_empty() && self.remove.is_empty()
+    }
+
+    /// タグの一覧に適用（追加したタグは末尾に並ぶ）
+    pub fn apply(&self, tags: &mut Vec<String>) {
+        if self.clear {
+            tags.clear();
+        }
+        for tag in &self.add {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+        tags.retain(|tag| !self.remove.contains(tag));
+    }
+}
+
+/// クイック入力の `+tag`（追加）と `-tag`（削除）の語を取り出し、残りの文字列と変更を返す
+///
+/// 記号の直後が文字でない語（`-` や `+1` など）はそのまま残す。
+pub fn parse_quick_tags(text: &str) -> (String, TagChanges) {
+    let mut changes = TagChanges::default();
+    let mut rest = Vec::new();
+    for word in text.split_whitespace() {
+        let mut chars = word.chars();
+        let sign = chars.next();
+        let is_tag = chars.next().is_some_and(char::is_alphabetic) && !word.contains(',');
+        match sign {
+            Some('+') if is_tag => changes.add.push(word[1..].to_string()),
+            Some('-') if is_tag => changes.remove.push(word[1..].to_string()),
+            _ => rest.push(word),
+        }
+    }
+    if changes.is_empty() {
+        return (text.to_string(), changes);
+    }
+    (rest.join(" "), changes)
+}
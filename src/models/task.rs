@@ -1,7 +1,12 @@
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Duration, Local};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 
+use crate::models::attachment::Attachment;
+use crate::models::checklist::ChecklistItem;
+use crate::models::note::Note;
+
 /// タスクの優先度を表す列挙型
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Priority {
@@ -16,22 +21,66 @@ impl Priority {
             1 => Ok(Priority::Low),
             2 => Ok(Priority::Medium),
             3 => Ok(Priority::High),
-            _ => Err(format!("不正な優先度: {}. 1, 2, 3 のいずれかを指定してください", value)),
+            _ => Err(t!("priority.invalid", value = value)),
+        }
+    }
+
+    /// 優先度の名前（高・中・低、high・medium・low、1〜3）を解析
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "高" | "high" | "3" => Some(Priority::High),
+            "中" | "medium" | "2" => Some(Priority::Medium),
+            "低" | "low" | "1" => Some(Priority::Low),
+            _ => None,
         }
     }
 
-    pub fn to_string(&self) -> String {
+    /// 言語によらない名前（設定ファイルのキーなどに使う）
+    pub fn key(&self) -> &'static str {
         match self {
-            Priority::Low => "低".to_string(),
-            Priority::Medium => "中".to_string(),
-            Priority::High => "高".to_string(),
+            Priority::Low => "low",
+            Priority::Medium => "medium",
+            Priority::High => "high",
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Priority::Low => t!("priority.low"),
+            Priority::Medium => t!("priority.medium"),
+            Priority::High => t!("priority.high"),
         }
     }
 }
 
 impl fmt::Display for Priority {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.to_string())
+        write!(f, "{}", self.label())
+    }
+}
+
+/// 新しいタスクのステータス
+pub const DEFAULT_STATUS: &str = "todo";
+
+fn default_status() -> String {
+    DEFAULT_STATUS.to_string()
+}
+
+/// プロジェクト名を解析（前後の空白を除き、空の場合はプロジェクトなし）
+pub fn parse_project(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// 見積もり時間の上限（分、100 年）。多くのタスクの見積もりを合計しても期間の範囲を超えない
+pub const MAX_ESTIMATE_MINUTES: i64 = 100 * 365 * 24 * 60;
+
+/// 見積もり時間（分）が 1 分から上限までの範囲にあるか検証
+pub fn validate_estimate(minutes: i64) -> Result<i64, String> {
+    if (1..=MAX_ESTIMATE_MINUTES).contains(&minutes) {
+        Ok(minutes)
+    } else {
+        Err(t!("task.estimate_out_of_range", minutes = minutes))
     }
 }
 
@@ -43,10 +92,40 @@ pub struct Task {
     pub description: Option<String>,
     pub created_at: DateTime<Local>,
     pub due_date: Option<DateTime<Local>>,
+    /// 作業を開始する予定の日時（scheduled）
+    #[serde(default, alias = "scheduled")]
+    pub start_date: Option<DateTime<Local>>,
+    /// この日時までは一覧などに表示しない（wait）
+    #[serde(default)]
+    pub wait_until: Option<DateTime<Local>>,
     pub completed: bool,
     pub completed_at: Option<DateTime<Local>>,
     pub priority: Priority,
     pub tags: Vec<String>,
+    /// ボードの列に対応するステータス（例: "todo", "doing"）
+    #[serde(default = "default_status")]
+    pub status: String,
+    /// このタスクより先に完了する必要があるタスクのID
+    #[serde(default)]
+    pub depends_on: Vec<i64>,
+    /// 見積もり時間（分）
+    #[serde(default)]
+    pub estimate_minutes: Option<i64>,
+    /// 所属するプロジェクト
+    #[serde(default)]
+    pub project: Option<String>,
+    /// カスタムフィールドの値（フィールド名 → 値）
+    #[serde(default)]
+    pub custom_fields: BTreeMap<String, String>,
+    /// チェックリストの項目（表示する順）
+    #[serde(default)]
+    pub checklist: Vec<ChecklistItem>,
+    /// メモ（古い順。追加は `TaskRepository::add_note` で行い、`update_task` では変更しない）
+    #[serde(default)]
+    pub notes: Vec<Note>,
+    /// 関連付けた URL・ファイル（追加順。追加は `TaskRepository::add_attachment` で行い、`update_task` では変更しない）
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
 }
 
 impl Task {
@@ -63,10 +142,20 @@ impl Task {
             description,
             created_at: Local::now(),
             due_date,
+            start_date: None,
+            wait_until: None,
             completed: false,
             completed_at: None,
             priority,
             tags,
+            status: default_status(),
+            depends_on: Vec::new(),
+            estimate_minutes: None,
+            project: None,
+            custom_fields: BTreeMap::new(),
+            checklist: Vec::new(),
+            notes: Vec::new(),
+            attachments: Vec::new(),
         }
     }
 
@@ -101,6 +190,27 @@ impl Task {
         }
     }
 
+    /// 見積もり時間（表せる期間の範囲を超える値は None）
+    pub fn estimate(&self) -> Option<Duration> {
+        self.estimate_minutes.and_then(Duration::try_minutes)
+    }
+
+    /// タイトル・説明に語が含まれるか（term は小文字に変換済み。メモは全文検索の索引で探す）
+    pub fn matches_text(&self, term: &str) -> bool {
+        self.title.to_lowercase().contains(term)
+            || self.description.as_deref().is_some_and(|d| d.to_lowercase().contains(term))
+    }
+
+    /// 待機中（wait の日時より前）か
+    pub fn is_waiting(&self) -> bool {
+        !self.completed && self.wait_until.is_some_and(|wait| wait > Local::now())
+    }
+
+    /// 開始日が期限より後になっているか
+    pub fn starts_after_due(&self) -> bool {
+        matches!((self.start_date, self.due_date), (Some(start), Some(due)) if start > due)
+    }
+
     #[allow(dead_code)]
     pub fn has_tag(&self, tag: &str) -> bool {
         self.tags.iter().any(|t| t == tag)
@@ -110,10 +220,11 @@ impl Task {
     pub fn format_due_date(&self) -> String {
         match self.due_date {
             Some(date) => date.format("%Y-%m-%d %H:%M").to_string(),
-            None => "なし".to_string(),
+            None => t!("task.no_due"),
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn from_row(
         id: i64,
         title: String,
@@ -127,7 +238,7 @@ impl Task {
     ) -> Result<Self, String> {
         // Unix タイムスタンプから DateTime<Local> に変換
         let created_at_dt = DateTime::from_timestamp(created_at, 0)
-            .ok_or_else(|| format!("不正な作成日時: {}", created_at))?;
+            .ok_or_else(|| t!("task.invalid_created_at", timestamp = created_at))?;
         let created_at_local = created_at_dt.with_timezone(&Local);
 
         // due_date と completed_at も同様に変換
@@ -147,7 +258,7 @@ impl Task {
         // タグの変換
         let tags = tags_str
             .map(|s| s.split(',').map(|t| t.trim().to_string()).collect())
-            .unwrap_or_else(Vec::new);
+            .unwrap_or_default();
 
         Ok(Self {
             id: Some(id),
@@ -155,10 +266,20 @@ impl Task {
             description,
             created_at: created_at_local,
             due_date: due_date_local,
+            start_date: None,
+            wait_until: None,
             completed,
             completed_at: completed_at_local,
             priority,
             tags,
+            status: default_status(),
+            depends_on: Vec::new(),
+            estimate_minutes: None,
+            project: None,
+            custom_fields: BTreeMap::new(),
+            checklist: Vec::new(),
+            notes: Vec::new(),
+            attachments: Vec::new(),
         })
     }
 } 
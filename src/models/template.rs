@@ -0,0 +1,137 @@
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::models::checklist::ChecklistItem;
+use crate::models::task::{Priority, Task};
+
+/// 繰り返し作成するタスクのまとまり（先頭が基準のタスク、続くタスクは依存先）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskTemplate {
+    pub name: String,
+    pub tasks: Vec<TemplateTask>,
+}
+
+/// テンプレート内のタスク
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateTask {
+    pub title: String,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub project: Option<String>,
+    pub priority: Priority,
+    /// 基準の期限からの差（分）
+    pub due_offset_minutes: Option<i64>,
+    /// 先に完了する必要があるタスクのテンプレート内の位置
+    pub depends_on: Vec<usize>,
+    /// チェックリストの項目（作成時はすべて未完了）
+    #[serde(default)]
+    pub checklist: Vec<String>,
+}
+
+impl TaskTemplate {
+    /// 基準のタスクとその依存先（依存先の依存先も含む）からテンプレートを作成
+    ///
+    /// 期限は基準のタスクの期限（ない場合は最も遅い期限）からの差として記録する。
+    pub fn capture(name: String, root: &Task, dependencies: &HashMap<i64, Task>) -> Self {
+        // 基準のタスクから依存先を幅優先で辿る
+        let mut order = vec![root.clone()];
+        let mut index = 0;
+        while index < order.len() {
+            for id in order[index].depends_on.clone() {
+                if order.iter().all(|task| task.id != Some(id)) {
+                    if let Some(task) = dependencies.get(&id) {
+                        order.push(task.clone());
+                    }
+                }
+            }
+            index += 1;
+        }
+
+        let anchor = root.due_date.or_else(|| order.iter().filter_map(|task| task.due_date).max());
+        let tasks = order
+            .iter()
+            .map(|task| TemplateTask {
+                title: task.title.clone(),
+                description: task.description.clone(),
+                tags: task.tags.clone(),
+                project: task.project.clone(),
+                priority: task.priority,
+                due_offset_minutes: task.due_date.zip(anchor).map(|(due, anchor)| (due - anchor).num_minutes()),
+                depends_on: task
+                    .depends_on
+                    .iter()
+                    .filter_map(|id| order.iter().position(|t| t.id == Some(*id)))
+                    .collect(),
+                checklist: task.checklist.iter().map(|item| item.text.clone()).collect(),
+            })
+            .collect();
+        Self { name, tasks }
+    }
+
+    /// 変数を置き換えたタスクを作成（依存関係は設定しない。due は基準の期限）
+    pub fn instantiate(&self, due: Option<DateTime<Local>>, vars: &HashMap<String, String>) -> Result<Vec<Task>, String> {
+        self.tasks
+            .iter()
+            .map(|template| {
+                let title = substitute(&template.title, vars)?;
+                let description = template.description.as_deref().map(|d| substitute(d, vars)).transpose()?;
+                let due_date = due.zip(template.due_offset_minutes).map(|(due, offset)| due + Duration::minutes(offset));
+                let mut task = Task::new(title, description, due_date, template.priority, template.tags.clone());
+                task.project = template.project.clone();
+                task.checklist = template
+                    .checklist
+                    .iter()
+                    .map(|text| substitute(text, vars).map(ChecklistItem::new))
+                    .collect::<Result<_, _>>()?;
+                Ok(task)
+            })
+            .collect()
+    }
+
+    /// タイトル・説明・チェックリストで使われている変数名（重複なし、出現順）
+    pub fn variables(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for text in self.tasks.iter().flat_map(|t| std::iter::once(&t.title).chain(&t.description).chain(&t.checklist)) {
+            for name in placeholders(text) {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        names
+    }
+}
+
+/// `{{name}}` の変数名（前後の空白は除く）
+fn placeholders(text: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        names.push(rest[start + 2..start + 2 + end].trim().to_string());
+        rest = &rest[start + 2 + end + 2..];
+    }
+    names
+}
+
+/// `{{name}}` を変数の値に置き換える（未定義の変数はエラー、閉じていない `{{` はそのまま）
+pub fn substitute(text: &str, vars: &HashMap<String, String>) -> Result<String, String> {
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let name = rest[start + 2..start + 2 + end].trim();
+        let value = vars.get(name).ok_or_else(|| t!("task_template.undefined_var", name = name))?;
+        result.push_str(&rest[..start]);
+        result.push_str(value);
+        rest = &rest[start + 2 + end + 2..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
@@ -0,0 +1,44 @@
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+
+/// タスクの作業時間の記録を表す構造体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeEntry {
+    pub id: Option<i64>,
+    pub task_id: i64,
+    pub started_at: DateTime<Local>,
+    /// 実行中のタイマーの場合は None
+    pub ended_at: Option<DateTime<Local>>,
+}
+
+impl TimeEntry {
+    pub fn new(task_id: i64, started_at: DateTime<Local>, ended_at: Option<DateTime<Local>>) -> Self {
+        Self {
+            id: None,
+            task_id,
+            started_at,
+            ended_at,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.ended_at.is_none()
+    }
+
+    /// 記録された時間（実行中の場合は現在までの経過時間）
+    pub fn duration(&self) -> Duration {
+        let end = self.ended_at.unwrap_or_else(Local::now);
+        end.signed_duration_since(self.started_at)
+    }
+
+    /// 指定した期間と重なる部分の時間
+    pub fn duration_within(&self, from: DateTime<Local>, to: DateTime<Local>) -> Duration {
+        let start = self.started_at.max(from);
+        let end = self.ended_at.unwrap_or_else(Local::now).min(to);
+        if end > start {
+            end.signed_duration_since(start)
+        } else {
+            Duration::zero()
+        }
+    }
+}
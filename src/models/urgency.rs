@@ -0,0 +1,125 @@
+use chrono::{DateTime, Local};
+use std::collections::HashSet;
+
+use crate::config::UrgencyConfig;
+use crate::models::task::{Priority, Task};
+
+/// 期限による値が最小になるまでの日数
+const DUE_HORIZON_DAYS: f64 = 14.0;
+/// 期限超過の値を加算する最大日数
+const OVERDUE_MAX_DAYS: f64 = 14.0;
+/// 経過日数による値が最大になるまでの日数
+const AGE_MAX_DAYS: f64 = 365.0;
+
+/// 緊急度の内訳の 1 項目
+#[derive(Debug, Clone, PartialEq)]
+pub struct UrgencyComponent {
+    pub label: String,
+    pub value: f64,
+}
+
+/// タスクの緊急度（内訳付き）
+#[derive(Debug, Clone, PartialEq)]
+pub struct Urgency {
+    pub components: Vec<UrgencyComponent>,
+    pub total: f64,
+}
+
+impl Urgency {
+    /// 内訳を "優先度 6.0 + 期限 9.1 - ブロック中 5.0" の形式で表す
+    pub fn explain(&self) -> String {
+        let mut result = String::new();
+        for (i, component) in self.components.iter().enumerate() {
+            let sign = if component.value < 0.0 { "-" } else { "+" };
+            if i == 0 {
+                if component.value < 0.0 {
+                    result.push('-');
+                }
+            } else {
+                result.push_str(&format!(" {} ", sign));
+            }
+            result.push_str(&format!("{} {:.1}", component.label, component.value.abs()));
+        }
+        result
+    }
+}
+
+/// 緊急度を計算するための構造体（ブロック中のタスクを把握している）
+pub struct UrgencyScorer {
+    config: UrgencyConfig,
+    blocked: HashSet<i64>,
+    now: DateTime<Local>,
+}
+
+impl UrgencyScorer {
+    pub fn new(config: UrgencyConfig, blocked: HashSet<i64>) -> Self {
+        Self {
+            config,
+            blocked,
+            now: Local::now(),
+        }
+    }
+
+    /// 未完了の依存タスクがあるか
+    pub fn is_blocked(&self, task: &Task) -> bool {
+        task.id.is_some_and(|id| self.blocked.contains(&id))
+    }
+
+    /// タスクの緊急度を計算
+    pub fn score(&self, task: &Task) -> Urgency {
+        let mut components = Vec::new();
+        let mut push = |label: String, value: f64| {
+            if value.abs() >= 0.05 {
+                components.push(UrgencyComponent { label, value });
+            }
+        };
+
+        // 優先度（高: 1.0, 中: 0.65, 低: 0.3 の割合）
+        let priority_factor = match task.priority {
+            Priority::High => 1.0,
+            Priority::Medium => 0.65,
+            Priority::Low => 0.3,
+        };
+        push(t!("urgency.priority"), self.config.priority * priority_factor);
+
+        // 期限までの近さ（期限を過ぎていれば 1.0、14 日以上先なら 0.2）
+        if let Some(due) = task.due_date {
+            let days_until = due.signed_duration_since(self.now).num_seconds() as f64 / 86400.0;
+            let due_factor = if days_until <= 0.0 {
+                1.0
+            } else if days_until >= DUE_HORIZON_DAYS {
+                0.2
+            } else {
+                1.0 - 0.8 * (days_until / DUE_HORIZON_DAYS)
+            };
+            push(t!("urgency.due"), self.config.due * due_factor);
+
+            if days_until < 0.0 {
+                let overdue_days = (-days_until).min(OVERDUE_MAX_DAYS);
+                push(t!("urgency.overdue"), self.config.overdue * overdue_days);
+            }
+        }
+
+        // 作成からの経過日数
+        let age_days = self.now.signed_duration_since(task.created_at).num_seconds() as f64 / 86400.0;
+        push(
+            t!("urgency.age"),
+            self.config.age * (age_days.max(0.0) / AGE_MAX_DAYS).min(1.0),
+        );
+
+        // ブロック中
+        if self.is_blocked(task) {
+            push(t!("urgency.blocked"), self.config.blocked);
+        }
+
+        // タグ
+        for tag in &task.tags {
+            if let Some(weight) = self.config.tags.get(tag) {
+                push(format!("#{}", tag), *weight);
+            }
+        }
+
+        let total = components.iter().map(|c| c.value).sum();
+        Urgency { components, total }
+    }
+}
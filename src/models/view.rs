@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// 名前を付けて保存した一覧の表示条件（`list @名前` で呼び出す）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct View {
+    pub name: String,
+    /// --where と同じ形式の絞り込みの条件
+    #[serde(default)]
+    pub filter: Option<String>,
+    #[serde(default)]
+    pub sort: Option<String>,
+    #[serde(default)]
+    pub format: Option<String>,
+    #[serde(default)]
+    pub columns: Option<String>,
+    /// 完了済みタスクも含める
+    #[serde(default)]
+    pub all: bool,
+}
+
+impl View {
+    /// 名前の検証（空白・カンマ・先頭の @ は不可）
+    pub fn validate_name(name: &str) -> Result<(), String> {
+        if name.is_empty() || name.starts_with('@') || name.contains(|c: char| c.is_whitespace() || c == ',') {
+            return Err(t!("view.invalid_name", name = name));
+        }
+        Ok(())
+    }
+}
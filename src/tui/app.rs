@@ -0,0 +1,497 @@
+use std::collections::HashSet;
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::widgets::ListState;
+
+use crate::db::repository::DeletedTask;
+use crate::db::TaskRepository;
+use crate::error::{Result, TaskError};
+use crate::models::tag::{parse_quick_tags, TagChanges};
+use crate::models::task::{Priority, Task};
+use crate::utils::date::parse_date;
+
+/// 入力欄の用途
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    /// 新しいタスクのタイトル
+    Add,
+    /// 選択中のタスクのタイトル
+    Title,
+    /// 選択中のタスクの期限（空で削除）
+    Due,
+}
+
+/// 画面の操作モード
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mode {
+    Normal,
+    /// 検索バーに入力中
+    Filter,
+    Input { kind: InputKind, buffer: String },
+    /// タグの選択（既存のタグを選ぶか、新しいタグを入力）
+    TagPicker { options: Vec<(String, bool)>, cursor: usize, buffer: String },
+    PriorityPicker { cursor: usize },
+    /// 削除の確認
+    ConfirmDelete,
+}
+
+/// 取り消しのための操作の記録
+#[derive(Debug, Clone)]
+enum UndoAction {
+    /// 追加したタスク（取り消しで削除）
+    Added(i64),
+    /// 変更前のタスク
+    Updated(Task),
+    /// 削除したタスク（取り消しで同じIDのタスクとして戻す）
+    Deleted(DeletedTask),
+}
+
+/// 優先度の選択肢（上から順に表示）
+pub const PRIORITY_CHOICES: [Priority; 3] = [Priority::High, Priority::Medium, Priority::Low];
+
+/// TUI の状態
+///
+/// キー操作は `handle_key` で受け取り、データの変更はすべて `TaskRepository` を通して保存する。
+/// 描画は `tui::draw` が行うため、端末がなくても状態の変化を確認できる。
+pub struct App {
+    repo: TaskRepository,
+    /// 読み込んだタスク（完了済みを表示しない場合は未完了のみ）
+    tasks: Vec<Task>,
+    /// 検索に一致したタスクの tasks 内の位置
+    visible: Vec<usize>,
+    pub list_state: ListState,
+    pub filter: String,
+    pub show_completed: bool,
+    pub mode: Mode,
+    /// 画面下部に表示するメッセージ（エラーの場合は true）
+    pub message: Option<(String, bool)>,
+    undo_stack: Vec<UndoAction>,
+    quit: bool,
+}
+
+impl App {
+    pub fn new(repo: TaskRepository) -> Result<Self> {
+        let mut app = Self {
+            repo,
+            tasks: Vec::new(),
+            visible: Vec::new(),
+            list_state: ListState::default(),
+            filter: String::new(),
+            show_completed: false,
+            mode: Mode::Normal,
+            message: None,
+            undo_stack: Vec::new(),
+            quit: false,
+        };
+        app.reload()?;
+        Ok(app)
+    }
+
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// 検索に一致したタスク
+    pub fn visible_tasks(&self) -> impl Iterator<Item = &Task> {
+        self.visible.iter().map(|&i| &self.tasks[i])
+    }
+
+    /// 選択中のタスク
+    pub fn selected_task(&self) -> Option<&Task> {
+        self.list_state
+            .selected()
+            .and_then(|i| self.visible.get(i))
+            .map(|&i| &self.tasks[i])
+    }
+
+    /// タスクを読み込み直す（選択中のタスクはできるだけ維持する）
+    fn reload(&mut self) -> Result<()> {
+        let selected_id = self.selected_task().and_then(|t| t.id);
+        self.tasks = self.repo.get_all_tasks(self.show_completed)?;
+        self.tasks.sort_by_key(|t| (t.completed, std::cmp::Reverse(t.priority as i32), t.id));
+        self.apply_filter(selected_id);
+        Ok(())
+    }
+
+    /// 検索条件を適用（"#タグ" はタグ、それ以外はタイトル・説明に含まれる語か、メモの全文検索の語）
+    fn apply_filter(&mut self, selected_id: Option<i64>) {
+        let terms: Vec<(String, HashSet<i64>)> = self
+            .filter
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .map(|term| {
+                // 検索できない場合はメモを照合しない
+                let notes = if term.starts_with('#') {
+                    HashSet::new()
+                } else {
+                    self.repo.search_note_task_ids(&term).unwrap_or_default()
+                };
+                (term, notes)
+            })
+            .collect();
+        self.visible = self
+            .tasks
+            .iter()
+            .enumerate()
+            .filter(|(_, task)| terms.iter().all(|(term, notes)| matches_term(task, term, notes)))
+            .map(|(i, _)| i)
+            .collect();
+
+        let position = selected_id
+            .and_then(|id| self.visible.iter().position(|&i| self.tasks[i].id == Some(id)))
+            .or_else(|| self.list_state.selected().map(|i| i.min(self.visible.len().saturating_sub(1))))
+            .or(Some(0));
+        self.list_state.select(position.filter(|_| !self.visible.is_empty()));
+    }
+
+    /// キー入力を処理する（エラーはメッセージとして表示）
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.quit = true;
+            return;
+        }
+
+        let result = match self.mode {
+            Mode::Normal => self.handle_normal(key),
+            Mode::Filter => {
+                self.handle_filter(key);
+                Ok(())
+            }
+            Mode::Input { .. } => self.handle_input(key),
+            Mode::TagPicker { .. } => self.handle_tag_picker(key),
+            Mode::PriorityPicker { .. } => self.handle_priority_picker(key),
+            Mode::ConfirmDelete => self.handle_confirm_delete(key),
+        };
+        if let Err(e) = result {
+            self.message = Some((e.to_string(), true));
+        }
+    }
+
+    fn handle_normal(&mut self, key: KeyEvent) -> Result<()> {
+        self.message = None;
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Home | KeyCode::Char('g') => self.list_state.select_first(),
+            KeyCode::End | KeyCode::Char('G') => {
+                self.list_state.select(self.visible.len().checked_sub(1));
+            }
+            KeyCode::Char('/') => self.mode = Mode::Filter,
+            KeyCode::Char('a') => {
+                self.mode = Mode::Input { kind: InputKind::Add, buffer: String::new() };
+            }
+            KeyCode::Char('e') => {
+                if let Some(task) = self.selected_task() {
+                    let buffer = task.title.clone();
+                    self.mode = Mode::Input { kind: InputKind::Title, buffer };
+                }
+            }
+            KeyCode::Char('d') => {
+                if let Some(task) = self.selected_task() {
+                    let buffer = task
+                        .due_date
+                        .map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+                        .unwrap_or_default();
+                    self.mode = Mode::Input { kind: InputKind::Due, buffer };
+                }
+            }
+            KeyCode::Char(' ') | KeyCode::Char('x') => self.toggle_complete()?,
+            KeyCode::Delete | KeyCode::Char('D') if self.selected_task().is_some() => {
+                self.mode = Mode::ConfirmDelete;
+            }
+            KeyCode::Char('t') => self.open_tag_picker()?,
+            KeyCode::Char('p') => {
+                if let Some(task) = self.selected_task() {
+                    let cursor = PRIORITY_CHOICES.iter().position(|p| *p == task.priority).unwrap_or(1);
+                    self.mode = Mode::PriorityPicker { cursor };
+                }
+            }
+            KeyCode::Char('z') => {
+                self.show_completed = !self.show_completed;
+                self.reload()?;
+            }
+            KeyCode::Char('u') => self.undo()?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0) as isize;
+        let next = (current + delta).clamp(0, self.visible.len() as isize - 1);
+        self.list_state.select(Some(next as usize));
+    }
+
+    /// 検索バーの入力（入力のたびに一覧を絞り込む）
+    fn handle_filter(&mut self, key: KeyEvent) {
+        let selected_id = self.selected_task().and_then(|t| t.id);
+        match key.code {
+            KeyCode::Enter => self.mode = Mode::Normal,
+            KeyCode::Esc => {
+                self.filter.clear();
+                self.mode = Mode::Normal;
+            }
+            KeyCode::Backspace => {
+                self.filter.pop();
+            }
+            KeyCode::Char(c) => self.filter.push(c),
+            _ => return,
+        }
+        self.apply_filter(selected_id);
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Result<()> {
+        let Mode::Input { kind, buffer } = &mut self.mode else {
+            return Ok(());
+        };
+        match key.code {
+            KeyCode::Esc => self.mode = Mode::Normal,
+            KeyCode::Backspace => {
+                buffer.pop();
+            }
+            KeyCode::Char(c) => buffer.push(c),
+            KeyCode::Enter => {
+                let kind = *kind;
+                let value = buffer.trim().to_string();
+                self.mode = Mode::Normal;
+                self.submit_input(kind, value)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn submit_input(&mut self, kind: InputKind, value: String) -> Result<()> {
+        match kind {
+            InputKind::Add => {
+                if value.is_empty() {
+                    return Ok(());
+                }
+                // +tag でタグを付ける
+                let (title, tag_changes) = parse_quick_tags(&value);
+                if title.is_empty() {
+                    return Err(TaskError::InvalidArgument(t!("tui.empty_title")));
+                }
+                let mut tags = Vec::new();
+                tag_changes.apply(&mut tags);
+                let task = Task::new(title, None, None, Priority::Medium, tags);
+                let id = self.repo.add_task(&task)?;
+                self.undo_stack.push(UndoAction::Added(id));
+                self.reload()?;
+                // 追加したタスクを選択（検索に一致しない場合はそのまま）
+                if let Some(position) = self.visible.iter().position(|&i| self.tasks[i].id == Some(id)) {
+                    self.list_state.select(Some(position));
+                }
+                self.message = Some((t!("tui.added", id = id), false));
+            }
+            InputKind::Title => {
+                // +tag / -tag でタグを追加・削除（タグだけの入力ではタイトルを変えない）
+                let (title, tag_changes) = parse_quick_tags(&value);
+                if title.is_empty() && tag_changes.is_empty() {
+                    return Err(TaskError::InvalidArgument(t!("tui.empty_title")));
+                }
+                if title.is_empty() {
+                    self.update_selected_tags(&tag_changes)?;
+                } else {
+                    self.update_selected(|task| {
+                        task.title = title;
+                        tag_changes.apply(&mut task.tags);
+                        Ok(())
+                    })?;
+                }
+            }
+            InputKind::Due => {
+                let due = if value.is_empty() { None } else { Some(parse_date(&value)?) };
+                self.update_selected(|task| {
+                    task.due_date = due;
+                    Ok(())
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 選択中のタスクを変更して保存（変更前の内容を取り消し用に記録）
+    fn update_selected(&mut self, change: impl FnOnce(&mut Task) -> Result<()>) -> Result<()> {
+        let Some(previous) = self.selected_task().cloned() else {
+            return Ok(());
+        };
+        let mut task = previous.clone();
+        change(&mut task)?;
+        self.repo.update_task(&task)?;
+        self.undo_stack.push(UndoAction::Updated(previous));
+        self.reload()
+    }
+
+    /// 選択中のタスクのタグだけを変更して保存
+    fn update_selected_tags(&mut self, changes: &TagChanges) -> Result<()> {
+        let Some(previous) = self.selected_task().cloned() else {
+            return Ok(());
+        };
+        let Some(id) = previous.id else {
+            return Ok(());
+        };
+        self.repo.update_tags(id, changes)?;
+        self.undo_stack.push(UndoAction::Updated(previous));
+        self.reload()
+    }
+
+    fn toggle_complete(&mut self) -> Result<()> {
+        self.update_selected(|task| {
+            if task.completed {
+                task.uncomplete();
+            } else {
+                task.complete();
+            }
+            Ok(())
+        })
+    }
+
+    fn handle_confirm_delete(&mut self, key: KeyEvent) -> Result<()> {
+        self.mode = Mode::Normal;
+        if !matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+            return Ok(());
+        }
+        let Some(id) = self.selected_task().and_then(|task| task.id) else {
+            return Ok(());
+        };
+        let deleted = self.repo.delete_task_restorable(id)?;
+        self.undo_stack.push(UndoAction::Deleted(deleted));
+        self.reload()?;
+        self.message = Some((t!("tui.deleted", id = id), false));
+        Ok(())
+    }
+
+    fn open_tag_picker(&mut self) -> Result<()> {
+        let Some(task) = self.selected_task() else {
+            return Ok(());
+        };
+        let mut names: Vec<String> = self.repo.get_all_tags()?.into_iter().map(|t| t.name).collect();
+        names.extend(task.tags.iter().cloned());
+        names.sort();
+        names.dedup();
+        let options = names
+            .into_iter()
+            .map(|name| {
+                let checked = task.tags.contains(&name);
+                (name, checked)
+            })
+            .collect();
+        self.mode = Mode::TagPicker { options, cursor: 0, buffer: String::new() };
+        Ok(())
+    }
+
+    fn handle_tag_picker(&mut self, key: KeyEvent) -> Result<()> {
+        let Mode::TagPicker { options, cursor, buffer } = &mut self.mode else {
+            return Ok(());
+        };
+        match key.code {
+            KeyCode::Esc => self.mode = Mode::Normal,
+            KeyCode::Down => *cursor = (*cursor + 1).min(options.len().saturating_sub(1)),
+            KeyCode::Up => *cursor = cursor.saturating_sub(1),
+            KeyCode::Char(' ') if buffer.is_empty() => {
+                if let Some(option) = options.get_mut(*cursor) {
+                    option.1 = !option.1;
+                }
+            }
+            KeyCode::Char(c) if !c.is_whitespace() && c != ',' => buffer.push(c),
+            KeyCode::Backspace => {
+                buffer.pop();
+            }
+            // 入力中の新しいタグを追加
+            KeyCode::Enter if !buffer.is_empty() => {
+                let name = std::mem::take(buffer);
+                match options.iter().position(|(n, _)| *n == name) {
+                    Some(position) => {
+                        options[position].1 = true;
+                        *cursor = position;
+                    }
+                    None => {
+                        options.push((name, true));
+                        *cursor = options.len() - 1;
+                    }
+                }
+            }
+            KeyCode::Enter => {
+                let options = std::mem::take(options);
+                self.mode = Mode::Normal;
+                let current = self.selected_task().map(|task| task.tags.clone()).unwrap_or_default();
+                let changes = TagChanges {
+                    clear: false,
+                    add: options.iter().filter(|(n, checked)| *checked && !current.contains(n)).map(|(n, _)| n.clone()).collect(),
+                    remove: options.iter().filter(|(n, checked)| !*checked && current.contains(n)).map(|(n, _)| n.clone()).collect(),
+                };
+                if !changes.is_empty() {
+                    self.update_selected_tags(&changes)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_priority_picker(&mut self, key: KeyEvent) -> Result<()> {
+        let Mode::PriorityPicker { cursor } = &mut self.mode else {
+            return Ok(());
+        };
+        let chosen = match key.code {
+            KeyCode::Esc => {
+                self.mode = Mode::Normal;
+                return Ok(());
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                *cursor = (*cursor + 1).min(PRIORITY_CHOICES.len() - 1);
+                return Ok(());
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                *cursor = cursor.saturating_sub(1);
+                return Ok(());
+            }
+            KeyCode::Char(c @ '1'..='3') => Priority::from_i32(c as i32 - '0' as i32).map_err(TaskError::InvalidArgument)?,
+            KeyCode::Enter => PRIORITY_CHOICES[*cursor],
+            _ => return Ok(()),
+        };
+        self.mode = Mode::Normal;
+        self.update_selected(|task| {
+            task.priority = chosen;
+            Ok(())
+        })
+    }
+
+    /// 直前の変更を取り消す
+    fn undo(&mut self) -> Result<()> {
+        let Some(action) = self.undo_stack.pop() else {
+            self.message = Some((t!("tui.nothing_to_undo"), false));
+            return Ok(());
+        };
+        let message = match action {
+            UndoAction::Added(id) => {
+                self.repo.delete_task(id)?;
+                t!("tui.undo_add", id = id)
+            }
+            UndoAction::Updated(task) => {
+                self.repo.update_task(&task)?;
+                t!("tui.undo_update", id = task.id.unwrap_or(0))
+            }
+            UndoAction::Deleted(deleted) => {
+                self.repo.restore_task(&deleted)?;
+                t!("tui.undo_delete", id = deleted.task.id.unwrap_or(0))
+            }
+        };
+        self.reload()?;
+        self.message = Some((message, false));
+        Ok(())
+    }
+}
+
+/// 検索語に一致するか（小文字に変換済みの語。notes はメモが一致したタスクのID）
+fn matches_term(task: &Task, term: &str, notes: &HashSet<i64>) -> bool {
+    match term.strip_prefix('#') {
+        Some(tag) => task.tags.iter().any(|t| t.to_lowercase().contains(tag)),
+        None => task.matches_text(term) || task.id.is_some_and(|id| notes.contains(&id)),
+    }
+}
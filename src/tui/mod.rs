@@ -0,0 +1,10 @@
+//! 全画面の対話的なインターフェース
+//!
+//! 状態とキー操作は `App`、描画は `draw` が担当する。描画先は ratatui の `Terminal` なので、
+//! `TestBackend` を使えば端末なしでも画面の内容を確認できる。
+
+mod app;
+mod ui;
+
+pub use app::App;
+pub use ui::draw;
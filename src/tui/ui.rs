@@ -0,0 +1,328 @@
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap};
+use ratatui::Frame;
+
+use super::app::{App, InputKind, Mode, PRIORITY_CHOICES};
+use crate::models::checklist;
+use crate::models::task::{Priority, Task};
+use crate::utils::date::format_datetime;
+use crate::utils::duration::format_duration;
+use crate::utils::theme::{self, glyph, Glyph, Role};
+
+/// 画面全体を描画（上から検索バー、一覧と詳細、操作の説明）
+pub fn draw(frame: &mut Frame, app: &mut App) {
+    let [filter_area, main_area, status_area] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(3),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [list_area, detail_area] =
+        Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(main_area);
+
+    draw_filter(frame, app, filter_area);
+    draw_list(frame, app, list_area);
+    draw_detail(frame, app, detail_area);
+    draw_status(frame, app, status_area);
+
+    match &app.mode {
+        Mode::Input { kind, buffer } => {
+            let title = match kind {
+                InputKind::Add => t!("tui.input_add"),
+                InputKind::Title => t!("tui.input_title"),
+                InputKind::Due => t!("tui.input_due"),
+            };
+            let area = popup_area(frame.area(), 60, 3);
+            frame.render_widget(Clear, area);
+            frame.render_widget(
+                Paragraph::new(format!("{}_", buffer)).block(Block::default().borders(Borders::ALL).title(title)),
+                area,
+            );
+        }
+        Mode::TagPicker { options, cursor, buffer } => {
+            let mut lines: Vec<Line> = options
+                .iter()
+                .enumerate()
+                .map(|(i, (name, checked))| {
+                    let mark = if *checked { glyph(Glyph::Done) } else { glyph(Glyph::Pending) };
+                    let line = Line::from(format!("{} #{}", mark, name));
+                    if i == *cursor {
+                        line.style(Style::default().add_modifier(Modifier::REVERSED))
+                    } else {
+                        line
+                    }
+                })
+                .collect();
+            lines.push(Line::from(format!("{} {}_", t!("tui.new_tag"), buffer)));
+            let area = popup_area(frame.area(), 50, lines.len() as u16 + 2);
+            frame.render_widget(Clear, area);
+            frame.render_widget(
+                Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(t!("tui.tag_picker"))),
+                area,
+            );
+        }
+        Mode::PriorityPicker { cursor } => {
+            let lines: Vec<Line> = PRIORITY_CHOICES
+                .iter()
+                .enumerate()
+                .map(|(i, priority)| {
+                    let style = priority_style(*priority);
+                    let style = if i == *cursor { style.add_modifier(Modifier::REVERSED) } else { style };
+                    Line::styled(format!("{} {}", *priority as i32, priority.label()), style)
+                })
+                .collect();
+            let area = popup_area(frame.area(), 30, lines.len() as u16 + 2);
+            frame.render_widget(Clear, area);
+            frame.render_widget(
+                Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(t!("tui.priority_picker"))),
+                area,
+            );
+        }
+        Mode::ConfirmDelete => {
+            let title = app.selected_task().map(|t| t.title.clone()).unwrap_or_default();
+            let area = popup_area(frame.area(), 60, 3);
+            frame.render_widget(Clear, area);
+            frame.render_widget(
+                Paragraph::new(t!("tui.confirm_delete", title = title))
+                    .style(role_style(Role::Warning))
+                    .block(Block::default().borders(Borders::ALL)),
+                area,
+            );
+        }
+        Mode::Normal | Mode::Filter => {}
+    }
+}
+
+fn draw_filter(frame: &mut Frame, app: &App, area: Rect) {
+    let editing = app.mode == Mode::Filter;
+    let text = if editing { format!("{}_", app.filter) } else { app.filter.clone() };
+    let border = if editing { role_style(Role::Highlight) } else { Style::default() };
+    let mut title = t!("tui.filter");
+    if app.show_completed {
+        title = format!("{} {}", title, t!("tui.showing_completed"));
+    }
+    frame.render_widget(
+        Paragraph::new(text).block(Block::default().borders(Borders::ALL).border_style(border).title(title)),
+        area,
+    );
+}
+
+fn draw_list(frame: &mut Frame, app: &mut App, area: Rect) {
+    let items: Vec<ListItem> = app.visible_tasks().map(list_item).collect();
+    let title = tn!("tui.tasks", items.len());
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(list, area, &mut app.list_state);
+}
+
+/// 一覧の1行（状態の記号・ID・タイトル・タグ）
+fn list_item(task: &Task) -> ListItem<'static> {
+    let (mark, role) = if task.completed {
+        (glyph(Glyph::Done), Role::Done)
+    } else if task.is_overdue() {
+        (glyph(Glyph::Overdue), Role::Overdue)
+    } else if task.is_due_today() {
+        (glyph(Glyph::DueToday), Role::DueToday)
+    } else {
+        (glyph(Glyph::Pending), Role::Muted)
+    };
+    let title_style = if task.completed || task.is_waiting() {
+        role_style(Role::Muted)
+    } else {
+        priority_style(task.priority)
+    };
+
+    let mut spans = vec![
+        Span::styled(mark, role_style(role)),
+        Span::raw(" "),
+        Span::styled(format!("{:>3}", task.id.unwrap_or(0)), role_style(Role::Id)),
+        Span::raw(" "),
+        Span::styled(task.title.clone(), title_style),
+    ];
+    for tag in &task.tags {
+        spans.push(Span::styled(format!(" #{}", tag), role_style(Role::Tag)));
+    }
+    ListItem::new(Line::from(spans))
+}
+
+fn draw_detail(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default().borders(Borders::ALL).title(t!("tui.detail"));
+    let Some(task) = app.selected_task() else {
+        frame.render_widget(Paragraph::new(t!("tui.no_tasks")).block(block), area);
+        return;
+    };
+
+    let mut lines = vec![
+        Line::styled(task.title.clone(), Style::default().add_modifier(Modifier::BOLD)),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw(format!("{}: ", t!("table.priority"))),
+            Span::styled(task.priority.label(), priority_style(task.priority)),
+        ]),
+        Line::from(t!("format.status", status = if task.completed { "done" } else { task.status.as_str() })),
+    ];
+    if let Some(due) = task.due_date {
+        let role = if task.completed {
+            Role::Muted
+        } else if task.is_overdue() {
+            Role::Overdue
+        } else if task.is_due_today() {
+            Role::DueToday
+        } else {
+            Role::Info
+        };
+        lines.push(Line::styled(t!("format.due", time = format_datetime(due)), role_style(role)));
+    }
+    if let Some(start) = task.start_date {
+        lines.push(Line::from(t!("format.start", date = format_datetime(start))));
+    }
+    if let Some(wait) = task.wait_until {
+        lines.push(Line::from(t!("format.wait", date = format_datetime(wait))));
+    }
+    if let Some(project) = &task.project {
+        lines.push(Line::from(t!("format.project", name = project)));
+    }
+    if let Some(estimate) = task.estimate() {
+        lines.push(Line::from(t!("format.estimate", duration = format_duration(estimate))));
+    }
+    if !task.tags.is_empty() {
+        let tags = task.tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" ");
+        lines.push(Line::from(vec![
+            Span::raw(format!("{}: ", t!("table.tags"))),
+            Span::styled(tags, role_style(Role::Tag)),
+        ]));
+    }
+    if !task.depends_on.is_empty() {
+        let ids = task.depends_on.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(", ");
+        lines.push(Line::from(t!("format.depends_on", ids = ids)));
+    }
+    for (name, value) in &task.custom_fields {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}: ", name), role_style(Role::Tag)),
+            Span::raw(value.clone()),
+        ]));
+    }
+    if let Some((done, total)) = checklist::progress(&task.checklist) {
+        lines.push(Line::from(t!("format.checklist", done = done, total = total)));
+        for (index, item) in task.checklist.iter().enumerate() {
+            let (mark, style) = if item.done {
+                (glyph(Glyph::Done), role_style(Role::Muted))
+            } else {
+                (glyph(Glyph::Pending), Style::default())
+            };
+            lines.push(Line::styled(format!("  {:>2}. {} {}", index + 1, mark, item.text), style));
+        }
+    }
+    lines.push(Line::styled(t!("format.created", time = format_datetime(task.created_at)), role_style(Role::Muted)));
+    if let Some(completed_at) = task.completed_at {
+        lines.push(Line::styled(t!("format.completed", time = format_datetime(completed_at)), role_style(Role::Done)));
+    }
+    if let Some(description) = &task.description {
+        lines.push(Line::from(""));
+        lines.extend(description.lines().map(|l| Line::from(l.to_string())));
+    }
+    if !task.attachments.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(tn!("show.attachments", task.attachments.len())));
+        for (index, attachment) in task.attachments.iter().enumerate() {
+            let label = attachment.name.clone().unwrap_or_else(|| attachment.target.clone());
+            lines.push(Line::from(format!("  {:>2}. {}", index + 1, label)));
+        }
+    }
+    if !task.notes.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(tn!("show.notes", task.notes.len())));
+        for note in &task.notes {
+            lines.push(Line::styled(format_datetime(note.created_at), role_style(Role::Muted)));
+            lines.extend(note.text.lines().map(|l| Line::from(format!("  {}", l))));
+        }
+    }
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }).block(block), area);
+}
+
+/// 画面下部の行（メッセージがあればメッセージ、なければ操作の説明）
+fn draw_status(frame: &mut Frame, app: &App, area: Rect) {
+    let line = match &app.message {
+        Some((message, true)) => Line::styled(message.clone(), role_style(Role::Error)),
+        Some((message, false)) => Line::styled(message.clone(), role_style(Role::Info)),
+        None => {
+            let help = match app.mode {
+                Mode::Filter => t!("tui.help_filter"),
+                Mode::TagPicker { .. } => t!("tui.help_tags"),
+                Mode::PriorityPicker { .. } => t!("tui.help_priority"),
+                Mode::Input { .. } => t!("tui.help_input"),
+                Mode::ConfirmDelete => String::new(),
+                Mode::Normal => t!("tui.help"),
+            };
+            Line::styled(help, role_style(Role::Muted))
+        }
+    };
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+/// 画面の中央に置くポップアップの領域
+fn popup_area(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+fn priority_style(priority: Priority) -> Style {
+    role_style(match priority {
+        Priority::High => Role::PriorityHigh,
+        Priority::Medium => Role::PriorityMedium,
+        Priority::Low => Role::PriorityLow,
+    })
+}
+
+/// テーマの装飾を ratatui の装飾に変換（色を付けない設定では装飾のみ）
+fn role_style(role: Role) -> Style {
+    let themed = theme::palette().style(role);
+    let mut style = Style::default();
+    if colored::control::SHOULD_COLORIZE.should_colorize() {
+        if let Some(color) = themed.color {
+            style = style.fg(convert_color(color));
+        }
+        if let Some(background) = themed.background {
+            style = style.bg(convert_color(background));
+        }
+    }
+    if themed.bold {
+        style = style.add_modifier(Modifier::BOLD);
+    }
+    if themed.dimmed {
+        style = style.add_modifier(Modifier::DIM);
+    }
+    style
+}
+
+fn convert_color(color: colored::Color) -> Color {
+    match color {
+        colored::Color::Black => Color::Black,
+        colored::Color::Red => Color::Red,
+        colored::Color::Green => Color::Green,
+        colored::Color::Yellow => Color::Yellow,
+        colored::Color::Blue => Color::Blue,
+        colored::Color::Magenta => Color::Magenta,
+        colored::Color::Cyan => Color::Cyan,
+        colored::Color::White => Color::Gray,
+        colored::Color::BrightBlack => Color::DarkGray,
+        colored::Color::BrightRed => Color::LightRed,
+        colored::Color::BrightGreen => Color::LightGreen,
+        colored::Color::BrightYellow => Color::LightYellow,
+        colored::Color::BrightBlue => Color::LightBlue,
+        colored::Color::BrightMagenta => Color::LightMagenta,
+        colored::Color::BrightCyan => Color::LightCyan,
+        colored::Color::BrightWhite => Color::White,
+        colored::Color::TrueColor { r, g, b } => Color::Rgb(r, g, b),
+    }
+}
@@ -1,4 +1,4 @@
-use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
 use crate::error::{Result, TaskError};
 
 /// 日付文字列からDateTime<Local>を作成
@@ -10,11 +10,8 @@ pub fn parse_date(date_str: &str) -> Result<DateTime<Local>> {
     if contains_time {
         // YYYY-MM-DD HH:MM 形式
         match NaiveDateTime::parse_from_str(date_str, "%Y-%m-%d %H:%M") {
-            Ok(dt) => Ok(DateTime::<Local>::from_naive_utc_and_offset(dt, *Local::now().offset())),
-            Err(_) => Err(TaskError::InvalidDateFormat(format!(
-                "不正な日付形式: {}. 正しい形式は YYYY-MM-DD HH:MM です", 
-                date_str
-            ))),
+            Ok(dt) => naive_to_local(dt, date_str),
+            Err(_) => Err(TaskError::InvalidDateFormat(t!("date.expected_datetime", input = date_str))),
         }
     } else {
         // YYYY-MM-DD 形式 (時間は 00:00 とする)
@@ -22,16 +19,51 @@ pub fn parse_date(date_str: &str) -> Result<DateTime<Local>> {
             Ok(date) => {
                 let time = NaiveTime::from_hms_opt(0, 0, 0).unwrap();
                 let dt = NaiveDateTime::new(date, time);
-                Ok(DateTime::<Local>::from_naive_utc_and_offset(dt, *Local::now().offset()))
+                naive_to_local(dt, date_str)
             },
-            Err(_) => Err(TaskError::InvalidDateFormat(format!(
-                "不正な日付形式: {}. 正しい形式は YYYY-MM-DD です", 
-                date_str
-            ))),
+            Err(_) => Err(TaskError::InvalidDateFormat(t!("date.expected_date", input = date_str))),
         }
     }
 }
 
+/// ローカル時刻として解釈した日時を DateTime<Local> に変換
+fn naive_to_local(dt: NaiveDateTime, date_str: &str) -> Result<DateTime<Local>> {
+    dt.and_local_timezone(Local)
+        .earliest()
+        .ok_or_else(|| TaskError::InvalidDateFormat(t!("date.nonexistent", input = date_str)))
+}
+
+/// 日付の 0:00 を DateTime<Local> に変換
+pub fn start_of_day(date: NaiveDate) -> DateTime<Local> {
+    let dt = NaiveDateTime::new(date, NaiveTime::from_hms_opt(0, 0, 0).unwrap());
+    dt.and_local_timezone(Local)
+        .earliest()
+        .unwrap_or_else(Local::now)
+}
+
+/// 日付が属する週の最初の日を取得
+pub fn start_of_week(date: NaiveDate, week_start: Weekday) -> NaiveDate {
+    date - chrono::Duration::days(date.weekday().days_since(week_start) as i64)
+}
+
+/// 曜日の表示名を取得
+pub fn weekday_name(weekday: Weekday) -> String {
+    match weekday {
+        Weekday::Mon => t!("weekday.mon"),
+        Weekday::Tue => t!("weekday.tue"),
+        Weekday::Wed => t!("weekday.wed"),
+        Weekday::Thu => t!("weekday.thu"),
+        Weekday::Fri => t!("weekday.fri"),
+        Weekday::Sat => t!("weekday.sat"),
+        Weekday::Sun => t!("weekday.sun"),
+    }
+}
+
+/// 日付を曜日付きで表示する形式に変換（例: "2026-11-02（月）"）
+pub fn format_day(date: NaiveDate) -> String {
+    t!("date.day", date = date.format("%Y-%m-%d"), weekday = weekday_name(date.weekday()))
+}
+
 /// 日時を人間が読みやすい形式に変換
 pub fn format_datetime(date: DateTime<Local>) -> String {
     date.format("%Y-%m-%d %H:%M").to_string()
@@ -43,46 +75,46 @@ pub fn get_relative_time(date: DateTime<Local>) -> String {
     let duration = now.signed_duration_since(date);
     
     if duration.num_seconds().abs() < 60 {
-        return "たった今".to_string();
+        return t!("time.just_now");
     }
     
     if duration.num_minutes().abs() < 60 {
         let minutes = duration.num_minutes();
         if minutes > 0 {
-            return format!("{}分前", minutes);
+            return tn!("time.minutes_ago", minutes);
         } else {
-            return format!("{}分後", minutes.abs());
+            return tn!("time.minutes_later", minutes.abs());
         }
     }
     
     if duration.num_hours().abs() < 24 {
         let hours = duration.num_hours();
         if hours > 0 {
-            return format!("{}時間前", hours);
+            return tn!("time.hours_ago", hours);
         } else {
-            return format!("{}時間後", hours.abs());
+            return tn!("time.hours_later", hours.abs());
         }
     }
     
     let days = duration.num_days();
     if days.abs() < 7 {
         if days == 1 {
-            return "昨日".to_string();
+            return t!("time.yesterday");
         } else if days == -1 {
-            return "明日".to_string();
+            return t!("time.tomorrow");
         } else if days > 0 {
-            return format!("{}日前", days);
+            return tn!("time.days_ago", days);
         } else {
-            return format!("{}日後", days.abs());
+            return tn!("time.days_later", days.abs());
         }
     }
     
     if days.abs() < 30 {
         let weeks = days / 7;
         if weeks > 0 {
-            return format!("{}週間前", weeks);
+            return tn!("time.weeks_ago", weeks);
         } else {
-            return format!("{}週間後", weeks.abs());
+            return tn!("time.weeks_later", weeks.abs());
         }
     }
     
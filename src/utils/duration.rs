@@ -0,0 +1,79 @@
+use chrono::{DateTime, Duration, Local};
+use crate::error::{Result, TaskError};
+use crate::models::task::validate_estimate;
+
+/// 期間文字列から Duration を作成
+/// 形式: 1h30m, 90m, 2h, 3d（単位を省略した数値は分として扱う）
+pub fn parse_duration(duration_str: &str) -> Result<Duration> {
+    let invalid = || TaskError::InvalidArgument(t!("duration.invalid", input = duration_str));
+    let too_large = || TaskError::InvalidArgument(t!("duration.too_large", input = duration_str));
+
+    let input = duration_str.trim();
+    if input.is_empty() {
+        return Err(invalid());
+    }
+
+    // 単位なしの数値は分
+    if let Ok(minutes) = input.parse::<i64>() {
+        return if minutes > 0 { Duration::try_minutes(minutes).ok_or_else(too_large) } else { Err(invalid()) };
+    }
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        let value = number.parse::<i64>().map_err(|_| invalid())?;
+        number.clear();
+        let part = match c.to_ascii_lowercase() {
+            'd' => Duration::try_days(value),
+            'h' => Duration::try_hours(value),
+            'm' => Duration::try_minutes(value),
+            's' => Duration::try_seconds(value),
+            _ => return Err(invalid()),
+        };
+        total = part.and_then(|part| total.checked_add(&part)).ok_or_else(too_large)?;
+    }
+
+    // 末尾に単位のない数値が残っている場合は不正
+    if !number.is_empty() || total <= Duration::zero() {
+        return Err(invalid());
+    }
+
+    Ok(total)
+}
+
+/// 見積もり時間を解析して分に変換（1 分から上限までの範囲）
+pub fn parse_estimate(input: &str) -> Result<i64> {
+    validate_estimate(parse_duration(input)?.num_minutes()).map_err(TaskError::InvalidArgument)
+}
+
+/// 日時に期間を加える（表せる日時の範囲を超える場合はエラー）
+pub fn add_duration(date: DateTime<Local>, duration: Duration) -> Result<DateTime<Local>> {
+    date.checked_add_signed(duration)
+        .ok_or_else(|| TaskError::InvalidArgument(t!("duration.out_of_range", duration = format_duration(duration))))
+}
+
+/// 日時から期間を引く（表せる日時の範囲を超える場合はエラー）
+pub fn sub_duration(date: DateTime<Local>, duration: Duration) -> Result<DateTime<Local>> {
+    date.checked_sub_signed(duration)
+        .ok_or_else(|| TaskError::InvalidArgument(t!("duration.out_of_range", duration = format_duration(duration))))
+}
+
+/// Duration を人間が読みやすい形式に変換（例: "1h30m", "45m"）
+pub fn format_duration(duration: Duration) -> String {
+    let total_minutes = duration.num_minutes().max(0);
+    let hours = total_minutes / 60;
+    let minutes = total_minutes % 60;
+
+    if hours > 0 && minutes > 0 {
+        format!("{}h{}m", hours, minutes)
+    } else if hours > 0 {
+        format!("{}h", hours)
+    } else {
+        format!("{}m", minutes)
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::HashSet;
+
+use crate::error::{Result, TaskError};
+use crate::models::field::{FieldCondition, FieldDefinition};
+use crate::models::task::{Priority, Task};
+
+/// 期限による条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DueCondition {
+    Today,
+    Overdue,
+    None,
+}
+
+/// 状態による条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StateCondition {
+    Done,
+    Pending,
+    Waiting,
+}
+
+#[derive(Debug, Clone)]
+enum Term {
+    Tag(String),
+    Project(String),
+    Priority(Priority),
+    Status(String),
+    Due(DueCondition),
+    State(StateCondition),
+    Field(FieldCondition, FieldDefinition),
+    /// タイトル・説明に含まれる語（小文字に変換済み）と、全文検索でメモが一致したタスクのID
+    Text(String, HashSet<i64>),
+}
+
+/// --where で指定する絞り込み条件（空白区切りの条件をすべて満たすタスク。`or` で区切った
+/// いずれかの条件の組を満たすタスク）
+///
+/// 条件は tag:NAME、project:NAME、priority:N（高・中・低も可）、status:NAME、due:today|overdue|none、
+/// is:done|pending|waiting、カスタムフィールドの比較（story_points>=3）で、
+/// それ以外の語はタイトル・説明に含まれる語、またはメモの全文検索の語として扱う。
+#[derive(Debug, Clone)]
+pub struct TaskFilter {
+    groups: Vec<Vec<Term>>,
+}
+
+impl TaskFilter {
+    pub fn parse(expression: &str, definitions: &[FieldDefinition]) -> Result<Self> {
+        let words = shell_words::split(expression)
+            .map_err(|e| TaskError::InvalidArgument(t!("filter.invalid", detail = e)))?;
+        let mut groups = vec![Vec::new()];
+        for word in &words {
+            if word.eq_ignore_ascii_case("or") {
+                groups.push(Vec::new());
+            } else if let Some(group) = groups.last_mut() {
+                group.push(parse_term(word, definitions)?);
+            }
+        }
+        if groups.iter().any(Vec::is_empty) {
+            return Err(TaskError::InvalidArgument(t!("filter.empty")));
+        }
+        Ok(Self { groups })
+    }
+
+    /// 語に一致するメモのあるタスクを search（メモの全文検索）で探しておく
+    ///
+    /// 呼ばない場合、語はタイトル・説明だけに照合する。
+    pub fn search_notes(mut self, mut search: impl FnMut(&str) -> Result<HashSet<i64>>) -> Result<Self> {
+        for term in self.groups.iter_mut().flatten() {
+            if let Term::Text(text, notes) = term {
+                *notes = search(text)?;
+            }
+        }
+        Ok(self)
+    }
+
+    pub fn matches(&self, task: &Task) -> bool {
+        self.groups.iter().any(|terms| terms.iter().all(|term| matches_term(term, task)))
+    }
+
+    /// どの条件の組でも必要なタグ（追加するタスクに付けると条件を満たしやすくなる）
+    pub fn required_tags(&self) -> Vec<String> {
+        let tags_of = |terms: &[Term]| -> Vec<String> {
+            terms
+                .iter()
+                .filter_map(|term| match term {
+                    Term::Tag(tag) => Some(tag.clone()),
+                    _ => None,
+                })
+                .collect()
+        };
+        let mut tags = tags_of(&self.groups[0]);
+        for group in &self.groups[1..] {
+            let other = tags_of(group);
+            tags.retain(|tag| other.contains(tag));
+        }
+        tags
+    }
+
+    /// どの条件の組でも同じプロジェクトを指定している場合のプロジェクト
+    pub fn required_project(&self) -> Option<String> {
+        let project_of = |terms: &[Term]| {
+            terms.iter().find_map(|term| match term {
+                Term::Project(project) => Some(project.clone()),
+                _ => None,
+            })
+        };
+        let project = project_of(&self.groups[0])?;
+        self.groups[1..]
+            .iter()
+            .all(|group| project_of(group).as_ref() == Some(&project))
+            .then_some(project)
+    }
+}
+
+fn matches_term(term: &Term, task: &Task) -> bool {
+    match term {
+        Term::Tag(tag) => task.tags.iter().any(|t| t == tag),
+        Term::Project(project) => task.project.as_ref() == Some(project),
+        Term::Priority(priority) => task.priority == *priority,
+        Term::Status(status) => !task.completed && task.status == *status,
+        Term::Due(DueCondition::Today) => task.is_due_today(),
+        Term::Due(DueCondition::Overdue) => task.is_overdue(),
+        Term::Due(DueCondition::None) => task.due_date.is_none(),
+        Term::State(StateCondition::Done) => task.completed,
+        Term::State(StateCondition::Pending) => !task.completed,
+        Term::State(StateCondition::Waiting) => task.is_waiting(),
+        Term::Field(condition, definition) => {
+            condition.matches(definition, task.custom_fields.get(&condition.name).map(String::as_str))
+        }
+        Term::Text(text, notes) => task.matches_text(text) || task.id.is_some_and(|id| notes.contains(&id)),
+    }
+}
+
+fn parse_term(word: &str, definitions: &[FieldDefinition]) -> Result<Term> {
+    let invalid = || TaskError::InvalidArgument(t!("filter.invalid_term", term = word));
+    if let Some((key, value)) = word.split_once(':') {
+        let value = value.trim();
+        let term = match key {
+            "tag" => Some(Term::Tag(value.trim_start_matches('#').to_string())),
+            "project" => Some(Term::Project(value.to_string())),
+            "priority" => Some(Term::Priority(Priority::parse(value).ok_or_else(invalid)?)),
+            "status" => Some(Term::Status(value.to_string())),
+            "due" => Some(Term::Due(match value {
+                "today" => DueCondition::Today,
+                "overdue" => DueCondition::Overdue,
+                "none" => DueCondition::None,
+                _ => return Err(invalid()),
+            })),
+            "is" => Some(Term::State(match value {
+                "done" | "completed" => StateCondition::Done,
+                "pending" => StateCondition::Pending,
+                "waiting" => StateCondition::Waiting,
+                _ => return Err(invalid()),
+            })),
+            _ => None,
+        };
+        if let Some(term) = term {
+            if value.is_empty() {
+                return Err(invalid());
+            }
+            return Ok(term);
+        }
+    }
+
+    // カスタムフィールドの比較（定義されていない名前はエラー）
+    if let Ok(condition) = FieldCondition::parse(word) {
+        let definition = definitions
+            .iter()
+            .find(|d| d.name == condition.name)
+            .ok_or_else(|| TaskError::FieldNotFound(condition.name.clone()))?;
+        return Ok(Term::Field(condition, definition.clone()));
+    }
+    Ok(Term::Text(word.to_lowercase(), HashSet::new()))
+}
@@ -1,36 +1,44 @@
 use colored::*;
+use crate::models::checklist::{self, ChecklistItem};
+use crate::models::field::FieldDefinition;
 use crate::models::task::{Priority, Task};
-use crate::utils::date::get_relative_time;
+use crate::utils::date::{format_datetime, get_relative_time};
+use crate::utils::duration::format_duration;
+use crate::utils::text::{display_width, truncate_to_width};
+use crate::utils::theme::{glyph, paint, Glyph, Role};
+use chrono::{DateTime, Local};
 
 /// タスクの表示用文字列を作成
 pub fn format_task(task: &Task, verbose: bool) -> String {
     let status = if task.completed {
-        "[✓]".green()
+        paint(glyph(Glyph::Done), Role::Done)
     } else if task.is_overdue() {
-        "[!]".red()
+        paint(glyph(Glyph::Overdue), Role::Overdue)
     } else if task.is_due_today() {
-        "[⚑]".yellow()
+        paint(glyph(Glyph::DueToday), Role::DueToday)
     } else {
-        "[ ]".normal()
+        glyph(Glyph::Pending).normal()
     };
 
-    let id = format!("{:>3}", task.id.unwrap_or(0)).blue();
+    let id = paint(&format!("{:>3}", task.id.unwrap_or(0)), Role::Id);
     let title = if task.completed {
         task.title.strikethrough()
     } else {
-        match task.priority {
-            Priority::High => task.title.red().bold(),
-            Priority::Medium => task.title.yellow(),
-            Priority::Low => task.title.normal(),
-        }
+        color_by_priority(&task.title, task.priority)
     };
 
     let mut result = format!("{} {} {}", status, id, title);
 
+    // チェックリストの進み具合の表示
+    if let Some((done, total)) = checklist::progress(&task.checklist) {
+        let role = if done == total { Role::Done } else { Role::Muted };
+        result = format!("{} {}", result, paint(&format!("[{}/{}]", done, total), role));
+    }
+
     // タグの表示
     if !task.tags.is_empty() {
         let tags = task.tags.iter()
-            .map(|t| format!("#{}", t).cyan().to_string())
+            .map(|t| paint(&format!("#{}", t), Role::Tag).to_string())
             .collect::<Vec<_>>()
             .join(" ");
         result = format!("{} {}", result, tags);
@@ -38,16 +46,26 @@ pub fn format_task(task: &Task, verbose: bool) -> String {
 
     // 期限の表示
     if let Some(due_date) = task.due_date {
-        let due_str = format!("期限: {}", get_relative_time(due_date));
+        let due_str = t!("format.due", time = get_relative_time(due_date));
         if task.is_overdue() {
-            result = format!("{} {}", result, due_str.red());
+            result = format!("{} {}", result, paint(&due_str, Role::Overdue));
         } else if task.is_due_today() {
-            result = format!("{} {}", result, due_str.yellow());
+            result = format!("{} {}", result, paint(&due_str, Role::DueToday));
         } else {
-            result = format!("{} {}", result, due_str.normal());
+            result = format!("{} {}", result, due_str);
         }
     }
 
+    // 待機中の表示
+    if let Some(wait_until) = task.wait_until.filter(|_| task.is_waiting()) {
+        let waiting = format!(
+            "{} {}",
+            glyph(Glyph::Waiting),
+            t!("format.waiting", time = get_relative_time(wait_until))
+        );
+        result = format!("{} {}", result, paint(&waiting, Role::Muted));
+    }
+
     // 詳細表示
     if verbose {
         if let Some(desc) = &task.description {
@@ -56,13 +74,49 @@ pub fn format_task(task: &Task, verbose: bool) -> String {
             }
         }
 
-        let created_str = format!("作成: {}", get_relative_time(task.created_at));
+        if !task.completed {
+            result = format!("{}\n    {}", result, t!("format.status", status = task.status));
+        }
+
+        if let Some(project) = &task.project {
+            result = format!("{}\n    {}", result, t!("format.project", name = project));
+        }
+
+        if let Some(start_date) = task.start_date {
+            result = format!("{}\n    {}", result, t!("format.start", date = format_datetime(start_date)));
+        }
+
+        if let Some(wait_until) = task.wait_until {
+            result = format!("{}\n    {}", result, t!("format.wait", date = format_datetime(wait_until)));
+        }
+
+        if !task.depends_on.is_empty() {
+            let depends_on = task.depends_on.iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            result = format!("{}\n    {}", result, t!("format.depends_on", ids = depends_on));
+        }
+
+        if let Some(estimate) = task.estimate() {
+            result = format!("{}\n    {}", result, t!("format.estimate", duration = format_duration(estimate)));
+        }
+
+        for (name, value) in &task.custom_fields {
+            result = format!("{}\n    {}: {}", result, paint(name, Role::Tag), value);
+        }
+
+        for line in format_checklist(&task.checklist) {
+            result = format!("{}\n    {}", result, line);
+        }
+
+        let created_str = t!("format.created", time = get_relative_time(task.created_at));
         result = format!("{}\n    {}", result, created_str);
 
         if task.completed {
             if let Some(completed_at) = task.completed_at {
-                let completed_str = format!("完了: {}", get_relative_time(completed_at));
-                result = format!("{} | {}", result, completed_str.green());
+                let completed_str = t!("format.completed", time = get_relative_time(completed_at));
+                result = format!("{} | {}", result, paint(&completed_str, Role::Done));
             }
         }
     }
@@ -70,14 +124,37 @@ pub fn format_task(task: &Task, verbose: bool) -> String {
     result
 }
 
+/// チェックリストの表示用の行を作成（見出しと番号付きの項目。項目がない場合は空）
+pub fn format_checklist(items: &[ChecklistItem]) -> Vec<String> {
+    let Some((done, total)) = checklist::progress(items) else {
+        return Vec::new();
+    };
+
+    let mut lines = vec![t!("format.checklist", done = done, total = total)];
+    for (index, item) in items.iter().enumerate() {
+        let line = if item.done {
+            format!("{} {}", paint(glyph(Glyph::Done), Role::Done), paint(&item.text, Role::Muted))
+        } else {
+            format!("{} {}", glyph(Glyph::Pending), item.text)
+        };
+        lines.push(format!("  {:>2}. {}", index + 1, line));
+    }
+    lines
+}
+
+/// 文字列を優先度に対応する色で表示
+pub fn color_by_priority(text: &str, priority: Priority) -> ColoredString {
+    let role = match priority {
+        Priority::High => Role::PriorityHigh,
+        Priority::Medium => Role::PriorityMedium,
+        Priority::Low => Role::PriorityLow,
+    };
+    paint(text, role)
+}
+
 /// 優先度に対応する色付きの文字列を作成
-#[allow(dead_code)]
 pub fn format_priority(priority: Priority) -> ColoredString {
-    match priority {
-        Priority::High => "高".red().bold(),
-        Priority::Medium => "中".yellow(),
-        Priority::Low => "低".normal(),
-    }
+    color_by_priority(&priority.label(), priority)
 }
 
 /// 統計情報の表示用文字列を作成
@@ -87,6 +164,8 @@ pub fn format_stats(stats: &std::collections::HashMap<String, i64>) -> String {
     let active = stats.get("active").unwrap_or(&0);
     let overdue = stats.get("overdue").unwrap_or(&0);
     let due_today = stats.get("due_today").unwrap_or(&0);
+    let pomodoros = stats.get("pomodoros").unwrap_or(&0);
+    let pomodoros_today = stats.get("pomodoros_today").unwrap_or(&0);
     
     let priority_1 = stats.get("priority_1").unwrap_or(&0);
     let priority_2 = stats.get("priority_2").unwrap_or(&0);
@@ -98,27 +177,301 @@ pub fn format_stats(stats: &std::collections::HashMap<String, i64>) -> String {
         0.0
     };
     
-    format!(
-        r#"📊 タスク統計
-
-総タスク数: {}
-完了済み: {} ({:.1}%)
-未完了: {}
-期限切れ: {}
-今日が期限: {}
-
-優先度:
-  高: {}
-  中: {}
-  低: {}"#,
-        total.to_string().bold(),
-        completed.to_string().green(),
-        completion_rate,
-        active.to_string().blue(),
-        overdue.to_string().red(),
-        due_today.to_string().yellow(),
-        priority_3.to_string().red(),
-        priority_2.to_string().yellow(),
-        priority_1.to_string().normal(),
+    t!(
+        "stats.summary",
+        total = total.to_string().bold(),
+        completed = paint(&completed.to_string(), Role::Done),
+        rate = format!("{:.1}", completion_rate),
+        active = paint(&active.to_string(), Role::Info),
+        overdue = paint(&overdue.to_string(), Role::Overdue),
+        due_today = paint(&due_today.to_string(), Role::DueToday),
+        high = color_by_priority(&priority_3.to_string(), Priority::High),
+        medium = color_by_priority(&priority_2.to_string(), Priority::Medium),
+        low = color_by_priority(&priority_1.to_string(), Priority::Low),
+        pomodoros = pomodoros.to_string().bold(),
+        pomodoros_today = pomodoros_today,
+        chart = glyph(Glyph::Chart),
+        pomodoro = glyph(Glyph::Pomodoro),
     )
 } 
+
+/// テンプレートで使用できるタスクのフィールド
+const TEMPLATE_FIELDS: &[&str] = &[
+    "id", "title", "description", "status", "priority", "tags", "due", "created", "start", "wait",
+    "estimate", "checklist", "project",
+];
+
+/// 幅・最大幅に指定できる最大値
+const MAX_TEMPLATE_WIDTH: usize = 1000;
+
+/// 日時のフィールド（:relative, :date, :datetime を指定可能）
+const TEMPLATE_DATE_FIELDS: &[&str] = &["due", "created", "start", "wait"];
+
+/// 日時の表示方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DateStyle {
+    DateTime,
+    Date,
+    Relative,
+}
+
+/// テンプレートのフィールドの表示指定（例: ">4", "<20.20"）
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FieldSpec {
+    name: String,
+    date_style: DateStyle,
+    align: char,
+    width: Option<usize>,
+    max_width: Option<usize>,
+}
+
+/// テンプレートの構成要素
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TemplateNode {
+    Text(String),
+    Field(FieldSpec),
+    /// 値があるとき（negate の場合は値がないとき）のみ表示する区間
+    Section {
+        name: String,
+        negate: bool,
+        children: Vec<TemplateNode>,
+    },
+}
+
+/// タスクの表示用テンプレート
+///
+/// `{id:>4} {title} {?due}（期限 {due:relative}）{/due}` のように書く。
+/// `{?name}...{/name}` は値があるときのみ、`{?!name}...{/name}` は値がないときのみ表示され、
+/// `{{` と `}}` は波括弧そのものを表す。カスタムフィールドも名前で参照できる。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    nodes: Vec<TemplateNode>,
+}
+
+impl Template {
+    /// テンプレートを解析して検証する（不正なフィールド名や閉じていない区間はエラー）
+    pub fn parse(source: &str, definitions: &[FieldDefinition]) -> Result<Self, String> {
+        // (区間名, 否定か, 要素) のスタック。先頭はテンプレート全体
+        let mut stack: Vec<(String, bool, Vec<TemplateNode>)> = vec![(String::new(), false, Vec::new())];
+        let mut text = String::new();
+        let mut chars = source.char_indices().peekable();
+
+        while let Some((pos, c)) = chars.next() {
+            match c {
+                '{' if chars.peek().map(|(_, c)| *c) == Some('{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek().map(|(_, c)| *c) == Some('}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '}' => return Err(t!("template.unmatched_close", pos = pos + 1)),
+                '{' => {
+                    let mut tag = String::new();
+                    let mut closed = false;
+                    for (_, c) in chars.by_ref() {
+                        if c == '}' {
+                            closed = true;
+                            break;
+                        }
+                        tag.push(c);
+                    }
+                    if !closed {
+                        return Err(t!("template.unclosed_brace", pos = pos + 1));
+                    }
+
+                    let top = &mut stack.last_mut().unwrap().2;
+                    if !text.is_empty() {
+                        top.push(TemplateNode::Text(std::mem::take(&mut text)));
+                    }
+
+                    let tag = tag.trim();
+                    if let Some(name) = tag.strip_prefix('?') {
+                        let (negate, name) = match name.strip_prefix('!') {
+                            Some(name) => (true, name.trim()),
+                            None => (false, name.trim()),
+                        };
+                        validate_template_field(name, definitions)?;
+                        stack.push((name.to_string(), negate, Vec::new()));
+                    } else if let Some(name) = tag.strip_prefix('/') {
+                        let name = name.trim();
+                        if stack.len() == 1 {
+                            return Err(t!("template.unmatched_section_end", name = name));
+                        }
+                        let (open, negate, children) = stack.pop().unwrap();
+                        if !name.is_empty() && name != open {
+                            return Err(t!("template.mismatched_section", open = open, name = name));
+                        }
+                        stack.last_mut().unwrap().2.push(TemplateNode::Section {
+                            name: open,
+                            negate,
+                            children,
+                        });
+                    } else {
+                        let spec = parse_field_spec(tag, definitions)?;
+                        stack.last_mut().unwrap().2.push(TemplateNode::Field(spec));
+                    }
+                }
+                c => text.push(c),
+            }
+        }
+
+        if stack.len() > 1 {
+            return Err(t!("template.unclosed_section", name = stack.last().unwrap().0));
+        }
+        let mut nodes = stack.pop().unwrap().2;
+        if !text.is_empty() {
+            nodes.push(TemplateNode::Text(text));
+        }
+
+        Ok(Self { nodes })
+    }
+
+    /// タスクをテンプレートに従って文字列にする
+    pub fn render(&self, task: &Task) -> String {
+        let mut result = String::new();
+        render_nodes(&self.nodes, task, &mut result);
+        result
+    }
+}
+
+/// フィールド名を検証
+fn validate_template_field(name: &str, definitions: &[FieldDefinition]) -> Result<(), String> {
+    if TEMPLATE_FIELDS.contains(&name) || definitions.iter().any(|d| d.name == name) {
+        Ok(())
+    } else {
+        Err(t!("template.unknown_field", name = name, fields = TEMPLATE_FIELDS.join(", ")))
+    }
+}
+
+/// "name:spec:spec" 形式のフィールド指定を解析
+fn parse_field_spec(tag: &str, definitions: &[FieldDefinition]) -> Result<FieldSpec, String> {
+    let mut parts = tag.split(':');
+    let name = parts.next().unwrap_or_default().trim();
+    validate_template_field(name, definitions)?;
+
+    let mut spec = FieldSpec {
+        name: name.to_string(),
+        date_style: DateStyle::DateTime,
+        align: '<',
+        width: None,
+        max_width: None,
+    };
+
+    for part in parts.map(str::trim) {
+        let date_style = match part {
+            "relative" => Some(DateStyle::Relative),
+            "date" => Some(DateStyle::Date),
+            "datetime" => Some(DateStyle::DateTime),
+            _ => None,
+        };
+        if let Some(style) = date_style {
+            if !TEMPLATE_DATE_FIELDS.contains(&name) {
+                return Err(t!("template.date_style_on_non_date", name = name, style = part));
+            }
+            spec.date_style = style;
+            continue;
+        }
+
+        // [<>^]幅.最大幅
+        let invalid = || t!("template.invalid_spec", name = name, spec = part);
+        let mut rest = part;
+        if let Some(align) = rest.chars().next().filter(|c| matches!(c, '<' | '>' | '^')) {
+            spec.align = align;
+            rest = &rest[1..];
+        }
+        if rest.is_empty() {
+            return Err(invalid());
+        }
+        let (width, max_width) = match rest.split_once('.') {
+            Some((width, max_width)) => (width, Some(max_width)),
+            None => (rest, None),
+        };
+        let parse_width = |value: &str| -> Result<usize, String> {
+            let width: usize = value.parse().map_err(|_| invalid())?;
+            if width > MAX_TEMPLATE_WIDTH {
+                return Err(t!("template.width_too_large", name = name, spec = part, max = MAX_TEMPLATE_WIDTH));
+            }
+            Ok(width)
+        };
+        if !width.is_empty() {
+            spec.width = Some(parse_width(width)?);
+        }
+        if let Some(max_width) = max_width {
+            spec.max_width = Some(parse_width(max_width)?);
+        }
+    }
+
+    Ok(spec)
+}
+
+/// テンプレートの要素を順に出力
+fn render_nodes(nodes: &[TemplateNode], task: &Task, result: &mut String) {
+    for node in nodes {
+        match node {
+            TemplateNode::Text(text) => result.push_str(text),
+            TemplateNode::Field(spec) => {
+                let value = template_value(task, &spec.name, spec.date_style);
+                result.push_str(&apply_field_spec(&value, spec));
+            }
+            TemplateNode::Section { name, negate, children } => {
+                let has_value = !template_value(task, name, DateStyle::DateTime).is_empty();
+                if has_value != *negate {
+                    render_nodes(children, task, result);
+                }
+            }
+        }
+    }
+}
+
+/// フィールドの値を文字列で取得（値がない場合は空文字列）
+fn template_value(task: &Task, name: &str, date_style: DateStyle) -> String {
+    let format_date = |date: Option<DateTime<Local>>| match date {
+        Some(date) => match date_style {
+            DateStyle::DateTime => format_datetime(date),
+            DateStyle::Date => date.format("%Y-%m-%d").to_string(),
+            DateStyle::Relative => get_relative_time(date),
+        },
+        None => String::new(),
+    };
+
+    match name {
+        "id" => task.id.map(|id| id.to_string()).unwrap_or_default(),
+        "title" => task.title.clone(),
+        "description" => task.description.clone().unwrap_or_default(),
+        "status" if task.completed => "done".to_string(),
+        "status" => task.status.clone(),
+        "priority" => task.priority.label(),
+        "tags" => task.tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" "),
+        "due" => format_date(task.due_date),
+        "created" => format_date(Some(task.created_at)),
+        "start" => format_date(task.start_date),
+        "wait" => format_date(task.wait_until),
+        "estimate" => task.estimate().map(format_duration).unwrap_or_default(),
+        "checklist" => checklist_progress(task),
+        "project" => task.project.clone().unwrap_or_default(),
+        name => task.custom_fields.get(name).cloned().unwrap_or_default(),
+    }
+}
+
+/// チェックリストの進み具合（例: "2/5"、項目がない場合は空文字列）
+pub fn checklist_progress(task: &Task) -> String {
+    checklist::progress(&task.checklist)
+        .map(|(done, total)| format!("{}/{}", done, total))
+        .unwrap_or_default()
+}
+
+/// 幅の指定に従って切り詰め・空白埋めを行う
+fn apply_field_spec(value: &str, spec: &FieldSpec) -> String {
+    let value = match spec.max_width {
+        Some(max_width) => truncate_to_width(value, max_width),
+        None => value.to_string(),
+    };
+    let padding = spec.width.unwrap_or(0).saturating_sub(display_width(&value));
+    match spec.align {
+        '>' => format!("{}{}", " ".repeat(padding), value),
+        '^' => format!("{}{}{}", " ".repeat(padding / 2), value, " ".repeat(padding - padding / 2)),
+        _ => format!("{}{}", value, " ".repeat(padding)),
+    }
+}
@@ -0,0 +1,43 @@
+use crate::error::{Result, TaskError};
+
+/// カンマ区切りのタスクID一覧をパース（例: "3,4,10"、空文字列は空の一覧）
+pub fn parse_id_list(ids: &str) -> Result<Vec<i64>> {
+    let mut result = Vec::new();
+    for part in ids.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
+        let id = part.parse::<i64>().map_err(|_| {
+            TaskError::InvalidArgument(t!("ids.invalid", input = part))
+        })?;
+        if !result.contains(&id) {
+            result.push(id);
+        }
+    }
+    Ok(result)
+}
+
+/// 一度に指定できる範囲の大きさの上限
+const MAX_RANGE: i64 = 10_000;
+
+/// ID・範囲の一覧をパース（例: ["3", "5", "8-12"]、カンマ区切りも可。重複は除く）
+pub fn parse_id_ranges(args: &[String]) -> Result<Vec<i64>> {
+    let mut result = Vec::new();
+    for part in args.iter().flat_map(|arg| arg.split(',')).map(str::trim).filter(|s| !s.is_empty()) {
+        let parse = |s: &str| {
+            s.trim()
+                .parse::<i64>()
+                .map_err(|_| TaskError::InvalidArgument(t!("ids.invalid", input = part)))
+        };
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) => (parse(start)?, parse(end)?),
+            None => (parse(part)?, parse(part)?),
+        };
+        if start > end || end - start >= MAX_RANGE {
+            return Err(TaskError::InvalidArgument(t!("ids.invalid_range", input = part, max = MAX_RANGE)));
+        }
+        for id in start..=end {
+            if !result.contains(&id) {
+                result.push(id);
+            }
+        }
+    }
+    Ok(result)
+}
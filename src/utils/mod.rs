@@ -1,2 +1,10 @@
 pub mod date;
-pub mod format; 
+pub mod duration;
+pub mod filter;
+pub mod format;
+pub mod ids;
+pub mod prompt;
+pub mod sort;
+pub mod table;
+pub mod text;
+pub mod theme;
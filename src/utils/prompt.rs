@@ -0,0 +1,12 @@
+use std::io::{self, Write};
+
+use crate::error::Result;
+
+/// y/N で確認する（y 以外は中止）
+pub fn confirm(prompt: &str) -> Result<bool> {
+    print!("{} ", prompt);
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
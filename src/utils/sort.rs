@@ -0,0 +1,69 @@
+use std::cmp::Ordering;
+
+use crate::error::{Result, TaskError};
+use crate::models::field::FieldDefinition;
+use crate::models::task::Task;
+use crate::models::urgency::UrgencyScorer;
+
+/// タスクを指定したキーで並び替える
+///
+/// キーは id, title, priority, due, created, urgency またはカスタムフィールド名。
+/// urgency は高い順、それ以外は昇順で、先頭に '-' を付けると逆順になる。
+/// 値を持たないタスクは常に末尾に並ぶ。urgency の場合は scorer が必要。
+pub fn sort_tasks(
+    tasks: &mut [Task],
+    key: &str,
+    definitions: &[FieldDefinition],
+    scorer: Option<&UrgencyScorer>,
+) -> Result<()> {
+    let (descending, key) = match key.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, key),
+    };
+
+    let apply = |ordering: Ordering| if descending { ordering.reverse() } else { ordering };
+
+    match key {
+        "id" => tasks.sort_by(|a, b| apply(a.id.cmp(&b.id))),
+        "title" => tasks.sort_by(|a, b| apply(a.title.cmp(&b.title))),
+        "priority" => tasks.sort_by(|a, b| apply((a.priority as i32).cmp(&(b.priority as i32)))),
+        "created" => tasks.sort_by(|a, b| apply(a.created_at.cmp(&b.created_at))),
+        "due" => tasks.sort_by(|a, b| compare_optional(a.due_date, b.due_date, apply)),
+        "urgency" => {
+            let scorer = scorer.ok_or_else(|| {
+                TaskError::Unknown(t!("sort.no_scorer"))
+            })?;
+            tasks.sort_by_cached_key(|task| {
+                // 高い順に並べるため符号を反転（小数第 3 位までで比較）
+                let score = (scorer.score(task).total * 1000.0).round() as i64;
+                if descending { score } else { -score }
+            });
+        }
+        _ => {
+            let definition = definitions
+                .iter()
+                .find(|d| d.name == key)
+                .ok_or_else(|| TaskError::InvalidArgument(t!("sort.invalid_key", key = key)))?;
+            tasks.sort_by(|a, b| {
+                match (a.custom_fields.get(key), b.custom_fields.get(key)) {
+                    (Some(x), Some(y)) => apply(definition.compare(x, y)),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                }
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// 値を持たない要素を末尾に置いて比較
+fn compare_optional<T: Ord>(a: Option<T>, b: Option<T>, apply: impl Fn(Ordering) -> Ordering) -> Ordering {
+    match (a, b) {
+        (Some(x), Some(y)) => apply(x.cmp(&y)),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
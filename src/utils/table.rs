@@ -0,0 +1,190 @@
+use crate::error::{Result, TaskError};
+use crate::models::field::FieldDefinition;
+use crate::models::task::Task;
+use crate::models::urgency::UrgencyScorer;
+use crate::utils::date::format_datetime;
+use crate::utils::duration::format_duration;
+use crate::utils::format::checklist_progress;
+use crate::utils::text::{display_width, pad_to_width, truncate_to_width};
+
+/// --columns を省略したときの列
+pub const DEFAULT_COLUMNS: &str = "id,status,priority,title,tags,due";
+
+/// 列の区切り
+const SEPARATOR: &str = "  ";
+
+/// 端末の幅に合わせて縮めるときの最小幅
+const MIN_FLEX_WIDTH: usize = 8;
+
+/// 表の列
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TableColumn {
+    Id,
+    Status,
+    Priority,
+    Title,
+    Tags,
+    Project,
+    Due,
+    Created,
+    Urgency,
+    Estimate,
+    Start,
+    Wait,
+    /// チェックリストの進み具合
+    Checklist,
+    /// カスタムフィールド
+    Field(String),
+}
+
+impl TableColumn {
+    /// カンマ区切りの列名を解析（カスタムフィールド名も指定可能）
+    pub fn parse_list(columns: &str, definitions: &[FieldDefinition]) -> Result<Vec<Self>> {
+        let columns = columns
+            .split(',')
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .map(|column| match column {
+                "id" => Ok(TableColumn::Id),
+                "status" => Ok(TableColumn::Status),
+                "priority" => Ok(TableColumn::Priority),
+                "title" => Ok(TableColumn::Title),
+                "tags" => Ok(TableColumn::Tags),
+                "project" => Ok(TableColumn::Project),
+                "due" => Ok(TableColumn::Due),
+                "created" => Ok(TableColumn::Created),
+                "urgency" => Ok(TableColumn::Urgency),
+                "estimate" => Ok(TableColumn::Estimate),
+                "start" | "scheduled" => Ok(TableColumn::Start),
+                "wait" => Ok(TableColumn::Wait),
+                "checklist" => Ok(TableColumn::Checklist),
+                name if definitions.iter().any(|d| d.name == name) => Ok(TableColumn::Field(name.to_string())),
+                name => Err(TaskError::InvalidArgument(t!("table.invalid_column", name = name))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if columns.is_empty() {
+            return Err(TaskError::InvalidArgument(t!("table.no_columns")));
+        }
+        Ok(columns)
+    }
+
+    /// 見出し
+    pub fn header(&self) -> String {
+        match self {
+            TableColumn::Id => "ID".to_string(),
+            TableColumn::Status => t!("table.status"),
+            TableColumn::Priority => t!("table.priority"),
+            TableColumn::Title => t!("table.title"),
+            TableColumn::Tags => t!("table.tags"),
+            TableColumn::Project => t!("table.project"),
+            TableColumn::Due => t!("table.due"),
+            TableColumn::Created => t!("table.created"),
+            TableColumn::Urgency => t!("table.urgency"),
+            TableColumn::Estimate => t!("table.estimate"),
+            TableColumn::Start => t!("table.start"),
+            TableColumn::Wait => t!("table.wait"),
+            TableColumn::Checklist => t!("table.checklist"),
+            TableColumn::Field(name) => name.clone(),
+        }
+    }
+
+    /// 端末の幅に収まらない場合に縮める列か
+    fn is_flexible(&self) -> bool {
+        matches!(self, TableColumn::Title | TableColumn::Tags | TableColumn::Project | TableColumn::Field(_))
+    }
+
+    /// 右寄せで表示する列か
+    fn is_numeric(&self) -> bool {
+        matches!(self, TableColumn::Id | TableColumn::Urgency)
+    }
+
+    /// タスクの値
+    fn value(&self, task: &Task, scorer: Option<&UrgencyScorer>) -> String {
+        match self {
+            TableColumn::Id => task.id.map(|id| id.to_string()).unwrap_or_default(),
+            TableColumn::Status if task.completed => "done".to_string(),
+            TableColumn::Status => task.status.clone(),
+            TableColumn::Priority => task.priority.label(),
+            TableColumn::Title => task.title.clone(),
+            TableColumn::Tags => task.tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" "),
+            TableColumn::Project => task.project.clone().unwrap_or_default(),
+            TableColumn::Due => task.due_date.map(format_datetime).unwrap_or_default(),
+            TableColumn::Created => format_datetime(task.created_at),
+            TableColumn::Urgency => scorer
+                .map(|scorer| format!("{:.1}", scorer.score(task).total))
+                .unwrap_or_default(),
+            TableColumn::Estimate => task.estimate().map(format_duration).unwrap_or_default(),
+            TableColumn::Start => task.start_date.map(format_datetime).unwrap_or_default(),
+            TableColumn::Wait => task.wait_until.map(format_datetime).unwrap_or_default(),
+            TableColumn::Checklist => checklist_progress(task),
+            TableColumn::Field(name) => task.custom_fields.get(name).cloned().unwrap_or_default(),
+        }
+    }
+}
+
+/// タスクの一覧を列をそろえた表の行に変換する（先頭は見出し）
+///
+/// 合計の幅が max_width を超える場合は、タグ・カスタムフィールド・タイトルの順に列を縮め、
+/// 全角文字の表示幅を考慮して切り詰める。
+pub fn format_table(
+    tasks: &[Task],
+    columns: &[TableColumn],
+    scorer: Option<&UrgencyScorer>,
+    max_width: usize,
+) -> Vec<String> {
+    let headers: Vec<String> = columns.iter().map(TableColumn::header).collect();
+    let rows: Vec<Vec<String>> = tasks
+        .iter()
+        .map(|task| columns.iter().map(|column| column.value(task, scorer)).collect())
+        .collect();
+
+    // 各列の幅（見出しと値の最大の表示幅）
+    let mut widths: Vec<usize> = headers.iter().map(|h| display_width(h)).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(display_width(cell));
+        }
+    }
+
+    // 端末の幅に収まるように縮める
+    let total = widths.iter().sum::<usize>() + SEPARATOR.len() * (columns.len() - 1);
+    let mut excess = total.saturating_sub(max_width);
+    let shrink_order = columns
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.is_flexible() && **c != TableColumn::Title)
+        .chain(columns.iter().enumerate().filter(|(_, c)| **c == TableColumn::Title))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    for i in shrink_order {
+        if excess == 0 {
+            break;
+        }
+        let reducible = widths[i].saturating_sub(MIN_FLEX_WIDTH);
+        let reduce = reducible.min(excess);
+        widths[i] -= reduce;
+        excess -= reduce;
+    }
+
+    std::iter::once(headers)
+        .chain(rows)
+        .map(|row| {
+            let line = row
+                .iter()
+                .zip(columns)
+                .zip(&widths)
+                .map(|((cell, column), width)| {
+                    let cell = truncate_to_width(cell, *width);
+                    if column.is_numeric() {
+                        format!("{}{}", " ".repeat(width - display_width(&cell)), cell)
+                    } else {
+                        pad_to_width(&cell, *width)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(SEPARATOR);
+            line.trim_end().to_string()
+        })
+        .collect()
+}
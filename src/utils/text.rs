@@ -0,0 +1,48 @@
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// 端末上での表示幅を取得（全角文字は 2 として数える）
+pub fn display_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// 表示幅が width に収まるように切り詰める（切り詰めた場合は末尾に "…" を付ける）
+pub fn truncate_to_width(text: &str, width: usize) -> String {
+    if display_width(text) <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+
+    let mut result = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        // "…" の分を残しておく
+        if used + w > width - 1 {
+            break;
+        }
+        result.push(c);
+        used += w;
+    }
+    result.push('…');
+    result
+}
+
+/// 表示幅が width になるように右側を空白で埋める
+pub fn pad_to_width(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(display_width(text));
+    format!("{}{}", text, " ".repeat(padding))
+}
+
+/// 端末の幅を取得（取得できない場合は COLUMNS 環境変数、それもなければ 100）
+pub fn terminal_width() -> usize {
+    if let Some((terminal_size::Width(width), _)) = terminal_size::terminal_size() {
+        return width as usize;
+    }
+
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.parse().ok())
+        .unwrap_or(100)
+}
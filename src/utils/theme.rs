@@ -0,0 +1,352 @@
+//! 出力の色と記号
+//!
+//! 色は `Role`（期限切れ・完了などの意味）ごとにパレットで定義し、表示する側は
+//! `paint` で役割を指定する。記号は `glyph` で取得し、ASCII のみの表示にも切り替えられる。
+
+use colored::{Color, ColoredString, Colorize};
+use serde::Deserialize;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+/// 色を付けるかどうかの指定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    /// 端末に出力する場合のみ（NO_COLOR / CLICOLOR_FORCE も考慮）
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "auto" => Some(ColorChoice::Auto),
+            "always" => Some(ColorChoice::Always),
+            "never" => Some(ColorChoice::Never),
+            _ => None,
+        }
+    }
+
+    /// 環境変数の値と出力先が端末かどうかから、色を付けるかを決める
+    ///
+    /// auto の場合、NO_COLOR が空でなければ色なし、CLICOLOR_FORCE が "0" 以外なら色あり、
+    /// どちらもなければ端末に出力するときのみ色を付ける。
+    pub fn enabled(&self, no_color: Option<&str>, clicolor_force: Option<&str>, is_terminal: bool) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                if no_color.is_some_and(|v| !v.is_empty()) {
+                    false
+                } else if clicolor_force.is_some_and(|v| !v.is_empty() && v != "0") {
+                    true
+                } else {
+                    is_terminal
+                }
+            }
+        }
+    }
+}
+
+/// 色のパレット
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Palette {
+    #[default]
+    Default,
+    /// 明るい色と太字で背景とのコントラストを高めたパレット
+    HighContrast,
+    /// 赤と緑を使わない色覚多様性に配慮したパレット（Okabe-Ito の配色）
+    Colorblind,
+}
+
+/// 表示する内容の意味（パレットごとに色が決まる）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    PriorityHigh,
+    PriorityMedium,
+    PriorityLow,
+    /// 期限切れ
+    Overdue,
+    /// 今日が期限
+    DueToday,
+    /// 完了・成功
+    Done,
+    /// タスクID
+    Id,
+    /// タグ・フィールド名
+    Tag,
+    /// 件数などの補足情報
+    Info,
+    /// 目立たせない情報（待機中・完了済みの行など）
+    Muted,
+    Warning,
+    Error,
+    /// 背景色で強調（カレンダーの期限切れの日など）
+    Highlight,
+}
+
+/// 文字の装飾
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Style {
+    pub color: Option<Color>,
+    pub background: Option<Color>,
+    pub bold: bool,
+    pub dimmed: bool,
+}
+
+impl Style {
+    const fn fg(color: Color) -> Self {
+        Self { color: Some(color), background: None, bold: false, dimmed: false }
+    }
+
+    const fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    const fn on(mut self, background: Color) -> Self {
+        self.background = Some(background);
+        self
+    }
+
+    const PLAIN: Self = Self { color: None, background: None, bold: false, dimmed: false };
+    const DIMMED: Self = Self { color: None, background: None, bold: false, dimmed: true };
+
+    /// 文字列に装飾を適用
+    pub fn apply(&self, text: &str) -> ColoredString {
+        let mut result = text.normal();
+        if let Some(color) = self.color {
+            result = result.color(color);
+        }
+        if let Some(background) = self.background {
+            result = result.on_color(background);
+        }
+        if self.bold {
+            result = result.bold();
+        }
+        if self.dimmed {
+            result = result.dimmed();
+        }
+        result
+    }
+}
+
+// Okabe-Ito の配色
+const VERMILLION: Color = Color::TrueColor { r: 213, g: 94, b: 0 };
+const ORANGE: Color = Color::TrueColor { r: 230, g: 159, b: 0 };
+const SKY_BLUE: Color = Color::TrueColor { r: 86, g: 180, b: 233 };
+const BLUE: Color = Color::TrueColor { r: 0, g: 114, b: 178 };
+const REDDISH_PURPLE: Color = Color::TrueColor { r: 204, g: 121, b: 167 };
+
+impl Palette {
+    /// 役割に対応する装飾
+    pub fn style(&self, role: Role) -> Style {
+        match self {
+            Palette::Default => match role {
+                Role::PriorityHigh => Style::fg(Color::Red).bold(),
+                Role::PriorityMedium => Style::fg(Color::Yellow),
+                Role::PriorityLow => Style::PLAIN,
+                Role::Overdue => Style::fg(Color::Red),
+                Role::DueToday => Style::fg(Color::Yellow),
+                Role::Done => Style::fg(Color::Green),
+                Role::Id => Style::fg(Color::Blue),
+                Role::Tag => Style::fg(Color::Cyan),
+                Role::Info => Style::fg(Color::Blue),
+                Role::Muted => Style::DIMMED,
+                Role::Warning => Style::fg(Color::Yellow),
+                Role::Error => Style::fg(Color::Red).bold(),
+                Role::Highlight => Style::PLAIN.on(Color::Red),
+            },
+            Palette::HighContrast => match role {
+                Role::PriorityHigh => Style::fg(Color::BrightRed).bold(),
+                Role::PriorityMedium => Style::fg(Color::BrightYellow).bold(),
+                Role::PriorityLow => Style::fg(Color::BrightWhite),
+                Role::Overdue => Style::fg(Color::BrightRed).bold(),
+                Role::DueToday => Style::fg(Color::BrightYellow).bold(),
+                Role::Done => Style::fg(Color::BrightGreen).bold(),
+                Role::Id => Style::fg(Color::BrightCyan).bold(),
+                Role::Tag => Style::fg(Color::BrightMagenta),
+                Role::Info => Style::fg(Color::BrightBlue).bold(),
+                // 薄い表示は読みにくいため白にする
+                Role::Muted => Style::fg(Color::White),
+                Role::Warning => Style::fg(Color::BrightYellow).bold(),
+                Role::Error => Style::fg(Color::BrightRed).bold(),
+                Role::Highlight => Style::fg(Color::Black).on(Color::BrightYellow).bold(),
+            },
+            Palette::Colorblind => match role {
+                Role::PriorityHigh => Style::fg(VERMILLION).bold(),
+                Role::PriorityMedium => Style::fg(ORANGE),
+                Role::PriorityLow => Style::PLAIN,
+                Role::Overdue => Style::fg(VERMILLION).bold(),
+                Role::DueToday => Style::fg(ORANGE),
+                Role::Done => Style::fg(BLUE),
+                Role::Id => Style::fg(SKY_BLUE),
+                Role::Tag => Style::fg(REDDISH_PURPLE),
+                Role::Info => Style::fg(SKY_BLUE),
+                Role::Muted => Style::DIMMED,
+                Role::Warning => Style::fg(ORANGE).bold(),
+                Role::Error => Style::fg(VERMILLION).bold(),
+                Role::Highlight => Style::fg(Color::Black).on(ORANGE),
+            },
+        }
+    }
+}
+
+/// 表示に使う記号
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Glyph {
+    /// 完了したタスク
+    Done,
+    /// 期限切れのタスク
+    Overdue,
+    /// 今日が期限のタスク
+    DueToday,
+    /// その他の未完了タスク
+    Pending,
+    Waiting,
+    Blocked,
+    /// 計測中のタイマー
+    Running,
+    Paused,
+    Warning,
+    /// タイムラインの開始〜期限
+    Span,
+    /// タイムラインの期限のみ
+    DueMark,
+    /// タイムラインの開始のみ
+    StartMark,
+    /// タイムラインの今日の位置
+    TodayLine,
+    /// タイムラインの目盛りの今日の位置
+    TodayMarker,
+    /// 依存先を示す矢印
+    DependsOn,
+    /// 表の縦線
+    VerticalLine,
+    /// 表の横線
+    HorizontalLine,
+    /// 表の縦線と横線の交点
+    Cross,
+    /// 統計・タイムラインの見出し
+    Chart,
+    /// カレンダー・アジェンダの見出し
+    Calendar,
+    /// ポモドーロ
+    Pomodoro,
+    /// 次にやるべきタスクの見出し
+    Target,
+    /// 見積もりの見出し
+    Ruler,
+}
+
+impl Glyph {
+    pub fn unicode(&self) -> &'static str {
+        match self {
+            Glyph::Done => "[✓]",
+            Glyph::Overdue => "[!]",
+            Glyph::DueToday => "[⚑]",
+            Glyph::Pending => "[ ]",
+            Glyph::Waiting => "⏸",
+            Glyph::Blocked => "⛔",
+            Glyph::Running => "⏱",
+            Glyph::Paused => "⏸",
+            Glyph::Warning => "⚠",
+            Glyph::Span => "█",
+            Glyph::DueMark => "◆",
+            Glyph::StartMark => "▶",
+            Glyph::TodayLine => "│",
+            Glyph::TodayMarker => "▼",
+            Glyph::DependsOn => "←",
+            Glyph::VerticalLine => "│",
+            Glyph::HorizontalLine => "─",
+            Glyph::Cross => "┼",
+            Glyph::Chart => "📊",
+            Glyph::Calendar => "📅",
+            Glyph::Pomodoro => "🍅",
+            Glyph::Target => "🎯",
+            Glyph::Ruler => "📐",
+        }
+    }
+
+    pub fn ascii(&self) -> &'static str {
+        match self {
+            Glyph::Done => "[x]",
+            Glyph::Overdue => "[!]",
+            Glyph::DueToday => "[*]",
+            Glyph::Pending => "[ ]",
+            Glyph::Waiting => "~",
+            Glyph::Blocked => "X",
+            Glyph::Running => ">",
+            Glyph::Paused => "||",
+            Glyph::Warning => "!",
+            Glyph::Span => "#",
+            Glyph::DueMark => "*",
+            Glyph::StartMark => ">",
+            Glyph::TodayLine => "|",
+            Glyph::TodayMarker => "v",
+            Glyph::DependsOn => "<-",
+            Glyph::VerticalLine => "|",
+            Glyph::HorizontalLine => "-",
+            Glyph::Cross => "+",
+            Glyph::Chart => "#",
+            Glyph::Calendar => "#",
+            Glyph::Pomodoro => "*",
+            Glyph::Target => ">",
+            Glyph::Ruler => "#",
+        }
+    }
+}
+
+/// 現在のパレット（Palette の順番）
+static PALETTE: AtomicU8 = AtomicU8::new(0);
+
+/// ASCII の記号のみで表示するか
+static ASCII: AtomicBool = AtomicBool::new(false);
+
+/// 色・パレット・記号の設定を反映する
+///
+/// 色の指定は --color、設定ファイルの color の順に優先する。
+pub fn init(color: Option<&str>, config_color: ColorChoice, palette: Palette, ascii: bool) {
+    let choice = color.and_then(ColorChoice::parse).unwrap_or(config_color);
+    let enabled = choice.enabled(
+        std::env::var("NO_COLOR").ok().as_deref(),
+        std::env::var("CLICOLOR_FORCE").ok().as_deref(),
+        std::io::stdout().is_terminal(),
+    );
+    colored::control::set_override(enabled);
+    set_palette(palette);
+    set_ascii(ascii);
+}
+
+pub fn set_palette(palette: Palette) {
+    PALETTE.store(palette as u8, Ordering::Relaxed);
+}
+
+pub fn palette() -> Palette {
+    match PALETTE.load(Ordering::Relaxed) {
+        1 => Palette::HighContrast,
+        2 => Palette::Colorblind,
+        _ => Palette::Default,
+    }
+}
+
+pub fn set_ascii(ascii: bool) {
+    ASCII.store(ascii, Ordering::Relaxed);
+}
+
+/// 文字列を役割に対応する色で表示
+pub fn paint(text: &str, role: Role) -> ColoredString {
+    palette().style(role).apply(text)
+}
+
+/// 記号を取得（ASCII のみの表示では代わりの文字）
+pub fn glyph(glyph: Glyph) -> &'static str {
+    if ASCII.load(Ordering::Relaxed) {
+        glyph.ascii()
+    } else {
+        glyph.unicode()
+    }
+}
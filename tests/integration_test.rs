@@ -2,13 +2,48 @@
 extern crate task_manager_rs;
 extern crate tempfile;
 
-use task_manager_rs::models::task::{Task, Priority};
+use task_manager_rs::models::task::{Task, Priority, MAX_ESTIMATE_MINUTES};
+use task_manager_rs::models::field::{FieldCondition, FieldDefinition, FieldType};
+use task_manager_rs::models::pomodoro::Pomodoro;
+use task_manager_rs::models::time_entry::TimeEntry;
+use task_manager_rs::models::attachment::{Attachment, AttachmentKind};
+use task_manager_rs::models::checklist::ChecklistItem;
+use task_manager_rs::models::note::Note;
+use task_manager_rs::config::Config;
+use task_manager_rs::error::TaskError;
+use task_manager_rs::models::urgency::UrgencyScorer;
+use task_manager_rs::utils::sort::sort_tasks;
+use task_manager_rs::utils::date::start_of_week;
+use task_manager_rs::commands::agenda::{build_agenda, AgendaSection};
+use task_manager_rs::commands::calendar::{month_weeks, parse_month, summarize_days};
+use task_manager_rs::commands::board::{apply_move, build_board, BoardGrouping, DONE_COLUMN};
+use task_manager_rs::commands::timeline::{dependency_conflicts, render_bar, timeline_span};
+use task_manager_rs::utils::text::{display_width, pad_to_width, truncate_to_width};
+use task_manager_rs::utils::table::{format_table, TableColumn};
+use task_manager_rs::utils::format::Template;
+use task_manager_rs::utils::duration::{format_duration, parse_duration, parse_estimate};
+use task_manager_rs::commands::report::summarize_estimates;
+use task_manager_rs::utils::date::get_relative_time;
+use task_manager_rs::utils::theme::{ColorChoice, Glyph, Palette, Role};
+use task_manager_rs::i18n::{catalog_entries, translate, translate_plural, with_locale, Locale};
+use chrono::{Duration, Local, NaiveDate, TimeZone, Weekday};
 use task_manager_rs::db::TaskRepository;
+use task_manager_rs::cli::Cli;
 use task_manager_rs::commands;
+use clap::{CommandFactory, Parser};
+use std::collections::{BTreeMap, HashMap};
 use std::env;
+use std::sync::{Mutex, MutexGuard};
 use tempfile::TempDir;
 use std::path::Path;
 
+// テスト用DBは環境変数で切り替えるため、DBを使うテストは直列に実行する
+static DB_LOCK: Mutex<()> = Mutex::new(());
+
+fn lock_db() -> MutexGuard<'static, ()> {
+    DB_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
 // テスト用のヘルパー関数
 fn setup_test_db() -> (TempDir, String) {
     // 一時ディレクトリを作成
@@ -40,7 +75,7 @@ fn test_task_model() {
     assert_eq!(task.title, "テストタスク");
     assert_eq!(task.description, Some("これはテストタスクです".to_string()));
     assert_eq!(task.priority, Priority::High);
-    assert_eq!(task.completed, false);
+    assert!(!task.completed);
     assert_eq!(task.completed_at, None);
     assert_eq!(task.tags, vec!["テスト".to_string(), "タスク".to_string()]);
     
@@ -65,6 +100,7 @@ fn test_task_model() {
 #[test]
 fn test_repository_crud() {
     // テスト環境のセットアップ
+    let _guard = lock_db();
     let (temp_dir, _) = setup_test_db();
     let mut repo = TaskRepository::new().expect("リポジトリを作成できませんでした");
     
@@ -107,6 +143,7 @@ fn test_repository_crud() {
 #[test]
 fn test_export_import() {
     // テスト環境のセットアップ
+    let _guard = lock_db();
     let (temp_dir, _) = setup_test_db();
     let mut repo = TaskRepository::new().expect("リポジトリを作成できませんでした");
     
@@ -119,10 +156,25 @@ fn test_export_import() {
         vec!["テスト".to_string()]
     );
     
-    repo.add_task(&task).expect("タスクを追加できませんでした");
+    let first_id = repo.add_task(&task).expect("タスクを追加できませんでした");
+    
+    // 後から追加されたフィールドもすべて持つタスク
+    let mut detailed = Task::new("詳細".to_string(), None, Some(Local::now()), Priority::Low, vec![]);
+    detailed.estimate_minutes = Some(90);
+    detailed.project = Some("website".to_string());
+    detailed.status = "doing".to_string();
+    detailed.start_date = Some(Local::now() - Duration::days(1));
+    detailed.wait_until = Some(Local::now() + Duration::days(1));
+    detailed.depends_on = vec![first_id];
+    detailed.checklist = vec![ChecklistItem { text: "確認, する".to_string(), done: true }];
+    detailed.notes = vec![Note::new("メモ\n2 行目".to_string())];
+    detailed.attachments = vec![Attachment::new(AttachmentKind::Link, "https://example.com/?a=1&b=2".to_string(), None)];
+    repo.add_task(&detailed).unwrap();
     
     // エクスポート用の一時ファイル
     let export_path = temp_dir.path().join("export_test.json").to_str().unwrap().to_string();
+    let csv_path = temp_dir.path().join("export_test.csv").to_str().unwrap().to_string();
+    commands::export_tasks(csv_path.clone(), "csv".to_string()).unwrap();
     
     // エクスポートをテスト
     commands::export_tasks(
@@ -148,9 +200,1722 @@ fn test_export_import() {
     let new_repo = TaskRepository::new().unwrap();
     let tasks = new_repo.get_all_tasks(true).unwrap();
     assert!(!tasks.is_empty());
+    teardown();
+    
+    // CSV でもすべてのフィールドが往復する
+    let (csv_temp_dir, _) = setup_test_db();
+    commands::import_tasks(csv_path).unwrap();
+    let tasks = TaskRepository::new().unwrap().get_all_tasks(true).unwrap();
+    assert_eq!(tasks.len(), 2);
+    let first = tasks.iter().find(|t| t.title == "エクスポートテスト").unwrap();
+    assert_eq!(first.description.as_deref(), Some("エクスポート用テストタスク"));
+    assert_eq!(first.tags, vec!["テスト".to_string()]);
+    let imported = tasks.iter().find(|t| t.title == "詳細").unwrap();
+    assert_eq!(imported.estimate_minutes, Some(90));
+    assert_eq!(imported.project.as_deref(), Some("website"));
+    assert_eq!(imported.status, "doing");
+    assert_eq!(imported.due_date.map(|d| d.timestamp()), detailed.due_date.map(|d| d.timestamp()));
+    assert_eq!(imported.start_date.map(|d| d.timestamp()), detailed.start_date.map(|d| d.timestamp()));
+    assert_eq!(imported.wait_until.map(|d| d.timestamp()), detailed.wait_until.map(|d| d.timestamp()));
+    assert_eq!(imported.depends_on, vec![first.id.unwrap()]);
+    assert_eq!(imported.checklist, detailed.checklist);
+    assert_eq!(imported.notes[0].text, "メモ\n2 行目");
+    assert_eq!(imported.attachments[0].target, detailed.attachments[0].target);
     
     // クリーンアップ
     teardown();
     drop(temp_dir);
     drop(new_temp_dir);
-} 
+    drop(csv_temp_dir);
+}
+
+#[test]
+fn test_cli_definition() {
+    // 引数定義の重複などは clap のデバッグアサーションで検出される
+    Cli::command().debug_assert();
+}
+
+#[test]
+fn test_custom_fields() {
+    // テスト環境のセットアップ
+    let _guard = lock_db();
+    let (temp_dir, _) = setup_test_db();
+    let mut repo = TaskRepository::new().expect("リポジトリを作成できませんでした");
+    
+    // フィールドの定義
+    let points = FieldDefinition::new("story_points".to_string(), FieldType::Number, vec![])
+        .expect("フィールド定義を作成できませんでした");
+    repo.define_field(&points).expect("フィールドを定義できませんでした");
+    let size = FieldDefinition::new(
+        "size".to_string(),
+        FieldType::Enum,
+        vec!["S".to_string(), "M".to_string(), "L".to_string()],
+    ).unwrap();
+    repo.define_field(&size).unwrap();
+    assert!(repo.define_field(&size).is_err());
+    assert!(FieldDefinition::new("title".to_string(), FieldType::String, vec![]).is_err());
+    assert!(FieldDefinition::new("Notes".to_string(), FieldType::String, vec![]).is_err());
+    
+    // 型に合わない値は拒否される
+    let mut invalid = BTreeMap::new();
+    invalid.insert("story_points".to_string(), "たくさん".to_string());
+    assert!(repo.validate_custom_fields(&invalid).is_err());
+    let mut undefined = BTreeMap::new();
+    undefined.insert("customer".to_string(), "ACME".to_string());
+    assert!(repo.validate_custom_fields(&undefined).is_err());
+    
+    // 値を持つタスクの追加と取得
+    let mut task = Task::new("見積もり".to_string(), None, None, Priority::Medium, vec![]);
+    task.custom_fields.insert("story_points".to_string(), "5".to_string());
+    task.custom_fields.insert("size".to_string(), "M".to_string());
+    let task_id = repo.add_task(&task).unwrap();
+    let saved = repo.get_task(task_id).unwrap();
+    assert_eq!(saved.custom_fields.get("story_points"), Some(&"5".to_string()));
+
+    // 値は読み込んだタスクにだけ付く（一度に問い合わせる ID の数を超えても同じ）
+    let ids = repo.transaction(|repo| {
+        (0..600)
+            .map(|n| {
+                let mut other = Task::new(format!("タスク{}", n), None, None, Priority::Low, vec![]);
+                other.custom_fields.insert("story_points".to_string(), n.to_string());
+                repo.add_task(&other)
+            })
+            .collect::<Result<Vec<_>, _>>()
+    }).unwrap();
+    let all = repo.get_all_tasks(true).unwrap();
+    assert_eq!(all.len(), 601);
+    for task in &all {
+        let expected = match task.title.strip_prefix("タスク") {
+            Some(n) => n.to_string(),
+            None => "5".to_string(),
+        };
+        assert_eq!(task.custom_fields.get("story_points"), Some(&expected));
+    }
+    assert_eq!(repo.get_task(ids[599]).unwrap().custom_fields.get("story_points"), Some(&"599".to_string()));
+    repo.transaction(|repo| ids.iter().try_for_each(|id| repo.delete_task(*id))).unwrap();
+
+    // 条件による絞り込み（数値として比較される）
+    let condition = FieldCondition::parse("story_points>=10").unwrap();
+    assert!(!condition.matches(&points, Some("5")));
+    assert!(FieldCondition::parse("story_points!=3").unwrap().matches(&points, None));
+    
+    // CSVエクスポートではカスタムフィールドが列として出力され、インポートで復元される
+    let export_path = temp_dir.path().join("fields.csv").to_str().unwrap().to_string();
+    commands::export_tasks(export_path.clone(), "csv".to_string()).unwrap();
+    let content = std::fs::read_to_string(&export_path).unwrap();
+    assert!(content.lines().next().unwrap().ends_with("size,story_points"));
+    
+    repo.delete_task(task_id).unwrap();
+    commands::import_tasks(export_path).unwrap();
+    let tasks = repo.get_all_tasks(true).unwrap();
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].custom_fields.get("size"), Some(&"M".to_string()));
+    
+    // フィールドを削除すると値も削除される
+    repo.remove_field("size").unwrap();
+    let reloaded = repo.get_task(tasks[0].id.unwrap()).unwrap();
+    assert!(!reloaded.custom_fields.contains_key("size"));
+    
+    // クリーンアップ
+    teardown();
+    drop(temp_dir);
+}
+
+#[test]
+fn test_time_tracking() {
+    // テスト環境のセットアップ
+    let _guard = lock_db();
+    let (temp_dir, _) = setup_test_db();
+    let mut repo = TaskRepository::new().expect("リポジトリを作成できませんでした");
+    
+    let first = repo.add_task(&Task::new("設計".to_string(), None, None, Priority::High, vec![])).unwrap();
+    let second = repo.add_task(&Task::new("実装".to_string(), None, None, Priority::Medium, vec![])).unwrap();
+    
+    // タイマーは同時に 1 つまで
+    repo.start_timer(first).expect("タイマーを開始できませんでした");
+    assert!(matches!(repo.start_timer(second), Err(TaskError::TimerAlreadyRunning(id)) if id == first));
+    assert_eq!(repo.get_running_timer().unwrap().unwrap().task_id, first);
+    repo.stop_timer().expect("タイマーを停止できませんでした");
+    assert!(repo.get_running_timer().unwrap().is_none());
+    assert!(matches!(repo.stop_timer(), Err(TaskError::NoTimerRunning)));
+    
+    // 手動での記録
+    let duration = parse_duration("1h30m").unwrap();
+    assert_eq!(duration, Duration::minutes(90));
+    assert!(parse_duration("1x").is_err());
+    assert!(parse_duration("30").is_ok());
+    let started_at = Local::now() - Duration::days(30);
+    repo.add_time_entry(&TimeEntry::new(second, started_at, Some(started_at + duration))).unwrap();
+    assert!(repo.add_time_entry(&TimeEntry::new(second, started_at, Some(started_at))).is_err());
+    assert_eq!(repo.get_tracked_time(second).unwrap(), duration);
+    assert_eq!(format_duration(duration), "1h30m");
+    
+    // 期間による絞り込み
+    let recent = repo.get_time_entries_between(Some(Local::now() - Duration::days(7)), None).unwrap();
+    assert!(recent.iter().all(|e| e.task_id == first));
+    
+    // クリーンアップ
+    teardown();
+    drop(temp_dir);
+}
+
+#[test]
+fn test_estimates() {
+    // テスト環境のセットアップ
+    let _guard = lock_db();
+    let (temp_dir, _) = setup_test_db();
+    let mut repo = TaskRepository::new().expect("リポジトリを作成できませんでした");
+    
+    // 見積もり付きタスクの保存と更新
+    let mut task = Task::new("見積もりテスト".to_string(), None, None, Priority::Medium, vec![]);
+    task.estimate_minutes = Some(parse_duration("3h").unwrap().num_minutes());
+    let task_id = repo.add_task(&task).unwrap();
+    let mut saved = repo.get_task(task_id).unwrap();
+    assert_eq!(saved.estimate_minutes, Some(180));
+    
+    saved.estimate_minutes = Some(90);
+    repo.update_task(&saved).unwrap();
+    assert_eq!(repo.get_task(task_id).unwrap().estimate_minutes, Some(90));
+    
+    // タスクごとの合計作業時間
+    let ended_at = Local::now();
+    repo.add_time_entry(&TimeEntry::new(task_id, ended_at - Duration::minutes(40), Some(ended_at))).unwrap();
+    repo.add_time_entry(&TimeEntry::new(task_id, ended_at - Duration::hours(3), Some(ended_at - Duration::hours(2)))).unwrap();
+    let tracked = repo.get_all_tracked_times().unwrap();
+    assert_eq!(tracked.get(&task_id), Some(&Duration::minutes(100)));
+    
+    // 見積もりは JSON に含まれ、見積もりのない古いデータも読み込める
+    let json = serde_json::to_string(&saved).unwrap();
+    let restored: Task = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.estimate_minutes, Some(90));
+    let legacy = json.replace(",\"estimate_minutes\":90", "");
+    assert!(!legacy.contains("estimate_minutes"));
+    let restored: Task = serde_json::from_str(&legacy).unwrap();
+    assert_eq!(restored.estimate_minutes, None);
+    
+    // 範囲外の見積もりは指定できず、データにあっても表示で異常終了しない
+    assert_eq!(parse_estimate("1h30m").unwrap(), 90);
+    assert!(parse_estimate("40000d").is_err());
+    assert!(parse_estimate(&format!("{}", MAX_ESTIMATE_MINUTES + 1)).is_err());
+    let mut huge = saved.clone();
+    huge.estimate_minutes = Some(i64::MAX);
+    assert_eq!(huge.estimate(), None);
+    task_manager_rs::utils::format::format_task(&huge, true);
+    let import_path = temp_dir.path().join("huge.json");
+    std::fs::write(&import_path, serde_json::to_string(&vec![huge]).unwrap()).unwrap();
+    commands::import_tasks(import_path.to_str().unwrap().to_string()).unwrap();
+    let imported = repo.get_all_tasks(true).unwrap().into_iter().max_by_key(|t| t.id).unwrap();
+    assert_eq!(imported.estimate_minutes, None);
+    repo.delete_task(imported.id.unwrap()).unwrap();
+    
+    // 実績 / 見積もり の比率（記録がなければ作成から完了までの経過時間）
+    let completed_at = Local::now();
+    let mut tracked_task = Task::new("記録あり".to_string(), None, None, Priority::Medium, vec!["a".to_string()]);
+    tracked_task.id = Some(1001);
+    tracked_task.estimate_minutes = Some(60);
+    tracked_task.project = Some("web".to_string());
+    tracked_task.completed = true;
+    tracked_task.completed_at = Some(completed_at);
+    let mut elapsed_task = Task::new("記録なし".to_string(), None, None, Priority::Medium, vec!["a".to_string()]);
+    elapsed_task.id = Some(1002);
+    elapsed_task.estimate_minutes = Some(60);
+    elapsed_task.created_at = completed_at - Duration::minutes(120);
+    elapsed_task.completed = true;
+    elapsed_task.completed_at = Some(completed_at);
+    let mut open_task = Task::new("未完了".to_string(), None, None, Priority::Medium, vec![]);
+    open_task.id = Some(1003);
+    open_task.estimate_minutes = Some(30);
+    let tasks = vec![tracked_task, elapsed_task, open_task];
+    let tracked = HashMap::from([(1001, Duration::minutes(90)), (1003, Duration::minutes(15))]);
+    
+    let report = summarize_estimates(&tasks, &tracked, false);
+    assert_eq!(report.overall.count, 2);
+    assert_eq!(report.overall.estimate, Duration::minutes(120));
+    assert_eq!(report.overall.actual, Duration::minutes(210));
+    assert!((report.overall.ratio() - 1.75).abs() < 1e-9);
+    let tag = &report.by_tag["#a"];
+    assert_eq!((tag.count, tag.over_count), (2, 2));
+    assert!(tag.is_chronically_underestimated());
+    let project = &report.by_project["web"];
+    assert!((project.ratio() - 1.5).abs() < 1e-9);
+    assert!(!project.is_chronically_underestimated());
+    
+    // --all では未完了のタスクも記録した作業時間で比較する
+    let report = summarize_estimates(&tasks, &tracked, true);
+    assert_eq!(report.overall.count, 3);
+    assert!((report.overall.ratio() - 1.5).abs() < 1e-9);
+    assert_eq!(report.by_tag.len(), 2);
+    
+    // クリーンアップ
+    teardown();
+    drop(temp_dir);
+}
+
+#[test]
+fn test_pomodoros() {
+    // テスト環境のセットアップ
+    let _guard = lock_db();
+    let (temp_dir, _) = setup_test_db();
+    let mut repo = TaskRepository::new().expect("リポジトリを作成できませんでした");
+    
+    let task_id = repo.add_task(&Task::new("集中作業".to_string(), None, None, Priority::High, vec![])).unwrap();
+    let ended_at = Local::now();
+    for interruptions in [0, 2] {
+        let pomodoro = Pomodoro::new(task_id, ended_at - Duration::minutes(25), ended_at, 25, interruptions);
+        repo.record_pomodoro(&pomodoro).expect("ポモドーロを記録できませんでした");
+    }
+    assert!(repo.record_pomodoro(&Pomodoro::new(9999, ended_at, ended_at, 25, 0)).is_err());
+    
+    // ポモドーロは作業時間としても記録される
+    let pomodoros = repo.get_pomodoros(task_id).unwrap();
+    assert_eq!(pomodoros.len(), 2);
+    assert_eq!(pomodoros.iter().map(|p| p.interruptions).sum::<i64>(), 2);
+    assert_eq!(repo.get_tracked_time(task_id).unwrap(), Duration::minutes(50));
+    
+    // 統計情報
+    let stats = repo.get_stats().unwrap();
+    assert_eq!(stats.get("pomodoros"), Some(&2));
+    assert_eq!(repo.get_pomodoro_counts(5).unwrap(), vec![(task_id, "集中作業".to_string(), 2)]);
+    
+    // クリーンアップ
+    teardown();
+    drop(temp_dir);
+}
+
+#[test]
+fn test_dependencies_and_urgency() {
+    // テスト環境のセットアップ
+    let _guard = lock_db();
+    let (temp_dir, _) = setup_test_db();
+    let mut repo = TaskRepository::new().expect("リポジトリを作成できませんでした");
+    
+    let base = repo.add_task(&Task::new("土台".to_string(), None, None, Priority::Low, vec![])).unwrap();
+    let mut dependent = Task::new("上物".to_string(), None, None, Priority::High, vec![]);
+    dependent.depends_on = vec![base];
+    let dependent_id = repo.add_task(&dependent).unwrap();
+    assert_eq!(repo.get_task(dependent_id).unwrap().depends_on, vec![base]);
+    
+    // 自己依存・循環依存・存在しないタスクへの依存は拒否される
+    let mut base_task = repo.get_task(base).unwrap();
+    base_task.depends_on = vec![dependent_id];
+    assert!(repo.update_task(&base_task).is_err());
+    base_task.depends_on = vec![base];
+    assert!(repo.update_task(&base_task).is_err());
+    assert!(repo.validate_dependencies(None, &[9999]).is_err());
+    
+    // 依存先が未完了の間はブロックされ、緊急度が下がる
+    let config = Config::parse("[urgency]\nblocked = -10.0\n[urgency.tags]\nurgent = 3.0\n").unwrap();
+    assert_eq!(config.urgency.priority, 6.0);
+    let scorer = UrgencyScorer::new(config.urgency.clone(), repo.get_blocked_task_ids().unwrap());
+    let dependent = repo.get_task(dependent_id).unwrap();
+    assert!(scorer.is_blocked(&dependent));
+    let urgency = scorer.score(&dependent);
+    assert!(urgency.components.iter().any(|c| c.label == "ブロック中" && c.value == -10.0));
+    assert!(urgency.explain().contains("- ブロック中 10.0"));
+    
+    repo.complete_task(base).unwrap();
+    let scorer = UrgencyScorer::new(config.urgency.clone(), repo.get_blocked_task_ids().unwrap());
+    assert!(!scorer.is_blocked(&dependent));
+    
+    // 期限切れ・タグの重みが加算される
+    let mut overdue = Task::new("期限切れ".to_string(), None, Some(Local::now() - Duration::days(3)), Priority::Low, vec!["urgent".to_string()]);
+    overdue.id = Some(9999);
+    let plain = Task::new("通常".to_string(), None, None, Priority::Low, vec![]);
+    assert!(scorer.score(&overdue).total > scorer.score(&plain).total + 12.0);
+    
+    // 緊急度による並び替え
+    let mut tasks = vec![plain.clone(), overdue.clone()];
+    sort_tasks(&mut tasks, "urgency", &[], Some(&scorer)).unwrap();
+    assert_eq!(tasks[0].title, "期限切れ");
+    sort_tasks(&mut tasks, "-urgency", &[], Some(&scorer)).unwrap();
+    assert_eq!(tasks[0].title, "通常");
+    
+    // クリーンアップ
+    teardown();
+    drop(temp_dir);
+}
+
+#[test]
+fn test_agenda() {
+    // 2026-11-04 は水曜日
+    let today = NaiveDate::from_ymd_opt(2026, 11, 4).unwrap();
+    let due = |days: i64| {
+        let date = today + Duration::days(days);
+        Some(Local.from_local_datetime(&date.and_hms_opt(12, 0, 0).unwrap()).unwrap())
+    };
+    
+    // 週の始まりは設定で変更できる
+    assert_eq!(Config::default().week_start, Weekday::Mon);
+    assert_eq!(start_of_week(today, Weekday::Mon), NaiveDate::from_ymd_opt(2026, 11, 2).unwrap());
+    let config = Config::parse("week_start = \"sunday\"\n").unwrap();
+    assert_eq!(config.week_start, Weekday::Sun);
+    assert_eq!(start_of_week(today, config.week_start), NaiveDate::from_ymd_opt(2026, 11, 1).unwrap());
+    assert!(Config::parse("week_start = \"someday\"\n").is_err());
+    
+    let mut done = Task::new("完了".to_string(), None, due(0), Priority::Low, vec![]);
+    done.complete();
+    let tasks = vec![
+        Task::new("期限切れ".to_string(), None, due(-2), Priority::Low, vec![]),
+        Task::new("今日".to_string(), None, due(0), Priority::Low, vec![]),
+        Task::new("明日".to_string(), None, due(1), Priority::Low, vec![]),
+        Task::new("土曜".to_string(), None, due(3), Priority::Low, vec![]),
+        Task::new("来週".to_string(), None, due(10), Priority::Low, vec![]),
+        Task::new("いつか".to_string(), None, None, Priority::Low, vec![]),
+        done,
+    ];
+    
+    // 日曜日まで日ごとに表示し、それより後は「それ以降」にまとめる
+    let last_day = start_of_week(today, Weekday::Mon) + Duration::days(6);
+    let agenda = build_agenda(tasks.clone(), today, last_day);
+    let sections: Vec<AgendaSection> = agenda.iter().map(|(section, _)| *section).collect();
+    assert_eq!(sections, vec![
+        AgendaSection::Overdue,
+        AgendaSection::Today,
+        AgendaSection::Tomorrow,
+        AgendaSection::Day(today + Duration::days(3)),
+        AgendaSection::Later,
+        AgendaSection::Someday,
+    ]);
+    assert_eq!(agenda[1].1.len(), 1);
+    assert_eq!(agenda[3].0.label(), "2026-11-07（土）");
+    assert_eq!(agenda[4].1[0].title, "来週");
+    
+    // 今日のタスクがなくても「今日」は表示される
+    let agenda = build_agenda(vec![tasks[5].clone()], today, today);
+    assert_eq!(agenda.len(), 2);
+    assert!(agenda[0].1.is_empty());
+    assert_eq!(agenda[1].0, AgendaSection::Someday);
+}
+
+#[test]
+fn test_calendar() {
+    // 月の解析と週ごとの分割（2026-11-01 は日曜日）
+    let month = parse_month("2026-11").unwrap();
+    assert_eq!(month, NaiveDate::from_ymd_opt(2026, 11, 1).unwrap());
+    assert!(parse_month("2026-13").is_err());
+    
+    let weeks = month_weeks(month, Weekday::Mon);
+    assert_eq!(weeks.len(), 6);
+    assert_eq!(weeks[0][6], Some(month));
+    assert!(weeks[0][..6].iter().all(|d| d.is_none()));
+    assert_eq!(weeks[5][0], NaiveDate::from_ymd_opt(2026, 11, 30));
+    let weeks = month_weeks(month, Weekday::Sun);
+    assert_eq!(weeks.len(), 5);
+    assert_eq!(weeks[0][0], Some(month));
+    
+    // 日ごとの集計（最も高い優先度と期限切れ、完了済みは除外）
+    let yesterday = Local::now() - Duration::days(1);
+    let tomorrow = Local::now() + Duration::days(1);
+    let mut done = Task::new("完了".to_string(), None, Some(tomorrow), Priority::High, vec![]);
+    done.complete();
+    let tasks = vec![
+        Task::new("期限切れ".to_string(), None, Some(yesterday), Priority::Low, vec![]),
+        Task::new("明日 低".to_string(), None, Some(tomorrow), Priority::Low, vec![]),
+        Task::new("明日 中".to_string(), None, Some(tomorrow), Priority::Medium, vec![]),
+        Task::new("期限なし".to_string(), None, None, Priority::High, vec![]),
+        done,
+    ];
+    let summaries = summarize_days(&tasks);
+    assert_eq!(summaries.len(), 2);
+    let past = &summaries[&yesterday.date_naive()];
+    assert_eq!(past.count, 1);
+    assert!(past.overdue);
+    let next = &summaries[&tomorrow.date_naive()];
+    assert_eq!(next.count, 2);
+    assert_eq!(next.highest, Some(Priority::Medium));
+    assert!(!next.overdue);
+    
+    // 全角文字を考慮した切り詰めと空白埋め
+    assert_eq!(display_width("日本語abc"), 9);
+    assert_eq!(truncate_to_width("日本語のタスク", 7), "日本語…");
+    assert_eq!(truncate_to_width("short", 7), "short");
+    assert_eq!(display_width(&pad_to_width("日本", 7)), 7);
+}
+
+#[test]
+fn test_board() {
+    let _guard = lock_db();
+    let (temp_dir, _) = setup_test_db();
+    let mut repo = TaskRepository::new().unwrap();
+    
+    let config = Config::parse(
+        "[board]\nstatuses = [\"todo\", \"doing\"]\ntags = [\"frontend\", \"backend\"]\n[board.wip]\ndoing = 1\nlow = 2\n",
+    ).unwrap();
+    let board = &config.board;
+    assert!(BoardGrouping::parse("owner").is_err());
+    
+    // ステータスは保存され、既定は todo
+    let first = repo.add_task(&Task::new("A".to_string(), None, None, Priority::High, vec!["frontend".to_string(), "ui".to_string()])).unwrap();
+    let second = repo.add_task(&Task::new("B".to_string(), None, None, Priority::Low, vec![])).unwrap();
+    let mut task = repo.get_task(first).unwrap();
+    assert_eq!(task.status, "todo");
+    
+    apply_move(&mut task, "doing", BoardGrouping::Status, board).unwrap();
+    repo.update_task(&task).unwrap();
+    assert_eq!(repo.get_task(first).unwrap().status, "doing");
+    assert!(apply_move(&mut task, "review", BoardGrouping::Status, board).is_err());
+    
+    // WIP 制限を超えた列
+    let mut task = repo.get_task(second).unwrap();
+    apply_move(&mut task, "doing", BoardGrouping::Status, board).unwrap();
+    repo.update_task(&task).unwrap();
+    let columns = build_board(&repo.get_all_tasks(false).unwrap(), BoardGrouping::Status, board);
+    assert_eq!(columns.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(), vec!["todo", "doing"]);
+    assert_eq!(columns[1].tasks.len(), 2);
+    assert_eq!(columns[1].tasks[0].title, "A");
+    assert!(columns[1].is_over_limit());
+    assert!(!columns[0].is_over_limit());
+    
+    // done への移動で完了し、完了列に表示される
+    apply_move(&mut task, DONE_COLUMN, BoardGrouping::Status, board).unwrap();
+    assert!(task.completed);
+    repo.update_task(&task).unwrap();
+    let columns = build_board(&repo.get_all_tasks(true).unwrap(), BoardGrouping::Status, board);
+    assert_eq!(columns.last().unwrap().name, DONE_COLUMN);
+    assert!(!columns[1].is_over_limit());
+    apply_move(&mut task, "todo", BoardGrouping::Status, board).unwrap();
+    assert!(!task.completed);
+    
+    // タグの列間の移動は列のタグだけを置き換える
+    let mut task = repo.get_task(first).unwrap();
+    assert_eq!(apply_move(&mut task, "backend", BoardGrouping::Tag, board).unwrap(), "backend");
+    assert_eq!(task.tags, vec!["ui".to_string(), "backend".to_string()]);
+    assert!(apply_move(&mut task, "ui", BoardGrouping::Tag, board).is_err());
+    assert!(apply_move(&mut task, "ui", BoardGrouping::Tag, &Config::default().board).is_err());
+    let columns = build_board(&[task.clone(), repo.get_task(second).unwrap()], BoardGrouping::Tag, board);
+    assert_eq!(columns.iter().map(|c| c.tasks.len()).collect::<Vec<_>>(), vec![0, 1, 1]);
+    
+    // 優先度の列
+    assert_eq!(apply_move(&mut task, "低", BoardGrouping::Priority, board).unwrap(), "low");
+    assert_eq!(task.priority, Priority::Low);
+    let columns = build_board(&[task.clone()], BoardGrouping::Priority, board);
+    assert_eq!((columns[2].name.as_str(), columns[2].label.as_str()), ("low", "低"));
+    assert_eq!((columns[2].tasks.len(), columns[2].limit), (1, Some(2)));
+    apply_move(&mut task, "high", BoardGrouping::Priority, board).unwrap();
+    assert_eq!(task.priority, Priority::High);
+    
+    // クリーンアップ
+    teardown();
+    drop(temp_dir);
+}
+
+#[test]
+fn test_timeline() {
+    let _guard = lock_db();
+    let (temp_dir, _) = setup_test_db();
+    let mut repo = TaskRepository::new().unwrap();
+    
+    let day = |d: u32| NaiveDate::from_ymd_opt(2026, 11, d).unwrap();
+    let at = |d: u32| Local.from_local_datetime(&day(d).and_hms_opt(9, 0, 0).unwrap()).unwrap();
+    
+    // 開始日は保存され、期限より後の開始日は検出される
+    let mut design = Task::new("設計".to_string(), None, Some(at(5)), Priority::Medium, vec![]);
+    design.start_date = Some(at(2));
+    assert!(!design.starts_after_due());
+    let design_id = repo.add_task(&design).unwrap();
+    let design = repo.get_task(design_id).unwrap();
+    assert_eq!(design.start_date, Some(at(2)));
+    assert_eq!(timeline_span(&design), Some((day(2), day(5))));
+    
+    let mut invalid = design.clone();
+    invalid.start_date = Some(at(6));
+    assert!(invalid.starts_after_due());
+    
+    // バーの描画（1 列 = 1 日、1 列 = 2 日）
+    assert_eq!(render_bar(&design, day(1), 7, 1, day(7)), " ████ │");
+    assert_eq!(render_bar(&design, day(1), 4, 2, day(30)), "███ ");
+    let due_only = Task::new("期限のみ".to_string(), None, Some(at(3)), Priority::Low, vec![]);
+    assert_eq!(render_bar(&due_only, day(1), 5, 1, day(1)), "│ ◆  ");
+    let mut start_only = Task::new("開始のみ".to_string(), None, None, Priority::Low, vec![]);
+    start_only.start_date = Some(at(2));
+    assert_eq!(render_bar(&start_only, day(1), 3, 1, day(30)), " ▶ ");
+    assert_eq!(timeline_span(&Task::new("なし".to_string(), None, None, Priority::Low, vec![])), None);
+    
+    // 依存先が開始日より後に終わる場合のみ警告
+    let mut release = Task::new("リリース".to_string(), None, Some(at(10)), Priority::High, vec![]);
+    release.start_date = Some(at(4));
+    release.depends_on = vec![design_id];
+    let release_id = repo.add_task(&release).unwrap();
+    let tasks: HashMap<i64, Task> = repo.get_all_tasks(true).unwrap()
+        .into_iter()
+        .map(|t| (t.id.unwrap(), t))
+        .collect();
+    assert_eq!(dependency_conflicts(&tasks[&release_id], &tasks), vec![design_id]);
+    release.start_date = Some(at(6));
+    assert!(dependency_conflicts(&release, &tasks).is_empty());
+    
+    // クリーンアップ
+    teardown();
+    drop(temp_dir);
+}
+
+#[test]
+fn test_wait_and_scheduled() {
+    let _guard = lock_db();
+    let (temp_dir, _) = setup_test_db();
+    let mut repo = TaskRepository::new().unwrap();
+    
+    // 待機日時と開始予定日時は保存される
+    let mut task = Task::new("待機".to_string(), None, None, Priority::Medium, vec![]);
+    task.wait_until = Some(Local::now() + Duration::days(3));
+    task.start_date = Some(Local::now() + Duration::days(5));
+    let id = repo.add_task(&task).unwrap();
+    let task = repo.get_task(id).unwrap();
+    assert!(task.wait_until.is_some());
+    assert!(task.is_waiting());
+    
+    // 過去の待機日時・完了済みのタスクは待機中ではない
+    let mut past = task.clone();
+    past.wait_until = Some(Local::now() - Duration::hours(1));
+    assert!(!past.is_waiting());
+    let mut done = task.clone();
+    done.complete();
+    assert!(!done.is_waiting());
+    
+    // 待機中のタスクはアジェンダから除外される
+    let today = Local::now().date_naive();
+    let agenda = build_agenda(vec![task.clone(), past.clone()], today, today);
+    assert_eq!(agenda.iter().map(|(_, tasks)| tasks.len()).sum::<usize>(), 1);
+    
+    // JSON の scheduled は開始予定日時として読み込まれる
+    let json = serde_json::to_string(&task).unwrap().replace("\"start_date\"", "\"scheduled\"");
+    let parsed: Task = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.start_date, task.start_date);
+    assert_eq!(parsed.wait_until, task.wait_until);
+    
+    // クリーンアップ
+    teardown();
+    drop(temp_dir);
+}
+
+#[test]
+fn test_table_format() {
+    let definitions = vec![
+        FieldDefinition::new("points".to_string(), FieldType::Number, vec![]).unwrap(),
+    ];
+    
+    // 列の解析（カスタムフィールド名も指定可能）
+    let columns = TableColumn::parse_list("id, status,title,tags,points", &definitions).unwrap();
+    assert_eq!(columns.len(), 5);
+    assert_eq!(columns[4], TableColumn::Field("points".to_string()));
+    assert!(TableColumn::parse_list("id,owner", &definitions).is_err());
+    assert!(TableColumn::parse_list("", &definitions).is_err());
+    
+    let mut first = Task::new("日本語のとても長いタイトル".to_string(), None, None, Priority::High, vec!["仕事".to_string()]);
+    first.id = Some(7);
+    first.custom_fields.insert("points".to_string(), "3".to_string());
+    let mut second = Task::new("short".to_string(), None, None, Priority::Low, vec![]);
+    second.id = Some(12);
+    second.status = "doing".to_string();
+    second.complete();
+    let tasks = vec![first, second];
+    
+    // 全角文字を含んでも列の位置がそろう
+    let lines = format_table(&tasks, &columns, None, 200);
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("ID  状態"));
+    assert!(lines[1].starts_with(" 7  todo"));
+    assert!(lines[2].starts_with("12  done"));
+    let title_column = display_width("ID  状態  ");
+    assert_eq!(display_width(lines[1].split("日本語").next().unwrap()), title_column);
+    assert_eq!(display_width(lines[2].split("short").next().unwrap()), title_column);
+    
+    // 端末の幅に収まるように縮めて切り詰める
+    let lines = format_table(&tasks, &columns, None, 40);
+    assert!(lines.iter().all(|line| display_width(line) <= 40));
+    assert!(lines[1].contains('…'));
+}
+
+#[test]
+fn test_templates() {
+    let definitions = vec![
+        FieldDefinition::new("points".to_string(), FieldType::Number, vec![]).unwrap(),
+    ];
+    
+    let mut task = Task::new("日本語のタイトル".to_string(), None, None, Priority::High, vec!["a".to_string(), "b".to_string()]);
+    task.id = Some(7);
+    task.custom_fields.insert("points".to_string(), "3".to_string());
+    
+    // フィールド・幅・切り詰め・カスタムフィールド
+    let template = Template::parse("{id:>4}|{status:<6}|{title:.9}|{tags}|{points}|{{x}}", &definitions).unwrap();
+    assert_eq!(template.render(&task), "   7|todo  |日本語の…|#a #b|3|{x}");
+    let template = Template::parse("[{priority:^4}]", &definitions).unwrap();
+    assert_eq!(template.render(&task), "[ 高 ]");
+    
+    // 条件付きの区間
+    let template = Template::parse("{title}{?due} 期限 {due:date}{/due}{?!due} 期限なし{/}", &definitions).unwrap();
+    assert_eq!(template.render(&task), "日本語のタイトル 期限なし");
+    task.due_date = Some(Local.from_local_datetime(
+        &NaiveDate::from_ymd_opt(2026, 11, 4).unwrap().and_hms_opt(9, 0, 0).unwrap()
+    ).unwrap());
+    assert_eq!(template.render(&task), "日本語のタイトル 期限 2026-11-04");
+    task.complete();
+    assert_eq!(Template::parse("{status}", &definitions).unwrap().render(&task), "done");
+    
+    // 不正なテンプレートは表示の前にエラーになる
+    assert!(Template::parse("{owner}", &definitions).is_err());
+    assert!(Template::parse("{?due}{title}", &definitions).is_err());
+    assert!(Template::parse("{?due}{title}{/tags}", &definitions).is_err());
+    assert!(Template::parse("{title", &definitions).is_err());
+    assert!(Template::parse("title}", &definitions).is_err());
+    assert!(Template::parse("{title:relative}", &definitions).is_err());
+    assert!(Template::parse("{id:>x}", &definitions).is_err());
+    assert!(Template::parse("{id:>999999999999}", &definitions).is_err());
+    assert!(Template::parse("{title:.1001}", &definitions).is_err());
+    assert!(Template::parse("{/due}", &definitions).is_err());
+}
+
+/// ソースコード中の t!("...") / tn!("...") のキーを集める
+fn collect_message_keys(dir: &std::path::Path, keys: &mut Vec<(String, bool)>) {
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_message_keys(&path, keys);
+            continue;
+        }
+        if path.extension().and_then(|e| e.to_str()) != Some("rs") {
+            continue;
+        }
+        let source = std::fs::read_to_string(&path).unwrap();
+        for (macro_name, plural) in [("t!(\"", false), ("tn!(\"", true)] {
+            for (pos, _) in source.match_indices(macro_name) {
+                // print!(" などの一部に一致した場合は除く
+                let preceded_by_ident = source[..pos]
+                    .chars()
+                    .last()
+                    .is_some_and(|c| c.is_alphanumeric() || c == '_');
+                if preceded_by_ident {
+                    continue;
+                }
+                let rest = &source[pos + macro_name.len()..];
+                let key = &rest[..rest.find('"').unwrap()];
+                keys.push((key.to_string(), plural));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_i18n() {
+    // すべての言語に同じキーがある（件数による形は one を省略できるが other は必須）
+    let base_keys = |locale: Locale| {
+        let mut keys = catalog_entries(locale)
+            .into_keys()
+            .map(|key| match key.strip_suffix(".one") {
+                Some(base) => format!("{}.other", base),
+                None => key,
+            })
+            .collect::<Vec<_>>();
+        keys.sort();
+        keys.dedup();
+        keys
+    };
+    assert_eq!(base_keys(Locale::Ja), base_keys(Locale::En));
+    
+    // ソースコードで使われているキーはすべてのカタログにある
+    let mut keys = Vec::new();
+    collect_message_keys(&std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src"), &mut keys);
+    assert!(keys.len() > 100);
+    for locale in Locale::ALL {
+        let entries = catalog_entries(locale);
+        for (key, plural) in &keys {
+            let key = if *plural { format!("{}.other", key) } else { key.clone() };
+            assert!(entries.contains_key(&key), "{}: {} がありません", locale.code(), key);
+        }
+    }
+    
+    // 引数の置き換えと件数による形の切り替え
+    with_locale(Locale::En, || {
+        assert_eq!(translate("add.done", &[("id", &3)]), "Added task (ID: 3)");
+        assert_eq!(translate_plural("list.count", 1, &[]), "1 task:");
+        assert_eq!(translate_plural("list.count", 5, &[]), "5 tasks:");
+        assert_eq!(get_relative_time(Local::now() - Duration::days(3)), "3 days ago");
+        assert_eq!(Priority::High.label(), "High");
+        assert_eq!(TaskError::TaskNotFound(9).to_string(), "Task not found: ID 9");
+    });
+    with_locale(Locale::Ja, || {
+        assert_eq!(translate_plural("list.count", 1, &[]), "全1件のタスク:");
+        assert_eq!(get_relative_time(Local::now() - Duration::days(3)), "3日前");
+    });
+    
+    // 切り替えは入れ子にでき、抜けると元の言語に戻る
+    with_locale(Locale::En, || {
+        let count = || translate_plural("list.count", 1, &[]);
+        assert_eq!(with_locale(Locale::Ja, count), "全1件のタスク:");
+        assert_eq!(count(), "1 task:");
+    });
+    
+    // 言語名の解析と設定ファイルの lang
+    assert_eq!(Locale::parse("en_US.UTF-8"), Some(Locale::En));
+    assert_eq!(Locale::parse("ja-JP"), Some(Locale::Ja));
+    assert_eq!(Locale::parse("C"), None);
+    assert_eq!(Config::parse("lang = \"en\"").unwrap().lang.as_deref(), Some("en"));
+}
+
+#[test]
+fn test_theme() {
+    // --color auto は NO_COLOR・CLICOLOR_FORCE・端末かどうかの順に判定する
+    assert!(ColorChoice::Auto.enabled(None, None, true));
+    assert!(!ColorChoice::Auto.enabled(None, None, false));
+    assert!(!ColorChoice::Auto.enabled(Some("1"), Some("1"), true));
+    assert!(ColorChoice::Auto.enabled(Some(""), None, true));
+    assert!(ColorChoice::Auto.enabled(None, Some("1"), false));
+    assert!(!ColorChoice::Auto.enabled(None, Some("0"), false));
+    assert!(ColorChoice::Always.enabled(Some("1"), None, false));
+    assert!(!ColorChoice::Never.enabled(None, Some("1"), true));
+    assert_eq!(ColorChoice::parse("NEVER"), Some(ColorChoice::Never));
+    assert_eq!(ColorChoice::parse("sometimes"), None);
+    
+    // --color は auto / always / never のみ受け付ける
+    let cli = Cli::try_parse_from(["task", "list", "--color", "never", "--ascii"]).unwrap();
+    assert_eq!(cli.color.as_deref(), Some("never"));
+    assert!(cli.ascii);
+    assert!(Cli::try_parse_from(["task", "list", "--color", "sometimes"]).is_err());
+    
+    // 色覚多様性に配慮したパレットは赤・緑を使わない
+    let roles = [
+        Role::PriorityHigh, Role::PriorityMedium, Role::PriorityLow, Role::Overdue, Role::DueToday,
+        Role::Done, Role::Id, Role::Tag, Role::Info, Role::Muted, Role::Warning, Role::Error, Role::Highlight,
+    ];
+    for role in roles {
+        let style = Palette::Colorblind.style(role);
+        for color in [style.color, style.background].into_iter().flatten() {
+            assert!(!matches!(
+                color,
+                colored::Color::Red | colored::Color::Green | colored::Color::BrightRed | colored::Color::BrightGreen
+            ));
+        }
+        // 高コントラストのパレットは薄い表示を使わない
+        assert!(!Palette::HighContrast.style(role).dimmed);
+    }
+    assert_ne!(Palette::Default.style(Role::Overdue), Palette::HighContrast.style(Role::Overdue));
+    
+    // ASCII の記号
+    let glyphs = [
+        Glyph::Done, Glyph::Overdue, Glyph::DueToday, Glyph::Pending, Glyph::Waiting, Glyph::Blocked,
+        Glyph::Running, Glyph::Paused, Glyph::Warning, Glyph::Span, Glyph::DueMark, Glyph::StartMark,
+        Glyph::TodayLine, Glyph::TodayMarker, Glyph::DependsOn, Glyph::VerticalLine,
+        Glyph::HorizontalLine, Glyph::Cross, Glyph::Chart, Glyph::Calendar, Glyph::Pomodoro,
+        Glyph::Target, Glyph::Ruler,
+    ];
+    for glyph in glyphs {
+        assert!(glyph.ascii().is_ascii());
+    }
+    // タイムラインと罫線の記号は 1 列に収まる
+    let narrow = [
+        Glyph::Span, Glyph::DueMark, Glyph::StartMark, Glyph::TodayLine, Glyph::TodayMarker,
+        Glyph::VerticalLine, Glyph::HorizontalLine, Glyph::Cross,
+    ];
+    for glyph in narrow {
+        assert_eq!(display_width(glyph.ascii()), 1);
+        assert_eq!(display_width(glyph.unicode()), 1);
+    }
+    assert_eq!(Glyph::Done.ascii(), "[x]");
+    
+    // 設定ファイル
+    let config = Config::parse("color = \"never\"\ntheme = \"colorblind\"\nascii = true").unwrap();
+    assert_eq!(config.color, ColorChoice::Never);
+    assert_eq!(config.theme, Palette::Colorblind);
+    assert!(config.ascii);
+    assert_eq!(Config::parse("").unwrap().theme, Palette::Default);
+    assert!(Config::parse("theme = \"neon\"").is_err());
+}
+
+#[test]
+fn test_tui() {
+    use ratatui::backend::TestBackend;
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::Terminal;
+    use task_manager_rs::tui::{draw, App};
+    
+    let _guard = lock_db();
+    let (temp_dir, _) = setup_test_db();
+    
+    let mut repo = TaskRepository::new().unwrap();
+    let mut task = Task::new("既存のタスク".to_string(), Some("説明文".to_string()), None, Priority::Low, vec!["home".to_string()]);
+    let existing_id = repo.add_task(&task).unwrap();
+    task.id = Some(existing_id);
+    
+    let key = |code: KeyCode| KeyEvent::new(code, KeyModifiers::NONE);
+    let type_text = |app: &mut App, text: &str| {
+        for c in text.chars() {
+            app.handle_key(key(KeyCode::Char(c)));
+        }
+    };
+    // 画面全体の文字列（全角文字の後ろの空きセルは除く）
+    let render = |terminal: &mut Terminal<TestBackend>, app: &mut App| -> String {
+        terminal.draw(|frame| draw(frame, app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let mut screen = String::new();
+        for y in 0..buffer.area.height {
+            let mut x = 0;
+            while x < buffer.area.width {
+                let symbol = buffer[(x, y)].symbol();
+                screen.push_str(symbol);
+                x += display_width(symbol).max(1) as u16;
+            }
+            screen.push('\n');
+        }
+        screen
+    };
+    
+    with_locale(Locale::Ja, || {
+        let mut app = App::new(TaskRepository::new().unwrap()).unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        let screen = render(&mut terminal, &mut app);
+        assert!(screen.contains("既存のタスク"));
+        assert!(screen.contains("説明文"));
+        
+        // 追加（追加したタスクが選択される）
+        app.handle_key(key(KeyCode::Char('a')));
+        type_text(&mut app, "牛乳を買う");
+        assert!(render(&mut terminal, &mut app).contains("新しいタスク"));
+        app.handle_key(key(KeyCode::Enter));
+        let added = app.selected_task().unwrap().clone();
+        assert_eq!(added.title, "牛乳を買う");
+        assert_eq!(repo.get_all_tasks(true).unwrap().len(), 2);
+        
+        // 優先度とタグの選択
+        app.handle_key(key(KeyCode::Char('p')));
+        app.handle_key(key(KeyCode::Char('3')));
+        app.handle_key(key(KeyCode::Char('t')));
+        type_text(&mut app, "shopping");
+        app.handle_key(key(KeyCode::Enter));
+        app.handle_key(key(KeyCode::Enter));
+        let updated = repo.get_task(added.id.unwrap()).unwrap();
+        assert_eq!(updated.priority, Priority::High);
+        assert_eq!(updated.tags, vec!["shopping".to_string()]);
+        
+        // 期限の入力の誤りはメッセージとして表示
+        app.handle_key(key(KeyCode::Char('d')));
+        type_text(&mut app, "明日");
+        app.handle_key(key(KeyCode::Enter));
+        assert!(app.message.as_ref().is_some_and(|(_, error)| *error));
+        
+        // 完了すると一覧から消え、取り消すと戻る
+        app.handle_key(key(KeyCode::Char(' ')));
+        assert!(repo.get_task(added.id.unwrap()).unwrap().completed);
+        assert!(!render(&mut terminal, &mut app).contains("牛乳を買う"));
+        app.handle_key(key(KeyCode::Char('u')));
+        assert!(!repo.get_task(added.id.unwrap()).unwrap().completed);
+        assert!(render(&mut terminal, &mut app).contains("牛乳を買う"));
+        // 取り消しは新しい変更から順に戻す
+        app.handle_key(key(KeyCode::Char('u')));
+        assert!(repo.get_task(added.id.unwrap()).unwrap().tags.is_empty());
+        app.handle_key(key(KeyCode::Char('u')));
+        assert_eq!(repo.get_task(added.id.unwrap()).unwrap().priority, Priority::Medium);
+        
+        // 検索（入力のたびに絞り込み、#タグ でタグを指定）
+        app.handle_key(key(KeyCode::Char('/')));
+        type_text(&mut app, "#home");
+        let screen = render(&mut terminal, &mut app);
+        assert!(screen.contains("既存のタスク"));
+        assert!(!screen.contains("牛乳を買う"));
+        app.handle_key(key(KeyCode::Esc));
+        assert_eq!(app.visible_tasks().count(), 2);
+        
+        // 削除は確認してから行い、取り消すと同じIDで作業時間・依存関係ごと復元される
+        while app.selected_task().and_then(|t| t.id) != Some(existing_id) {
+            app.handle_key(key(KeyCode::Down));
+        }
+        let started_at = Local::now() - Duration::hours(1);
+        repo.add_time_entry(&TimeEntry::new(existing_id, started_at, Some(started_at + Duration::minutes(30)))).unwrap();
+        let mut dependent = Task::new("後続のタスク".to_string(), None, None, Priority::Low, vec![]);
+        dependent.depends_on = vec![existing_id];
+        let dependent_id = repo.add_task(&dependent).unwrap();
+        app.handle_key(key(KeyCode::Char('p')));
+        app.handle_key(key(KeyCode::Char('3')));
+        app.handle_key(key(KeyCode::Char('D')));
+        assert!(render(&mut terminal, &mut app).contains("削除しますか"));
+        app.handle_key(key(KeyCode::Char('n')));
+        assert!(repo.get_task(existing_id).is_ok());
+        app.handle_key(key(KeyCode::Char('D')));
+        app.handle_key(key(KeyCode::Char('y')));
+        assert!(repo.get_task(existing_id).is_err());
+        assert!(repo.get_task(dependent_id).unwrap().depends_on.is_empty());
+        app.handle_key(key(KeyCode::Char('u')));
+        let restored = repo.get_task(existing_id).unwrap();
+        assert_eq!((restored.title.as_str(), restored.tags.clone()), ("既存のタスク", vec!["home".to_string()]));
+        assert_eq!(repo.get_tracked_time(existing_id).unwrap(), Duration::minutes(30));
+        assert_eq!(repo.get_task(dependent_id).unwrap().depends_on, vec![existing_id]);
+        // 削除より前の変更も取り消せる
+        assert_eq!(restored.priority, Priority::High);
+        app.handle_key(key(KeyCode::Char('u')));
+        assert_eq!(repo.get_task(existing_id).unwrap().priority, Priority::Low);
+        
+        app.handle_key(key(KeyCode::Char('q')));
+        assert!(app.should_quit());
+    });
+    
+    teardown();
+    drop(temp_dir);
+}
+
+#[test]
+fn test_shell() {
+    use task_manager_rs::cli::Commands;
+    use task_manager_rs::commands::shell::{complete_line, CompletionData, Flow, Shell};
+    use task_manager_rs::db::schema::set_connection_reuse;
+    
+    let _guard = lock_db();
+    let (temp_dir, db_path) = setup_test_db();
+    
+    // 接続の再利用（DBファイルを削除しても同じ接続でデータを読める）
+    set_connection_reuse(true);
+    let mut repo = TaskRepository::new().unwrap();
+    repo.add_task(&Task::new("接続のテスト".to_string(), None, None, Priority::Medium, vec![])).unwrap();
+    drop(repo);
+    std::fs::remove_file(&db_path).unwrap();
+    assert_eq!(TaskRepository::new().unwrap().get_all_tasks(true).unwrap().len(), 1);
+    set_connection_reuse(false);
+    assert!(TaskRepository::new().unwrap().get_all_tasks(true).unwrap().is_empty());
+    
+    // 絞り込みは list の省略したオプションと add のタグに適用される
+    let mut shell = Shell::new();
+    assert_eq!(shell.prompt(), "task> ");
+    assert_eq!(shell.execute("filter set -t work --sort -due").unwrap(), Flow::Continue);
+    assert_eq!(shell.filter(), ["-t", "work", "--sort", "-due"]);
+    assert_eq!(shell.prompt(), "task [-t work --sort -due]> ");
+    
+    let mut list = Cli::try_parse_from(["task", "list", "--sort", "title"]).unwrap().command;
+    shell.apply_filter(&mut list).unwrap();
+    match list {
+        Commands::List { tags, sort, .. } => {
+            assert_eq!(tags.as_deref(), Some("work"));
+            assert_eq!(sort.as_deref(), Some("title"));
+        }
+        _ => unreachable!(),
+    }
+    
+    shell.execute("add 'レポートを書く' -g urgent,work").unwrap();
+    let tasks = TaskRepository::new().unwrap().get_all_tasks(true).unwrap();
+    assert_eq!(tasks[0].title, "レポートを書く");
+    assert_eq!(tasks[0].tags, vec!["urgent".to_string(), "work".to_string()]);
+    assert!(tasks[0].project.is_none());
+    shell.execute("filter set -t work --where project:alpha").unwrap();
+    shell.execute("add 設計").unwrap();
+    let tasks = TaskRepository::new().unwrap().get_all_tasks(true).unwrap();
+    assert_eq!(tasks.iter().find(|t| t.title == "設計").unwrap().project.as_deref(), Some("alpha"));
+    assert_eq!(TaskRepository::new().unwrap().get_all_projects().unwrap(), vec!["alpha".to_string()]);
+    shell.execute("filter set -t work --sort -due").unwrap();
+    
+    // 不正な絞り込み・入れ子の shell・閉じていない引用符はエラー
+    assert!(shell.execute("filter set --bogus").is_err());
+    assert!(shell.execute("filter -t work").is_err());
+    assert!(shell.execute("filter set").is_err());
+    assert_eq!(shell.filter().len(), 4);
+    assert!(shell.execute("shell").is_err());
+    assert!(shell.execute("add '閉じていない").is_err());
+    shell.execute("filter show").unwrap();
+    shell.execute("filter none").unwrap();
+    assert!(shell.filter().is_empty());
+    // context は保存するコンテキストのコマンド
+    shell.execute("context set work tag:work").unwrap();
+    assert_eq!(TaskRepository::new().unwrap().get_setting("context").unwrap().as_deref(), Some("work"));
+    shell.execute("context none").unwrap();
+    assert_eq!(shell.execute("   ").unwrap(), Flow::Continue);
+    assert_eq!(shell.execute("exit").unwrap(), Flow::Exit);
+    
+    // 補完
+    let command = Cli::command();
+    let data = CompletionData {
+        tasks: vec![(3, "牛乳を買う".to_string()), (12, "レポートを書く".to_string())],
+        tags: vec!["home".to_string(), "work".to_string()],
+        projects: vec!["alpha".to_string(), "beta".to_string()],
+    };
+    let complete = |line: &str| {
+        let (start, pairs) = complete_line(&command, line, &data);
+        (start, pairs.into_iter().map(|p| p.replacement).collect::<Vec<_>>())
+    };
+    assert_eq!(complete("co"), (0, vec!["complete".to_string(), "context".to_string()]));
+    assert_eq!(complete("fi"), (0, vec!["field".to_string(), "filter".to_string()]));
+    assert_eq!(complete("show 1"), (5, vec!["12".to_string()]));
+    assert_eq!(complete("show ").1, vec!["3".to_string(), "12".to_string()]);
+    assert_eq!(complete("show 3 ").1, Vec::<String>::new());
+    assert_eq!(complete("add x -g home,w"), (14, vec!["work".to_string()]));
+    assert_eq!(complete("update 3 --project b"), (19, vec!["beta".to_string()]));
+    assert_eq!(complete("add x --project ").1, vec!["alpha".to_string(), "beta".to_string()]);
+    assert_eq!(complete("list --due").1, vec!["--due-today".to_string()]);
+    assert_eq!(complete("timer st").1, vec!["start".to_string(), "stop".to_string(), "status".to_string()]);
+    assert_eq!(complete("timer start ").1, vec!["3".to_string(), "12".to_string()]);
+    assert_eq!(complete("update 3 --depends-on 1").1, vec!["12".to_string()]);
+    assert_eq!(complete("done 3 1").1, vec!["12".to_string()]);
+    assert!(complete("unknown ").1.is_empty());
+    
+    teardown();
+    drop(temp_dir);
+}
+
+#[test]
+fn test_edit_document() {
+    use task_manager_rs::commands::edit::{diff_lines, edit_document, parse_document, render_document, DiffLine, EditOutcome};
+    
+    let _guard = lock_db();
+    let (temp_dir, _) = setup_test_db();
+    let mut repo = TaskRepository::new().unwrap();
+    let points = FieldDefinition::new("story_points".to_string(), FieldType::Number, vec![]).unwrap();
+    repo.define_field(&points).unwrap();
+    let definitions = repo.get_field_definitions().unwrap();
+    
+    let mut task = Task::new(
+        "資料を作る".to_string(),
+        Some("## 手順\n\n1. 下書き".to_string()),
+        Some(Local.with_ymd_and_hms(2026, 11, 2, 18, 0, 0).unwrap()),
+        Priority::High,
+        vec!["work".to_string()],
+    );
+    task.estimate_minutes = Some(90);
+    task.custom_fields.insert("story_points".to_string(), "3".to_string());
+    task.id = Some(repo.add_task(&task).unwrap());
+    
+    // 文書への変換と解析で元に戻る
+    let document = with_locale(Locale::Ja, || render_document(&task, &definitions));
+    assert!(document.starts_with("---\n# "));
+    assert!(document.contains("\ndue: 2026-11-02 18:00\n"));
+    assert!(document.contains("\nestimate: 1h30m\n"));
+    assert!(document.contains("\nstory_points: 3\n"));
+    assert!(document.contains("\nwait:\n"));
+    assert!(document.ends_with("---\n\n## 手順\n\n1. 下書き\n"));
+    let parsed = parse_document(&document, &task, &definitions).unwrap();
+    assert_eq!(serde_json::to_value(&parsed).unwrap(), serde_json::to_value(&task).unwrap());
+    
+    // 値の変更・削除と検証
+    let edited = document
+        .replace("priority: 3", "priority: low")
+        .replace("tags: work", "tags: #work, home, work")
+        .replace("due: 2026-11-02 18:00", "due:")
+        .replace("story_points: 3", "story_points:")
+        .replace("1. 下書き", "1. 下書き\n2. 見直し");
+    let parsed = parse_document(&edited, &task, &definitions).unwrap();
+    assert_eq!(parsed.priority, Priority::Low);
+    assert_eq!(parsed.tags, vec!["work".to_string(), "home".to_string()]);
+    assert_eq!(parsed.due_date, None);
+    assert!(parsed.custom_fields.is_empty());
+    assert_eq!(parsed.description.as_deref(), Some("## 手順\n\n1. 下書き\n2. 見直し"));
+    
+    for invalid in [
+        document.replace("due: 2026-11-02 18:00", "due: 来週"),
+        document.replace("priority: 3", "priority: 最優先"),
+        document.replace("tags: work", "tags: deep work"),
+        document.replace("estimate: 1h30m", "estimate: 少し"),
+        document.replace("title: 資料を作る", "title:"),
+        document.replace("wait:", "wait:\nwait:"),
+        document.replace("wait:", "customer: ACME"),
+        document.replace("wait:", "これは不正な行"),
+        document.replacen("---\n", "", 1),
+        document.replace("\n---\n\n", "\n\n"),
+    ] {
+        assert!(parse_document(&invalid, &task, &definitions).is_err(), "{}", invalid);
+    }
+    
+    // 誤りがあるとエラーを書き込んだ文書で開き直す
+    let mut opened = Vec::new();
+    let result = with_locale(Locale::Ja, || {
+        edit_document(&repo, &task, &definitions, |text| {
+            opened.push(text.to_string());
+            Ok(match opened.len() {
+                1 => text.replace("story_points: 3", "story_points: たくさん"),
+                2 => text.replace("story_points: たくさん", "story_points: 5").replace("depends_on:", "depends_on: 999"),
+                _ => text.replace("story_points: たくさん", "story_points: 5").replace("depends_on: 999", "depends_on:"),
+            })
+        })
+    }).unwrap();
+    let EditOutcome::Edited(result) = result else { panic!("編集結果がありません: {:?}", result) };
+    assert_eq!(opened.len(), 3);
+    assert!(opened[1].starts_with("---\n# ! 内容に誤りがあります"));
+    assert!(opened[2].contains("999"));
+    assert_eq!(opened[2].matches("# ! 内容に誤りがあります").count(), 1);
+    assert_eq!(result.custom_fields.get("story_points").map(String::as_str), Some("5"));
+    
+    // 変更なしと、空にした場合の中止を区別する
+    assert!(matches!(
+        edit_document(&repo, &task, &definitions, |text| Ok(text.to_string())).unwrap(),
+        EditOutcome::Unchanged
+    ));
+    assert!(matches!(
+        edit_document(&repo, &task, &definitions, |_| Ok("\n".to_string())).unwrap(),
+        EditOutcome::Cancelled
+    ));
+    
+    // 差分
+    assert_eq!(
+        diff_lines("a\nb\nc\n", "a\nc\nd\n"),
+        vec![
+            DiffLine::Same("a".to_string()),
+            DiffLine::Removed("b".to_string()),
+            DiffLine::Same("c".to_string()),
+            DiffLine::Added("d".to_string()),
+        ]
+    );
+    
+    teardown();
+    drop(temp_dir);
+}
+
+#[test]
+fn test_bulk_operations() {
+    use task_manager_rs::commands::bulk::select_tasks;
+    use task_manager_rs::utils::filter::TaskFilter;
+    use task_manager_rs::utils::ids::parse_id_ranges;
+    
+    let _guard = lock_db();
+    let (temp_dir, _) = setup_test_db();
+    let mut repo = TaskRepository::new().unwrap();
+    let points = FieldDefinition::new("story_points".to_string(), FieldType::Number, vec![]).unwrap();
+    repo.define_field(&points).unwrap();
+    let definitions = repo.get_field_definitions().unwrap();
+    
+    // ID の一覧と範囲
+    let args = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+    assert_eq!(parse_id_ranges(&args(&["3", "5,6", "8-10", "5"])).unwrap(), vec![3, 5, 6, 8, 9, 10]);
+    for invalid in ["abc", "5-3", "1-", "-", "1-100000"] {
+        assert!(parse_id_ranges(&args(&[invalid])).is_err(), "{}", invalid);
+    }
+    
+    for (title, tags, priority, points) in [
+        ("設計レビュー", vec!["sprint-41"], Priority::High, "5"),
+        ("テストを書く", vec!["sprint-41", "qa"], Priority::Low, "2"),
+        ("リリース準備", vec!["sprint-42"], Priority::High, "3"),
+    ] {
+        let mut task = Task::new(title.to_string(), None, None, priority, tags.iter().map(|t| t.to_string()).collect());
+        task.custom_fields.insert("story_points".to_string(), points.to_string());
+        repo.add_task(&task).unwrap();
+    }
+    repo.complete_task(2).unwrap();
+    
+    // 条件の解析と照合
+    let filter = TaskFilter::parse("tag:sprint-41 priority:高 story_points>=3 レビュー", &definitions).unwrap();
+    let matched: Vec<_> = repo.get_all_tasks(true).unwrap().into_iter().filter(|t| filter.matches(t)).collect();
+    assert_eq!(matched.len(), 1);
+    assert_eq!(matched[0].id, Some(1));
+    for invalid in ["", "priority:最優先", "due:someday", "is:", "velocity>3", "'閉じていない"] {
+        assert!(TaskFilter::parse(invalid, &definitions).is_err(), "{}", invalid);
+    }
+    
+    // ID と条件の組み合わせ（両方を満たすタスク）
+    let ids = |tasks: Vec<Task>| tasks.into_iter().filter_map(|t| t.id).collect::<Vec<_>>();
+    assert_eq!(ids(select_tasks(&repo, &args(&["3", "1-2"]), None).unwrap()), vec![1, 2, 3]);
+    assert_eq!(ids(select_tasks(&repo, &[], Some("tag:sprint-41")).unwrap()), vec![1, 2]);
+    assert_eq!(ids(select_tasks(&repo, &args(&["2-3"]), Some("is:pending")).unwrap()), vec![3]);
+    assert!(select_tasks(&repo, &args(&["1-4"]), None).is_err());
+    assert!(select_tasks(&repo, &[], None).is_err());
+    
+    // 1 つのトランザクションで実行し、途中のエラーですべて取り消す
+    let result = repo.transaction(|repo| {
+        repo.complete_task(1)?;
+        repo.delete_task(3)?;
+        repo.complete_task(99)
+    });
+    assert!(result.is_err());
+    let tasks = repo.get_all_tasks(true).unwrap();
+    assert_eq!(tasks.len(), 3);
+    assert!(!tasks.iter().find(|t| t.id == Some(1)).unwrap().completed);
+    repo.transaction(|repo| [1, 3].iter().try_for_each(|id| repo.complete_task(*id))).unwrap();
+    assert!(repo.get_all_tasks(true).unwrap().iter().all(|t| t.completed));
+    
+    teardown();
+    drop(temp_dir);
+}
+
+#[test]
+fn test_tag_changes() {
+    use task_manager_rs::models::tag::{parse_quick_tags, TagChanges};
+    
+    let _guard = lock_db();
+    let (temp_dir, db_path) = setup_test_db();
+    let mut repo = TaskRepository::new().unwrap();
+    
+    // クイック入力の +tag / -tag
+    let (title, changes) = parse_quick_tags("牛乳を買う +home -work +errand");
+    assert_eq!(title, "牛乳を買う");
+    assert_eq!(changes.add, vec!["home".to_string(), "errand".to_string()]);
+    assert_eq!(changes.remove, vec!["work".to_string()]);
+    assert_eq!(parse_quick_tags("C++ の  +1 と - の話"), ("C++ の  +1 と - の話".to_string(), TagChanges::default()));
+    
+    let mut tags = vec!["work".to_string(), "home".to_string()];
+    TagChanges { clear: false, add: vec!["urgent".to_string(), "home".to_string()], remove: vec!["work".to_string()] }.apply(&mut tags);
+    assert_eq!(tags, vec!["home".to_string(), "urgent".to_string()]);
+    TagChanges { clear: true, add: vec!["a".to_string()], remove: vec![] }.apply(&mut tags);
+    assert_eq!(tags, vec!["a".to_string()]);
+    
+    // リポジトリでの追加・削除・すべて削除
+    let task = Task::new("資料".to_string(), None, None, Priority::Medium, vec!["work".to_string(), "draft".to_string()]);
+    let id = repo.add_task(&task).unwrap();
+    let tags_of = |repo: &TaskRepository| {
+        let mut tags = repo.get_task(id).unwrap().tags;
+        tags.sort();
+        tags
+    };
+    repo.update_tags(id, &TagChanges { clear: false, add: vec!["review".to_string(), "work".to_string()], remove: vec!["draft".to_string()] }).unwrap();
+    assert_eq!(tags_of(&repo), vec!["review".to_string(), "work".to_string()]);
+    assert!(repo.update_tags(999, &TagChanges::default()).is_err());
+    
+    // 置き換えでは変わらないタグの関連付けの行を書き換えない
+    let row_of = |tag: &str| -> i64 {
+        let conn = rusqlite::Connection::open(&db_path).unwrap();
+        conn.query_row(
+            "SELECT task_tags.rowid FROM task_tags JOIN tags ON task_tags.tag_id = tags.id WHERE task_id = ?1 AND tags.name = ?2",
+            rusqlite::params![id, tag],
+            |row| row.get(0),
+        )
+        .unwrap()
+    };
+    let work_row = row_of("work");
+    let mut updated = repo.get_task(id).unwrap();
+    updated.tags = vec!["work".to_string(), "done".to_string()];
+    repo.update_task(&updated).unwrap();
+    assert_eq!(tags_of(&repo), vec!["done".to_string(), "work".to_string()]);
+    assert_eq!(row_of("work"), work_row);
+    
+    repo.update_tags(id, &TagChanges { clear: true, add: vec![], remove: vec![] }).unwrap();
+    assert!(tags_of(&repo).is_empty());
+    
+    teardown();
+    drop(temp_dir);
+}
+
+#[test]
+fn test_views() {
+    use task_manager_rs::models::view::View;
+    
+    let _guard = lock_db();
+    let (temp_dir, _) = setup_test_db();
+    let mut repo = TaskRepository::new().unwrap();
+    
+    // 名前の検証と保存・置き換え・削除
+    assert!(View::validate_name("mywork").is_ok());
+    for invalid in ["", "@mywork", "my work", "a,b"] {
+        assert!(View::validate_name(invalid).is_err(), "{}", invalid);
+    }
+    let view = View {
+        name: "mywork".to_string(),
+        filter: Some("tag:work priority:高".to_string()),
+        sort: Some("-due".to_string()),
+        format: Some("table".to_string()),
+        columns: Some("id,title,due".to_string()),
+        all: false,
+    };
+    assert!(!repo.save_view(&view).unwrap());
+    assert_eq!(repo.get_view("mywork").unwrap(), view);
+    let replaced = View { all: true, ..view.clone() };
+    assert!(repo.save_view(&replaced).unwrap());
+    repo.save_view(&View { name: "inbox".to_string(), filter: None, sort: None, format: None, columns: None, all: false }).unwrap();
+    let names: Vec<_> = repo.get_views().unwrap().into_iter().map(|v| v.name).collect();
+    assert_eq!(names, vec!["inbox".to_string(), "mywork".to_string()]);
+    repo.remove_view("inbox").unwrap();
+    assert!(matches!(repo.remove_view("inbox"), Err(TaskError::ViewNotFound(_))));
+    assert!(matches!(repo.get_view("inbox"), Err(TaskError::ViewNotFound(_))));
+    
+    // 保存時の検証（未定義のフィールド・不正な並び替えキー・列）
+    assert!(commands::save_view("bad".to_string(), Some("velocity>3".to_string()), None, None, None, false).is_err());
+    assert!(commands::save_view("bad".to_string(), None, Some("velocity".to_string()), None, None, false).is_err());
+    assert!(commands::save_view("bad".to_string(), None, None, None, Some("id,bogus".to_string()), false).is_err());
+    assert!(repo.get_view("bad").is_err());
+    
+    // JSON のエクスポート・インポートに含まれる（タスクの配列だけの以前の形式も読み込める）
+    repo.add_task(&Task::new("設計".to_string(), None, None, Priority::High, vec!["work".to_string()])).unwrap();
+    let export_path = temp_dir.path().join("views.json").to_str().unwrap().to_string();
+    commands::export_tasks(export_path.clone(), "json".to_string()).unwrap();
+    let legacy_path = temp_dir.path().join("legacy.json").to_str().unwrap().to_string();
+    let tasks = repo.get_all_tasks(true).unwrap();
+    std::fs::write(&legacy_path, serde_json::to_string(&tasks).unwrap()).unwrap();
+    drop(repo);
+    teardown();
+    
+    let (new_temp_dir, _) = setup_test_db();
+    commands::import_tasks(export_path).unwrap();
+    commands::import_tasks(legacy_path).unwrap();
+    let repo = TaskRepository::new().unwrap();
+    assert_eq!(repo.get_views().unwrap(), vec![replaced]);
+    assert_eq!(repo.get_all_tasks(true).unwrap().len(), 2);
+    
+    teardown();
+    drop(temp_dir);
+    drop(new_temp_dir);
+}
+
+#[test]
+fn test_contexts() {
+    use task_manager_rs::commands::context::{active_context, retain_in_context};
+    use task_manager_rs::utils::filter::TaskFilter;
+    
+    let _guard = lock_db();
+    let (temp_dir, _) = setup_test_db();
+    let mut repo = TaskRepository::new().unwrap();
+    for (title, tags) in [("設計", vec!["work", "alpha"]), ("買い物", vec!["home"]), ("調査", vec!["alpha"])] {
+        repo.add_task(&Task::new(title.to_string(), None, None, Priority::Medium, tags.iter().map(|t| t.to_string()).collect())).unwrap();
+    }
+    
+    // or で区切った条件の組と、どの組でも必要なタグ
+    let filter = TaskFilter::parse("tag:work or tag:alpha 調査", &[]).unwrap();
+    let titles = |tasks: &[Task]| tasks.iter().map(|t| t.title.clone()).collect::<Vec<_>>();
+    let tasks = repo.get_all_tasks(true).unwrap();
+    let matched: Vec<Task> = tasks.iter().filter(|t| filter.matches(t)).cloned().collect();
+    assert_eq!(titles(&matched), vec!["設計".to_string(), "調査".to_string()]);
+    assert!(filter.required_tags().is_empty());
+    let filter = TaskFilter::parse("tag:alpha priority:2 OR tag:alpha tag:work", &[]).unwrap();
+    assert_eq!(filter.required_tags(), vec!["alpha".to_string()]);
+    assert_eq!(TaskFilter::parse("project:alpha tag:work or project:alpha", &[]).unwrap().required_project(), Some("alpha".to_string()));
+    assert_eq!(TaskFilter::parse("tag:work or project:alpha", &[]).unwrap().required_project(), None);
+    for invalid in ["or tag:work", "tag:work or", "tag:work or or tag:home"] {
+        assert!(TaskFilter::parse(invalid, &[]).is_err(), "{}", invalid);
+    }
+    
+    // 設定・適用・解除
+    assert!(active_context(&repo).unwrap().is_none());
+    assert!(commands::set_context("missing".to_string(), None).is_err());
+    commands::set_context("work".to_string(), Some("tag:alpha".to_string())).unwrap();
+    let context = active_context(&repo).unwrap().unwrap();
+    assert_eq!(context.name, "work");
+    assert_eq!(repo.get_view("work").unwrap().filter.as_deref(), Some("tag:alpha"));
+    let mut tasks = repo.get_all_tasks(true).unwrap();
+    retain_in_context(&repo, &mut tasks).unwrap();
+    assert_eq!(titles(&tasks), vec!["設計".to_string(), "調査".to_string()]);
+    
+    // add には条件のタグが付く（タイトルの -tag で外せる）
+    commands::add_task("レビュー".to_string(), None, None, 2, None, None, vec![], None, None, None, None).unwrap();
+    commands::add_task("メモ -alpha".to_string(), None, None, 2, None, None, vec![], None, None, None, None).unwrap();
+    let tasks = repo.get_all_tasks(true).unwrap();
+    assert_eq!(tasks.iter().find(|t| t.title == "レビュー").unwrap().tags, vec!["alpha".to_string()]);
+    assert!(tasks.iter().find(|t| t.title == "メモ").unwrap().tags.is_empty());
+    
+    // 条件のプロジェクトも付く（指定したプロジェクトが優先）
+    commands::set_context("alpha".to_string(), Some("project:alpha tag:work".to_string())).unwrap();
+    commands::add_task("見積もり".to_string(), None, None, 2, None, None, vec![], None, None, None, None).unwrap();
+    commands::add_task("別件".to_string(), None, None, 2, None, Some("beta".to_string()), vec![], None, None, None, None).unwrap();
+    let tasks = repo.get_all_tasks(true).unwrap();
+    let estimate = tasks.iter().find(|t| t.title == "見積もり").unwrap();
+    assert_eq!((estimate.project.as_deref(), estimate.tags.clone()), (Some("alpha"), vec!["work".to_string()]));
+    assert_eq!(tasks.iter().find(|t| t.title == "別件").unwrap().project.as_deref(), Some("beta"));
+    commands::set_context("work".to_string(), Some("tag:alpha".to_string())).unwrap();
+    
+    // 表示条件を削除するとコンテキストも無効になる
+    repo.remove_view("work").unwrap();
+    assert!(active_context(&repo).unwrap().is_none());
+    commands::set_context("home".to_string(), Some("tag:home".to_string())).unwrap();
+    commands::clear_context().unwrap();
+    assert!(active_context(&repo).unwrap().is_none());
+    
+    teardown();
+    drop(temp_dir);
+}
+
+#[test]
+fn test_task_templates() {
+    use chrono::{Duration, Local, TimeZone};
+    use std::collections::HashMap;
+    use task_manager_rs::models::template::substitute;
+    
+    let _guard = lock_db();
+    let (temp_dir, _) = setup_test_db();
+    let mut repo = TaskRepository::new().unwrap();
+    let due = Local.with_ymd_and_hms(2026, 11, 10, 0, 0, 0).unwrap();
+    let mut release = Task::new("Release {{version}}".to_string(), Some("Ship {{version}}".to_string()), Some(due), Priority::High, vec!["release".to_string()]);
+    let changelog = Task::new("Changelog {{version}}".to_string(), None, Some(due - Duration::days(2)), Priority::Medium, vec![]);
+    let changelog_id = repo.add_task(&changelog).unwrap();
+    let tag_id = repo.add_task(&Task::new("Tag {{version}}".to_string(), None, None, Priority::Low, vec![])).unwrap();
+    release.depends_on = vec![changelog_id, tag_id];
+    release.checklist = vec![task_manager_rs::models::checklist::ChecklistItem { text: "Announce {{version}}".to_string(), done: true }];
+    let release_id = repo.add_task(&release).unwrap();
+    
+    // 変数の置き換え
+    let vars: HashMap<String, String> = [("version".to_string(), "1.4".to_string())].into();
+    assert_eq!(substitute("v{{ version }}-{{version}}", &vars).unwrap(), "v1.4-1.4");
+    assert!(substitute("{{missing}}", &vars).is_err());
+    
+    // 依存先も含めて保存され、期限は基準からの差で記録される
+    commands::save_template("rel".to_string(), release_id).unwrap();
+    let template = repo.get_template("rel").unwrap();
+    assert_eq!(template.tasks.len(), 3);
+    assert_eq!(template.tasks[0].depends_on, vec![1, 2]);
+    assert_eq!(template.tasks[1].due_offset_minutes, Some(-2 * 24 * 60));
+    assert_eq!(template.tasks[2].due_offset_minutes, None);
+    assert_eq!(template.variables(), vec!["version".to_string()]);
+    
+    // 変数が足りない場合は何も作成しない
+    assert!(commands::apply_template("rel".to_string(), None, vec![]).is_err());
+    assert!(commands::apply_template("missing".to_string(), None, vec![]).is_err());
+    assert_eq!(repo.get_all_tasks(true).unwrap().len(), 3);
+    
+    commands::apply_template("rel".to_string(), Some("2026-12-01".to_string()), vec!["version=1.4".to_string()]).unwrap();
+    let tasks = repo.get_all_tasks(true).unwrap();
+    assert_eq!(tasks.len(), 6);
+    let find = |title: &str| tasks.iter().find(|t| t.title == title).unwrap().clone();
+    let (created, created_changelog, created_tag) = (find("Release 1.4"), find("Changelog 1.4"), find("Tag 1.4"));
+    assert_eq!(created.description.as_deref(), Some("Ship 1.4"));
+    assert_eq!(created.priority, Priority::High);
+    assert_eq!(created.tags, vec!["release".to_string()]);
+    assert_eq!(created.checklist.len(), 1);
+    assert_eq!((created.checklist[0].text.as_str(), created.checklist[0].done), ("Announce 1.4", false));
+    let new_due = Local.with_ymd_and_hms(2026, 12, 1, 0, 0, 0).unwrap();
+    assert_eq!(created.due_date, Some(new_due));
+    assert_eq!(created_changelog.due_date, Some(new_due - Duration::days(2)));
+    assert_eq!(created.depends_on, vec![created_changelog.id.unwrap(), created_tag.id.unwrap()]);
+    
+    commands::remove_template("rel".to_string()).unwrap();
+    assert!(repo.get_template("rel").is_err());
+    
+    teardown();
+    drop(temp_dir);
+}
+
+#[test]
+fn test_checklists() {
+    use task_manager_rs::models::checklist::ChecklistItem;
+    use task_manager_rs::utils::format::{checklist_progress, format_task};
+    
+    let _guard = lock_db();
+    let (temp_dir, _) = setup_test_db();
+    let mut repo = TaskRepository::new().unwrap();
+    let id = repo.add_task(&Task::new("リリース".to_string(), None, None, Priority::Medium, vec![])).unwrap();
+    
+    // 追加・切り替え・削除（番号は 1 から）
+    for text in ["ノートを書く", "タグを付ける", "告知する"] {
+        commands::add_check_item(id, text.to_string()).unwrap();
+    }
+    assert!(commands::add_check_item(id, "  ".to_string()).is_err());
+    commands::toggle_check_item(id, 1).unwrap();
+    commands::toggle_check_item(id, 3).unwrap();
+    commands::toggle_check_item(id, 3).unwrap();
+    assert!(commands::toggle_check_item(id, 0).is_err());
+    assert!(commands::toggle_check_item(id, 4).is_err());
+    commands::remove_check_item(id, 2).unwrap();
+    let task = repo.get_task(id).unwrap();
+    assert_eq!(task.checklist, vec![
+        ChecklistItem { text: "ノートを書く".to_string(), done: true },
+        ChecklistItem { text: "告知する".to_string(), done: false },
+    ]);
+    assert_eq!(checklist_progress(&task), "1/2");
+    assert!(format_task(&task, false).contains("[1/2]"));
+    
+    // 更新してもチェックリストは変わらない
+    let mut updated = task.clone();
+    updated.title = "リリース 1.4".to_string();
+    repo.update_task(&updated).unwrap();
+    assert_eq!(repo.get_task(id).unwrap().checklist, task.checklist);
+    
+    // JSON のエクスポート・インポートで保たれる
+    let export_path = temp_dir.path().join("checklist.json").to_str().unwrap().to_string();
+    commands::export_tasks(export_path.clone(), "json".to_string()).unwrap();
+    repo.delete_task(id).unwrap();
+    commands::import_tasks(export_path).unwrap();
+    let tasks = repo.get_all_tasks(true).unwrap();
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].checklist, task.checklist);
+    
+    teardown();
+    drop(temp_dir);
+}
+
+#[test]
+fn test_notes() {
+    use chrono::{Duration, Local};
+    use task_manager_rs::models::note::Note;
+    use task_manager_rs::utils::filter::TaskFilter;
+    
+    let _guard = lock_db();
+    let (temp_dir, _) = setup_test_db();
+    let mut repo = TaskRepository::new().unwrap();
+    let id = repo.add_task(&Task::new("デプロイ".to_string(), None, None, Priority::Medium, vec![])).unwrap();
+    repo.add_task(&Task::new("別のタスク".to_string(), None, None, Priority::Medium, vec![])).unwrap();
+    
+    // 追加した日時の古い順に並ぶ
+    commands::add_note(id, "移行が完了".to_string()).unwrap();
+    let earlier = Note { created_at: Local::now() - Duration::days(1), text: "DB の移行待ち".to_string() };
+    repo.add_note(id, &earlier).unwrap();
+    assert!(commands::add_note(id, " ".to_string()).is_err());
+    assert!(commands::add_note(999, "メモ".to_string()).is_err());
+    let task = repo.get_task(id).unwrap();
+    let texts = |task: &Task| task.notes.iter().map(|n| n.text.clone()).collect::<Vec<_>>();
+    assert_eq!(texts(&task), vec!["DB の移行待ち".to_string(), "移行が完了".to_string()]);
+    
+    // 全文検索（語をすべて含むメモを関連度の高い順。大文字・小文字、2 文字以下の語、記号も扱う）
+    commands::add_note(id + 1, "移行 migration の手順".to_string()).unwrap();
+    let found = |query: &str| repo.search_notes(query).unwrap().into_iter().map(|m| m.note.text).collect::<Vec<_>>();
+    assert_eq!(found("移行待ち"), vec!["DB の移行待ち".to_string()]);
+    assert_eq!(found("MIGRATION"), vec!["移行 migration の手順".to_string()]);
+    assert_eq!(found("db 移行"), vec!["DB の移行待ち".to_string()]);
+    assert_eq!(found("移行").len(), 3);
+    assert!(found("\"移行\" OR").is_empty());
+    assert!(found("デプロイ").is_empty());
+    commands::search_notes(vec!["移行".to_string()]).unwrap();
+    assert!(commands::search_notes(vec![" ".to_string()]).is_err());
+    
+    // --where の語は索引でメモも検索する（索引を使わない場合はタイトル・説明だけ）
+    let filter = TaskFilter::parse("移行待ち", &[]).unwrap();
+    let matched = |filter: &TaskFilter| {
+        repo.get_all_tasks(true).unwrap().into_iter().filter(|t| filter.matches(t)).map(|t| t.id).collect::<Vec<_>>()
+    };
+    assert!(matched(&filter).is_empty());
+    let filter = filter.search_notes(|term| repo.search_note_task_ids(term)).unwrap();
+    assert_eq!(matched(&filter), vec![Some(id)]);
+    
+    // 更新してもメモは残る
+    let mut updated = task.clone();
+    updated.notes.clear();
+    repo.update_task(&updated).unwrap();
+    assert_eq!(repo.get_task(id).unwrap().notes, task.notes);
+    
+    // JSON のエクスポート・インポートで日時ごと保たれる
+    let export_path = temp_dir.path().join("notes.json").to_str().unwrap().to_string();
+    commands::export_tasks(export_path.clone(), "json".to_string()).unwrap();
+    repo.delete_task(id).unwrap();
+    commands::import_tasks(export_path).unwrap();
+    let imported = repo.get_all_tasks(true).unwrap().into_iter().find(|t| t.title == "デプロイ").unwrap();
+    assert_eq!(texts(&imported), texts(&task));
+    assert_eq!(imported.notes[0].created_at.timestamp(), earlier.created_at.timestamp());
+    
+    // 索引はタスクの削除・インポートに追従する
+    assert_eq!(repo.search_note_task_ids("移行待ち").unwrap().into_iter().collect::<Vec<_>>(), vec![imported.id.unwrap()]);
+    
+    teardown();
+    drop(temp_dir);
+}
+
+#[test]
+fn test_attachments() {
+    use task_manager_rs::commands::attach::attachments_dir;
+    use task_manager_rs::models::attachment::{is_stored_name, stored_name, AttachmentKind};
+    
+    let _guard = lock_db();
+    let (temp_dir, _) = setup_test_db();
+    let mut repo = TaskRepository::new().unwrap();
+    let id = repo.add_task(&Task::new("設計".to_string(), None, None, Priority::Medium, vec![])).unwrap();
+    let other = repo.add_task(&Task::new("別のタスク".to_string(), None, None, Priority::Medium, vec![])).unwrap();
+    let spec = temp_dir.path().join("spec.pdf");
+    std::fs::write(&spec, "仕様").unwrap();
+    let spec = spec.to_str().unwrap().to_string();
+    
+    // コピーは内容のハッシュを名前にして保存し、同じ内容は 1 つだけ保存する
+    commands::attach_file(id, spec.clone(), false).unwrap();
+    commands::attach_file(other, spec.clone(), false).unwrap();
+    commands::attach_file(id, spec.clone(), true).unwrap();
+    commands::link_url(id, "https://example.com/doc".to_string()).unwrap();
+    assert!(commands::link_url(id, "example.com".to_string()).is_err());
+    assert!(commands::attach_file(id, temp_dir.path().join("missing.pdf").to_str().unwrap().to_string(), false).is_err());
+    assert!(commands::attach_file(999, spec.clone(), false).is_err());
+    
+    let task = repo.get_task(id).unwrap();
+    let kinds: Vec<_> = task.attachments.iter().map(|a| a.kind).collect();
+    assert_eq!(kinds, vec![AttachmentKind::Stored, AttachmentKind::File, AttachmentKind::Link]);
+    let stored = &task.attachments[0];
+    assert!(stored.target.ends_with(".pdf") && stored.target.len() == 64 + ".pdf".len());
+    assert_eq!(stored.name.as_deref(), Some("spec.pdf"));
+    let dir = attachments_dir().unwrap();
+    assert_eq!(std::fs::read_to_string(dir.join(&stored.target)).unwrap(), "仕様");
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+    assert_eq!(task.attachments[1].target, std::fs::canonicalize(&spec).unwrap().to_str().unwrap());
+    
+    // 番号の範囲外は開かない
+    assert!(commands::open_attachment(id, Some(4)).is_err());
+    assert!(commands::open_attachment(id, Some(0)).is_err());
+    
+    // コピーしたファイルの名前はハッシュと英数字の拡張子だけ
+    let hash = "0123456789abcdef".repeat(4);
+    assert!(is_stored_name(&hash));
+    assert!(is_stored_name(&format!("{}.pdf", hash)));
+    assert!(!is_stored_name(&format!("{}.", hash)));
+    assert!(!is_stored_name(&format!("{}.tar.gz", hash)));
+    assert!(!is_stored_name(&hash.to_uppercase()));
+    assert!(!is_stored_name("../../etc/passwd"));
+    assert!(!is_stored_name(&format!("../{}", &hash[3..])));
+    assert_eq!(stored_name(&hash, Some("pdf")), format!("{}.pdf", hash));
+    assert_eq!(stored_name(&hash, Some("a b")), hash);
+    assert_eq!(stored_name(&hash, None), hash);
+    
+    // 保存先の外を指す添付は開かない
+    let escape = Attachment::new(AttachmentKind::Stored, "../../etc/passwd".to_string(), None);
+    assert!(escape.validate().is_err());
+    assert_eq!(escape.location(&attachments_dir().unwrap()), None);
+    assert!(Attachment::new(AttachmentKind::File, "relative.pdf".to_string(), None).validate().is_err());
+    assert!(Attachment::new(AttachmentKind::Link, "example.com".to_string(), None).validate().is_err());
+    repo.add_attachment(other, &escape).unwrap();
+    assert!(commands::open_attachment(other, Some(2)).is_err());
+    
+    // どのタスクからも参照されなくなったファイルだけを削除する
+    commands::detach(id, 1).unwrap();
+    assert_eq!(repo.get_task(id).unwrap().attachments.len(), 2);
+    commands::clean_attachments(false).unwrap();
+    assert!(dir.join(&stored.target).exists());
+    repo.delete_task(other).unwrap();
+    commands::clean_attachments(true).unwrap();
+    assert!(dir.join(&stored.target).exists());
+    commands::clean_attachments(false).unwrap();
+    assert!(!dir.join(&stored.target).exists());
+    
+    // JSON のエクスポート・インポートで保たれる
+    let export_path = temp_dir.path().join("attachments.json").to_str().unwrap().to_string();
+    commands::export_tasks(export_path.clone(), "json".to_string()).unwrap();
+    let before = repo.get_task(id).unwrap().attachments;
+    repo.delete_task(id).unwrap();
+    commands::import_tasks(export_path.clone()).unwrap();
+    let imported = repo.get_all_tasks(true).unwrap();
+    assert_eq!(imported[0].attachments.len(), 2);
+    assert_eq!(imported[0].attachments[1].target, before[1].target);
+    assert_eq!(imported[0].attachments[1].added_at.timestamp(), before[1].added_at.timestamp());
+    
+    // インポートでは種類に合わない添付を取り込まない
+    let crafted_path = temp_dir.path().join("crafted.json");
+    let crafted = std::fs::read_to_string(&export_path).unwrap()
+        .replace(&format!("\"target\": \"{}\"", before[0].target), "\"target\": \"../../etc/passwd\"");
+    assert!(crafted.contains("../../etc/passwd"));
+    std::fs::write(&crafted_path, crafted).unwrap();
+    repo.delete_task(imported[0].id.unwrap()).unwrap();
+    commands::import_tasks(crafted_path.to_str().unwrap().to_string()).unwrap();
+    let imported = repo.get_all_tasks(true).unwrap();
+    assert_eq!(imported[0].attachments.len(), 1);
+    assert_eq!(imported[0].attachments[0].kind, AttachmentKind::Link);
+    
+    teardown();
+    drop(temp_dir);
+}
+
+#[test]
+fn test_projects() {
+    use task_manager_rs::models::task::parse_project;
+    use task_manager_rs::utils::filter::TaskFilter;
+    
+    let _guard = lock_db();
+    let (temp_dir, _) = setup_test_db();
+    let mut repo = TaskRepository::new().unwrap();
+    
+    // 追加・更新で設定し、空の値はプロジェクトなし
+    assert_eq!(parse_project(" alpha "), Some("alpha".to_string()));
+    assert_eq!(parse_project("  "), None);
+    commands::add_task("設計".to_string(), None, None, 2, None, Some("alpha".to_string()), vec![], None, None, None, None).unwrap();
+    let id = repo.get_all_tasks(true).unwrap()[0].id.unwrap();
+    assert_eq!(repo.get_task(id).unwrap().project.as_deref(), Some("alpha"));
+    let mut task = repo.get_task(id).unwrap();
+    task.project = Some("beta".to_string());
+    repo.update_task(&task).unwrap();
+    assert_eq!(repo.get_task(id).unwrap().project.as_deref(), Some("beta"));
+    let other = repo.add_task(&Task::new("買い物".to_string(), None, None, Priority::Low, vec![])).unwrap();
+    assert!(repo.get_task(other).unwrap().project.is_none());
+    
+    // project: の条件
+    let filter = TaskFilter::parse("project:beta", &[]).unwrap();
+    let tasks = repo.get_all_tasks(true).unwrap();
+    assert_eq!(tasks.iter().filter(|t| filter.matches(t)).map(|t| t.id.unwrap()).collect::<Vec<_>>(), vec![id]);
+    assert!(TaskFilter::parse("project:", &[]).is_err());
+    
+    // 表の列・テンプレートのフィールド
+    let columns = TableColumn::parse_list("id,project", &[]).unwrap();
+    let lines = format_table(&tasks, &columns, None, 80);
+    assert!(lines.iter().any(|line| line.contains("beta")));
+    let template = Template::parse("{id}:{project}", &[]).unwrap();
+    assert_eq!(template.render(&repo.get_task(id).unwrap()), format!("{}:beta", id));
+    
+    // 作業時間のレポートはプロジェクト別にも集計する
+    let started_at = Local::now() - Duration::hours(2);
+    repo.add_time_entry(&TimeEntry::new(id, started_at, Some(started_at + Duration::hours(1)))).unwrap();
+    commands::report_time(false, None, None).unwrap();
+    
+    // 見積もりのレポートもプロジェクト別に集計する
+    let mut task = repo.get_task(id).unwrap();
+    task.estimate_minutes = Some(30);
+    repo.update_task(&task).unwrap();
+    commands::report_estimate(true).unwrap();
+    
+    teardown();
+    drop(temp_dir);
+}